@@ -0,0 +1,117 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use embedded_audio_tools::block::{dot, scale, Fir};
+use embedded_audio_tools::memory_access::from_slice_mut;
+
+const SIZES: [usize; 3] = [16, 64, 256];
+
+// The scalar paths the block kernels replaced
+
+#[inline(never)]
+fn dot_scalar(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[inline(never)]
+fn scale_scalar(block: &mut [f32], gain: f32) {
+    for sample in block {
+        *sample *= gain;
+    }
+}
+
+struct FirScalar<const N: usize> {
+    coeffs: [f32; N],
+    history: [f32; N],
+    pos: usize,
+}
+
+impl<const N: usize> FirScalar<N> {
+    #[inline(never)]
+    fn process(&mut self, sample: f32) -> f32 {
+        self.history[self.pos] = sample;
+
+        let mut out = 0.0;
+        for (i, coeff) in self.coeffs.iter().enumerate() {
+            out += coeff * self.history[(self.pos + N - i) % N];
+        }
+
+        self.pos = (self.pos + 1) % N;
+        out
+    }
+}
+
+fn signal(len: usize) -> Vec<f32> {
+    (0..len).map(|i| (i as f32 * 0.1).sin()).collect()
+}
+
+fn bench_dot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Dot Product");
+
+    for size in SIZES {
+        let a = signal(size);
+        let b = signal(size);
+
+        group.bench_with_input(BenchmarkId::new("Scalar", size), &size, |bench, _| {
+            bench.iter(|| dot_scalar(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("Kernel", size), &size, |bench, _| {
+            bench.iter(|| dot(black_box(&a), black_box(&b)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_scale(c: &mut Criterion) {
+    // Unity gain, so the block doesn't decay into subnormals over the iterations
+    let mut group = c.benchmark_group("Gain");
+
+    for size in SIZES {
+        let mut block = signal(size);
+
+        group.bench_with_input(BenchmarkId::new("Scalar", size), &size, |bench, _| {
+            bench.iter(|| scale_scalar(black_box(&mut block), black_box(1.0)))
+        });
+        group.bench_with_input(BenchmarkId::new("Kernel", size), &size, |bench, _| {
+            bench.iter(|| scale(black_box(&mut block), black_box(1.0)))
+        });
+
+        let mut slice = from_slice_mut(&mut block);
+        group.bench_with_input(BenchmarkId::new("MemorySlice", size), &size, |bench, _| {
+            bench.iter(|| slice.scale(black_box(1.0)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_fir(c: &mut Criterion) {
+    let mut group = c.benchmark_group("FIR 32 Taps");
+    let coeffs: [f32; 32] = core::array::from_fn(|i| 1.0 / (i + 1) as f32);
+
+    let mut scalar = FirScalar {
+        coeffs,
+        history: [0.0; 32],
+        pos: 0,
+    };
+    let mut fir = Fir::new(coeffs);
+
+    for size in SIZES {
+        let mut block = signal(size);
+
+        group.bench_with_input(BenchmarkId::new("Scalar", size), &size, |bench, _| {
+            bench.iter(|| {
+                for sample in block.iter_mut() {
+                    *sample = scalar.process(black_box(*sample));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("Kernel", size), &size, |bench, _| {
+            bench.iter(|| fir.process_block(black_box(&mut block)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dot, bench_scale, bench_fir);
+criterion_main!(benches);
@@ -4,7 +4,9 @@
 use crate::delay_line::DelayLine;
 use crate::memory::{memory_slice::MemorySlice, Mutable};
 
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct AllPass {
     delay_line: DelayLine,
 }
@@ -1,11 +1,10 @@
-use crate::float::AdditionalF32Ext;
+use crate::float::Float;
 
 use super::BiquadCoeffs;
-use core::{f32::consts::PI, marker::PhantomData, ops::Neg};
-
-#[allow(unused_imports)]
-use micromath::F32Ext;
+use core::marker::PhantomData;
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub enum ButterworthType {
     Lowpass = 0,
     Highpass = 1,
@@ -13,113 +12,130 @@ pub enum ButterworthType {
     Notch = 3,
     Bell = 4,
     LowShelf = 5,
+    Bandpass = 6,
 }
 
 /// Coeffiecients based on this article: https://www.musicdsp.org/en/latest/Filters/37-zoelzer-biquad-filters.html
 ///
 /// Uses tan instead of cos and sin to calculate coefficients
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub struct Butterworth;
 
-impl BiquadCoeffs<Butterworth> {
-    pub fn new() -> BiquadCoeffs<Butterworth> {
+impl<F: Float> BiquadCoeffs<Butterworth, F> {
+    pub fn new() -> BiquadCoeffs<Butterworth, F> {
         BiquadCoeffs {
-            b0: 0.0,
-            b1: 0.0,
-            b2: 0.0,
-            a1: 0.0,
-            a2: 0.0,
+            b0: F::ZERO,
+            b1: F::ZERO,
+            b2: F::ZERO,
+            a1: F::ZERO,
+            a2: F::ZERO,
             state: PhantomData,
         }
     }
 
     #[inline(always)]
-    fn setup_coeffs(&self, fc: f32, q: f32, sr: f32) -> (f32, f32) {
-        let k = ((PI * fc) / sr).fast_tan();
+    fn setup_coeffs(&self, fc: F, q: F, sr: F) -> (F, F) {
+        let k = ((F::PI * fc) / sr).tan();
         (k * k, k / q)
     }
 
-    pub fn lowpass(&mut self, fc: f32, q: f32, sr: f32) {
+    pub fn lowpass(&mut self, fc: F, q: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
         self.b0 = norm * k2;
-        self.b1 = norm * 2.0 * k2;
+        self.b1 = norm * F::TWO * k2;
         self.b2 = self.b0;
-        self.a1 = norm * 2.0 * (k2 - 1.0);
-        self.a2 = norm * (1.0 - k_q + k2);
+        self.a1 = norm * F::TWO * (k2 - F::ONE);
+        self.a2 = norm * (F::ONE - k_q + k2);
     }
 
-    pub fn highpass(&mut self, fc: f32, q: f32, sr: f32) {
+    pub fn highpass(&mut self, fc: F, q: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
         self.b0 = norm;
-        self.b1 = norm * -2.0;
+        self.b1 = norm * -F::TWO;
         self.b2 = norm;
-        self.a1 = norm * (2.0 * (k2 - 1.0));
-        self.a2 = norm * (1.0 - k_q + k2)
+        self.a1 = norm * (F::TWO * (k2 - F::ONE));
+        self.a2 = norm * (F::ONE - k_q + k2)
+    }
+
+    /// Constant 0 dB peak gain at `fc`
+    pub fn bandpass(&mut self, fc: F, q: F, sr: F) {
+        let (k2, k_q) = self.setup_coeffs(fc, q, sr);
+
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
+
+        self.b0 = norm * k_q;
+        self.b1 = F::ZERO;
+        self.b2 = -self.b0;
+        self.a1 = norm * (F::TWO * (k2 - F::ONE));
+        self.a2 = norm * (F::ONE - k_q + k2);
     }
 
-    pub fn allpass(&mut self, fc: f32, q: f32, sr: f32) {
+    pub fn allpass(&mut self, fc: F, q: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
-        self.b0 = norm * (1.0 - k_q + k2);
-        self.b1 = norm * (2.0 * (k2 - 1.0));
-        self.b2 = 1.0;
+        self.b0 = norm * (F::ONE - k_q + k2);
+        self.b1 = norm * (F::TWO * (k2 - F::ONE));
+        self.b2 = F::ONE;
         self.a1 = self.b1;
         self.a2 = self.b0;
     }
 
-    pub fn notch(&mut self, fc: f32, q: f32, sr: f32) {
+    pub fn notch(&mut self, fc: F, q: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
-        self.b0 = norm * (1.0 + k2);
-        self.b1 = norm * (2.0 * (k2 - 1.0));
+        self.b0 = norm * (F::ONE + k2);
+        self.b1 = norm * (F::TWO * (k2 - F::ONE));
         self.b2 = self.b0;
         self.a1 = self.b1;
-        self.a2 = norm * (1.0 - k_q + k2);
+        self.a2 = norm * (F::ONE - k_q + k2);
     }
 
     #[inline(always)]
-    pub fn bell_boost_only(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn bell_boost_only(&mut self, fc: F, q: F, gain: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
-        self.b0 = norm * (1.0 + gain * k_q + k2);
-        self.b1 = norm * (2.0 * (k2 - 1.0));
-        self.b2 = norm * (1.0 - gain * k_q + k2);
+        self.b0 = norm * (F::ONE + gain * k_q + k2);
+        self.b1 = norm * (F::TWO * (k2 - F::ONE));
+        self.b2 = norm * (F::ONE - gain * k_q + k2);
         self.a1 = self.b1;
-        self.a2 = norm * (1.0 - k_q + k2);
+        self.a2 = norm * (F::ONE - k_q + k2);
     }
 
     #[inline(always)]
-    pub fn bell_cut_only(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn bell_cut_only(&mut self, fc: F, q: F, gain: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
         let gain = gain.neg();
-        let a0 = 1.0 + gain * k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + gain * k_q + k2;
+        let norm = F::ONE / a0;
 
-        self.b0 = norm * (1.0 + k_q + k2);
-        self.b1 = norm * (2.0 * (k2 - 1.0));
-        self.b2 = norm * (1.0 - k_q + k2);
+        self.b0 = norm * (F::ONE + k_q + k2);
+        self.b1 = norm * (F::TWO * (k2 - F::ONE));
+        self.b2 = norm * (F::ONE - k_q + k2);
         self.a1 = self.b1;
-        self.a2 = norm * (1.0 - gain * k_q + k2);
+        self.a2 = norm * (F::ONE - gain * k_q + k2);
     }
 
-    pub fn bell(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn bell(&mut self, fc: F, q: F, gain: F, sr: F) {
         if gain.is_sign_positive() {
             self.bell_boost_only(fc, q, gain, sr);
         } else {
@@ -128,35 +144,35 @@ impl BiquadCoeffs<Butterworth> {
     }
 
     #[inline(always)]
-    pub fn low_shelf_boost_only(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn low_shelf_boost_only(&mut self, fc: F, q: F, gain: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
-        let a0 = 1.0 + k_q + k2;
-        let norm = 1.0 / a0;
+        let a0 = F::ONE + k_q + k2;
+        let norm = F::ONE / a0;
 
-        self.b0 = norm * (1.0 + (gain * 2.0 * k2).sqrt() + gain * k2);
-        self.b1 = norm * (2.0 * (gain * k2 - 1.0));
-        self.b2 = norm * (1.0 - (gain * 2.0 * k2).sqrt() + gain * k2);
-        self.a1 = norm * (2.0 * (k2 - 1.0));
-        self.a2 = norm * (1.0 - k_q + k2);
+        self.b0 = norm * (F::ONE + (gain * F::TWO * k2).sqrt() + gain * k2);
+        self.b1 = norm * (F::TWO * (gain * k2 - F::ONE));
+        self.b2 = norm * (F::ONE - (gain * F::TWO * k2).sqrt() + gain * k2);
+        self.a1 = norm * (F::TWO * (k2 - F::ONE));
+        self.a2 = norm * (F::ONE - k_q + k2);
     }
 
     #[inline(always)]
-    pub fn low_shelf_cut_only(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn low_shelf_cut_only(&mut self, fc: F, q: F, gain: F, sr: F) {
         let (k2, k_q) = self.setup_coeffs(fc, q, sr);
 
         let gain = gain.neg();
-        let a0 = 1.0 + (gain * 2.0 * k2).sqrt() + gain * k2;
-        let norm = 1.0 / a0;
-
-        self.b0 = norm * (1.0 + k_q + k2);
-        self.b1 = norm * (2.0 * (k2 - 1.0));
-        self.b2 = norm * (1.0 - k_q + k2);
-        self.a1 = norm * (2.0 * (gain * k2 - 1.0));
-        self.a2 = norm * (1.0 - (gain * 2.0 * k2).sqrt() + gain * k2);
+        let a0 = F::ONE + (gain * F::TWO * k2).sqrt() + gain * k2;
+        let norm = F::ONE / a0;
+
+        self.b0 = norm * (F::ONE + k_q + k2);
+        self.b1 = norm * (F::TWO * (k2 - F::ONE));
+        self.b2 = norm * (F::ONE - k_q + k2);
+        self.a1 = norm * (F::TWO * (gain * k2 - F::ONE));
+        self.a2 = norm * (F::ONE - (gain * F::TWO * k2).sqrt() + gain * k2);
     }
 
-    pub fn low_shelf(&mut self, fc: f32, q: f32, gain: f32, sr: f32) {
+    pub fn low_shelf(&mut self, fc: F, q: F, gain: F, sr: F) {
         if gain.is_sign_positive() {
             self.low_shelf_boost_only(fc, q, gain, sr);
         } else {
@@ -164,3 +180,69 @@ impl BiquadCoeffs<Butterworth> {
         }
     }
 }
+
+impl<F: Float> Default for BiquadCoeffs<Butterworth, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "double")]
+    use crate::biquad::Biquad;
+    use core::f32::consts::PI;
+
+    #[test]
+    fn accurate_near_nyquist() {
+        let coeffs: BiquadCoeffs<Butterworth> = BiquadCoeffs::new();
+        let (k2, _) = coeffs.setup_coeffs(22_000.0, 1.0, 48_000.0);
+
+        let exact = (core::f64::consts::PI * 22_000.0 / 48_000.0).tan().powi(2);
+        assert!(((k2 as f64 - exact) / exact).abs() < 1e-4);
+    }
+
+    #[cfg(feature = "double")]
+    #[test]
+    fn double_precision() {
+        let mut single: BiquadCoeffs<Butterworth> = BiquadCoeffs::new();
+        let mut double: Biquad<Butterworth, f64> = Biquad::new(BiquadCoeffs::new());
+
+        single.lowpass(1000.0, 0.7, 48_000.0);
+        double.coeffs.lowpass(1000.0, 0.7, 48_000.0);
+        assert!((single.a1 as f64 - double.coeffs.a1).abs() < 1e-5);
+
+        // A 5 Hz lowpass settles at exactly unity gain for DC
+        double.coeffs.lowpass(5.0, 0.7, 48_000.0);
+        let out = (0..96_000).fold(0.0, |_, _| double.process(1.0));
+        assert!((out - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bandpass_peak() {
+        let mut coeffs: BiquadCoeffs<Butterworth> = BiquadCoeffs::new();
+        coeffs.bandpass(1000.0, 2.0, 48_000.0);
+
+        // Magnitude of the transfer function at `freq`
+        let gain = |freq: f32| {
+            let w = 2.0 * PI * freq / 48_000.0;
+            let (c1, s1, c2, s2) = (w.cos(), w.sin(), (2.0 * w).cos(), (2.0 * w).sin());
+
+            let num = (
+                coeffs.b0 + coeffs.b1 * c1 + coeffs.b2 * c2,
+                coeffs.b1 * s1 + coeffs.b2 * s2,
+            );
+            let den = (
+                1.0 + coeffs.a1 * c1 + coeffs.a2 * c2,
+                coeffs.a1 * s1 + coeffs.a2 * s2,
+            );
+
+            ((num.0 * num.0 + num.1 * num.1) / (den.0 * den.0 + den.1 * den.1)).sqrt()
+        };
+
+        assert!((gain(1000.0) - 1.0).abs() < 1e-3);
+        assert!(gain(100.0) < 0.1);
+        assert!(gain(10_000.0) < 0.1);
+    }
+}
@@ -1,20 +1,42 @@
 use core::marker::PhantomData;
 
+use crate::float::Float;
+
 pub mod butterworth;
+pub mod smoothed;
 
-pub struct BiquadCoeffs<T> {
-    pub b0: f32,
-    pub b1: f32,
-    pub b2: f32,
-    pub a1: f32,
-    pub a2: f32,
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct BiquadCoeffs<T, F = f32> {
+    pub b0: F,
+    pub b1: F,
+    pub b2: F,
+    pub a1: F,
+    pub a2: F,
 
     state: PhantomData<T>,
 }
 
+impl<T, F> BiquadCoeffs<T, F> {
+    /// Coefficients from an external design, normalized to `a0 = 1`
+    pub const fn from_raw(b0: F, b1: F, b2: F, a1: F, a2: F) -> BiquadCoeffs<T, F> {
+        BiquadCoeffs {
+            b0,
+            b1,
+            b2,
+            a1,
+            a2,
+            state: PhantomData,
+        }
+    }
+}
+
 /**
 Little suite of filters in a `Biquad` topology.
 
+`F` is the sample type, `f32` by default or `f64` with the `double` feature.
+
 ### Example
 
 ```rust
@@ -29,24 +51,27 @@ biquad.coeffs.lowpass(1000.0, 1.0, 48_000.0); // Cutoff = 1kHz, Q = 1.0, fs = 48
 biquad.process(1.0); // process a sample
 ```
 */
-pub struct Biquad<T> {
-    z1: f32,
-    z2: f32,
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Biquad<T, F = f32> {
+    z1: F,
+    z2: F,
 
-    pub coeffs: BiquadCoeffs<T>,
+    pub coeffs: BiquadCoeffs<T, F>,
 }
 
-impl<T> Biquad<T> {
-    pub fn new(filter_type: BiquadCoeffs<T>) -> Biquad<T> {
+impl<T, F: Float> Biquad<T, F> {
+    pub fn new(filter_type: BiquadCoeffs<T, F>) -> Biquad<T, F> {
         Biquad {
-            z1: 0.0,
-            z2: 0.0,
+            z1: F::ZERO,
+            z2: F::ZERO,
 
             coeffs: filter_type,
         }
     }
 
-    pub fn process(&mut self, input: f32) -> f32 {
+    pub fn process(&mut self, input: F) -> F {
         let out = self.coeffs.b0 * input + self.z1;
 
         self.z1 = self.coeffs.b1 * input + self.z2 - self.coeffs.a1 * out;
@@ -54,4 +79,19 @@ impl<T> Biquad<T> {
 
         out
     }
+
+    /// Clears the filter state, the coefficients stay
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.z1 = F::ZERO;
+        self.z2 = F::ZERO;
+    }
+
+    /// Flushes subnormal filter state to zero. Calling this once per block is
+    /// enough to prevent CPU spikes while the filter rings out into silence.
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.z1 = self.z1.flush_denormal();
+        self.z2 = self.z2.flush_denormal();
+    }
 }
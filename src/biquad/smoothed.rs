@@ -0,0 +1,175 @@
+use super::{
+    butterworth::{Butterworth, ButterworthType},
+    Biquad, BiquadCoeffs,
+};
+use crate::smoothed_param::{SmoothedParam, Smoothing};
+
+/**
+`Biquad` which keeps its design parameters, so the cutoff can glide to new values
+without zipper noise. The coefficients get recalculated once per block while the cutoff
+moves.
+
+### Example
+
+```rust
+use embedded_audio_tools::filter::{butterworth::ButterworthType, SmoothedBiquad};
+
+let mut filter = SmoothedBiquad::new(ButterworthType::Lowpass, 1000.0, 0.7, 48_000.0);
+filter.set_cutoff_smoothed(4000.0, 20.0);
+
+// during audio callback
+let mut block = [0.5; 32];
+filter.process_block(&mut block);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct SmoothedBiquad {
+    biquad: Biquad<Butterworth>,
+    filter_type: ButterworthType,
+    cutoff: SmoothedParam,
+    q: f32,
+    gain: f32,
+    sr: f32,
+}
+
+impl SmoothedBiquad {
+    /// Starts with a `gain` of 1, which only matters for bells and shelves
+    pub fn new(filter_type: ButterworthType, fc: f32, q: f32, sr: f32) -> SmoothedBiquad {
+        let mut filter = SmoothedBiquad {
+            biquad: Biquad::new(BiquadCoeffs::new()),
+            filter_type,
+            cutoff: SmoothedParam::new(fc, 0.0, sr, Smoothing::Linear),
+            q,
+            gain: 1.0,
+            sr,
+        };
+
+        filter.update_coeffs();
+        filter
+    }
+
+    /// Jumps to `fc`, stopping any glide
+    #[inline(always)]
+    pub fn set_cutoff(&mut self, fc: f32) {
+        self.cutoff.set_immediate(fc);
+        self.update_coeffs();
+    }
+
+    /// Glides linearly to `fc` within `time_ms`, applied by `update_smoothed`
+    #[inline(always)]
+    pub fn set_cutoff_smoothed(&mut self, fc: f32, time_ms: f32) {
+        self.cutoff.set_time(time_ms);
+        self.cutoff.set_target(fc);
+    }
+
+    #[inline(always)]
+    pub fn set_q(&mut self, q: f32) {
+        self.q = q;
+        self.update_coeffs();
+    }
+
+    #[inline(always)]
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+        self.update_coeffs();
+    }
+
+    #[inline(always)]
+    pub fn set_filter_type(&mut self, filter_type: ButterworthType) {
+        self.filter_type = filter_type;
+        self.update_coeffs();
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.cutoff.set_sr_unchecked(sr);
+        self.update_coeffs();
+    }
+
+    /// Current, possibly still gliding cutoff
+    #[inline(always)]
+    pub fn cutoff(&self) -> f32 {
+        self.cutoff.value()
+    }
+
+    /// Advances the cutoff glide by `samples`, call once per block.
+    /// `process_block` does this on its own.
+    pub fn update_smoothed(&mut self, samples: usize) {
+        if self.cutoff.is_smoothing() {
+            self.cutoff.skip(samples);
+            self.update_coeffs();
+        }
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, input: f32) -> f32 {
+        self.biquad.process(input)
+    }
+
+    /// Processes `block` in place, with the cutoff advanced by the length of the block
+    pub fn process_block(&mut self, block: &mut [f32]) {
+        self.update_smoothed(block.len());
+
+        for sample in block {
+            *sample = self.biquad.process(*sample);
+        }
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.biquad.flush_denormals();
+    }
+
+    fn update_coeffs(&mut self) {
+        let (fc, q, gain, sr) = (self.cutoff.value(), self.q, self.gain, self.sr);
+        let coeffs = &mut self.biquad.coeffs;
+
+        match self.filter_type {
+            ButterworthType::Lowpass => coeffs.lowpass(fc, q, sr),
+            ButterworthType::Highpass => coeffs.highpass(fc, q, sr),
+            ButterworthType::Allpass => coeffs.allpass(fc, q, sr),
+            ButterworthType::Bandpass => coeffs.bandpass(fc, q, sr),
+            ButterworthType::Notch => coeffs.notch(fc, q, sr),
+            ButterworthType::Bell => coeffs.bell(fc, q, gain, sr),
+            ButterworthType::LowShelf => coeffs.low_shelf(fc, q, gain, sr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn cutoff_glide() {
+        let mut filter = SmoothedBiquad::new(ButterworthType::Lowpass, 1000.0, 0.7, SR);
+        filter.set_cutoff_smoothed(2000.0, 1.0);
+
+        let mut block = [0.0; 24];
+        filter.process_block(&mut block);
+        assert!((filter.cutoff() - 1500.0).abs() < 0.1);
+
+        filter.process_block(&mut block);
+        assert_eq!(filter.cutoff(), 2000.0);
+
+        let mut reference: BiquadCoeffs<Butterworth> = BiquadCoeffs::new();
+        reference.lowpass(2000.0, 0.7, SR);
+        assert_eq!(filter.biquad.coeffs.b0, reference.b0);
+        assert_eq!(filter.biquad.coeffs.a1, reference.a1);
+    }
+
+    #[test]
+    fn immediate_cutoff() {
+        let mut filter = SmoothedBiquad::new(ButterworthType::Highpass, 1000.0, 0.7, SR);
+        filter.set_cutoff_smoothed(2000.0, 10.0);
+        filter.set_cutoff(500.0);
+        filter.update_smoothed(48);
+
+        assert_eq!(filter.cutoff(), 500.0);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::biquad::BiquadCoeffs;
+use crate::float::flush_denormal;
+
+#[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+use super::cmsis;
+
+/**
+`N` biquads in series with their coefficients and states packed into arrays, so a
+whole block runs through one section after the other.
+
+Uses the same transposed direct form II as `Biquad`, which is also the memory layout
+`arm_biquad_cascade_df2T_f32` of CMSIS-DSP expects.
+
+### Example
+
+```rust
+use embedded_audio_tools::block::BiquadCascade;
+use embedded_audio_tools::filter::{BiquadCoeffs, Butterworth};
+
+// 4th order lowpass
+let mut coeffs: BiquadCoeffs<Butterworth> = BiquadCoeffs::new();
+let mut cascade: BiquadCascade<2> = BiquadCascade::new();
+
+coeffs.lowpass(1000.0, 0.541, 48_000.0);
+cascade.set_section(0, &coeffs);
+
+coeffs.lowpass(1000.0, 1.307, 48_000.0);
+cascade.set_section(1, &coeffs);
+
+// during audio callback
+let mut block = [1.0; 32];
+cascade.process_block(&mut block);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct BiquadCascade<const N: usize> {
+    /// b0, b1, b2, -a1, -a2 for each section
+    coeffs: [[f32; 5]; N],
+    state: [[f32; 2]; N],
+}
+
+impl<const N: usize> BiquadCascade<N> {
+    /// All sections pass the signal through untouched
+    pub const fn new() -> BiquadCascade<N> {
+        assert!(N <= u8::MAX as usize, "At most 255 sections are supported");
+
+        BiquadCascade {
+            coeffs: [[1.0, 0.0, 0.0, 0.0, 0.0]; N],
+            state: [[0.0; 2]; N],
+        }
+    }
+
+    /// Ignored if `index` is out of range
+    #[inline]
+    pub fn set_section<T>(&mut self, index: usize, coeffs: &BiquadCoeffs<T>) {
+        if let Some(section) = self.coeffs.get_mut(index) {
+            *section = [coeffs.b0, coeffs.b1, coeffs.b2, -coeffs.a1, -coeffs.a2];
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = [[0.0; 2]; N];
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let mut x = sample;
+
+        for (c, z) in self.coeffs.iter().zip(self.state.iter_mut()) {
+            let out = c[0] * x + z[0];
+
+            z[0] = c[1] * x + z[1] + c[3] * out;
+            z[1] = c[2] * x + c[4] * out;
+
+            x = out;
+        }
+
+        x
+    }
+
+    /// Processes `block` in place, one section after the other
+    pub fn process_block(&mut self, block: &mut [f32]) {
+        #[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+        unsafe {
+            let instance = cmsis::arm_biquad_cascade_df2T_instance_f32 {
+                num_stages: N as u8,
+                p_state: self.state.as_mut_ptr() as *mut f32,
+                p_coeffs: self.coeffs.as_ptr() as *const f32,
+            };
+
+            cmsis::arm_biquad_cascade_df2T_f32(
+                &instance,
+                block.as_ptr(),
+                block.as_mut_ptr(),
+                block.len() as u32,
+            );
+        }
+
+        #[cfg(not(all(feature = "arm-dsp", target_arch = "arm")))]
+        for (c, z) in self.coeffs.iter().zip(self.state.iter_mut()) {
+            for sample in block.iter_mut() {
+                let x = *sample;
+                let out = c[0] * x + z[0];
+
+                z[0] = c[1] * x + z[1] + c[3] * out;
+                z[1] = c[2] * x + c[4] * out;
+
+                *sample = out;
+            }
+        }
+    }
+
+    /// Flushes subnormal filter states to zero, once per block is enough
+    #[inline]
+    pub fn flush_denormals(&mut self) {
+        for z in self.state.iter_mut() {
+            z[0] = flush_denormal(z[0]);
+            z[1] = flush_denormal(z[1]);
+        }
+    }
+}
+
+impl<const N: usize> Default for BiquadCascade<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::biquad::{butterworth::Butterworth, Biquad};
+
+    fn sections() -> [BiquadCoeffs<Butterworth>; 2] {
+        let mut lowpass = BiquadCoeffs::new();
+        lowpass.lowpass(2000.0, 0.7, 48_000.0);
+
+        let mut bell = BiquadCoeffs::new();
+        bell.bell(500.0, 1.0, 6.0, 48_000.0);
+
+        [lowpass, bell]
+    }
+
+    #[test]
+    fn matches_single_biquads() {
+        let [lowpass, bell] = sections();
+
+        let mut cascade: BiquadCascade<2> = BiquadCascade::new();
+        cascade.set_section(0, &lowpass);
+        cascade.set_section(1, &bell);
+
+        let mut first = Biquad::new(lowpass);
+        let mut second = Biquad::new(bell);
+
+        let mut block = [0.0; 64];
+        block[0] = 1.0;
+
+        let mut other = cascade;
+        let expected: [f32; 64] = core::array::from_fn(|i| second.process(first.process(block[i])));
+
+        cascade.process_block(&mut block);
+
+        for (i, sample) in block.iter().enumerate() {
+            assert!((sample - expected[i]).abs() < 1e-6);
+            assert_eq!(*sample, other.process(if i == 0 { 1.0 } else { 0.0 }));
+        }
+    }
+
+    #[test]
+    fn passthrough_by_default() {
+        let mut cascade: BiquadCascade<3> = BiquadCascade::default();
+        let mut block = [0.5, -0.25, 1.0];
+
+        cascade.set_section(3, &sections()[0]);
+        cascade.process_block(&mut block);
+
+        assert_eq!(block, [0.5, -0.25, 1.0]);
+    }
+}
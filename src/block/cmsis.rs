@@ -0,0 +1,39 @@
+//! Bindings to the CMSIS-DSP functions used by the block operations.
+//!
+//! Instance structs mirror the C definitions in `filtering_functions.h`.
+
+#![allow(non_camel_case_types, non_snake_case)]
+
+#[repr(C)]
+pub struct arm_biquad_cascade_df2T_instance_f32 {
+    pub num_stages: u8,
+    pub p_state: *mut f32,
+    pub p_coeffs: *const f32,
+}
+
+extern "C" {
+    pub fn arm_copy_f32(p_src: *const f32, p_dst: *mut f32, block_size: u32);
+
+    pub fn arm_scale_f32(p_src: *const f32, scale: f32, p_dst: *mut f32, block_size: u32);
+
+    pub fn arm_dot_prod_f32(
+        p_src_a: *const f32,
+        p_src_b: *const f32,
+        block_size: u32,
+        result: *mut f32,
+    );
+
+    pub fn arm_dot_prod_q15(
+        p_src_a: *const i16,
+        p_src_b: *const i16,
+        block_size: u32,
+        result: *mut i64,
+    );
+
+    pub fn arm_biquad_cascade_df2T_f32(
+        s: *const arm_biquad_cascade_df2T_instance_f32,
+        p_src: *const f32,
+        p_dst: *mut f32,
+        block_size: u32,
+    );
+}
@@ -0,0 +1,119 @@
+use super::dot_ring;
+
+/**
+FIR filter with `N` taps.
+
+The history lives in a ring buffer and the taps are stored in reversed order, so every
+output sample boils down to two contiguous `dot` products.
+
+### Example
+
+```rust
+use embedded_audio_tools::block::Fir;
+
+// 4 sample moving average
+let mut fir = Fir::new([0.25; 4]);
+
+let mut block = [1.0; 6];
+fir.process_block(&mut block);
+
+assert_eq!(block, [0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Fir<const N: usize> {
+    /// h[N - 1] down to h[0]
+    taps: [f32; N],
+    history: [f32; N],
+    pos: usize,
+}
+
+impl<const N: usize> Fir<N> {
+    /// `coeffs[0]` is the tap for the current sample
+    pub const fn new(coeffs: [f32; N]) -> Fir<N> {
+        assert!(N > 0, "A FIR filter needs at least one tap");
+
+        Fir {
+            taps: reversed(coeffs),
+            history: [0.0; N],
+            pos: 0,
+        }
+    }
+
+    #[inline]
+    pub fn set_coeffs(&mut self, coeffs: [f32; N]) {
+        self.taps = reversed(coeffs);
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.history = [0.0; N];
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        self.history[self.pos] = sample;
+
+        // The oldest sample sits right after the newest one
+        let oldest = if self.pos + 1 == N { 0 } else { self.pos + 1 };
+        let out = dot_ring(&self.taps, &self.history, oldest);
+
+        self.pos = oldest;
+        out
+    }
+
+    /// Processes `block` in place
+    #[inline]
+    pub fn process_block(&mut self, block: &mut [f32]) {
+        for sample in block {
+            *sample = self.process(*sample);
+        }
+    }
+}
+
+const fn reversed<const N: usize>(coeffs: [f32; N]) -> [f32; N] {
+    let mut taps = [0.0; N];
+
+    let mut i = 0;
+    while i < N {
+        taps[i] = coeffs[N - 1 - i];
+        i += 1;
+    }
+
+    taps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impulse_response() {
+        let coeffs = [0.5, -0.25, 0.125, 1.0, 2.0];
+        let mut fir = Fir::new(coeffs);
+
+        // More than one lap around the ring buffer
+        for round in 0..3 {
+            for (i, coeff) in coeffs.iter().enumerate() {
+                let input = if i == 0 { 1.0 } else { 0.0 };
+                assert_eq!(fir.process(input), *coeff, "round {}", round);
+            }
+        }
+    }
+
+    #[test]
+    fn convolution() {
+        let mut fir = Fir::new([1.0, 2.0, 3.0]);
+        let mut block = [1.0, 0.0, -1.0, 2.0, 0.0];
+
+        fir.process_block(&mut block);
+        assert_eq!(block, [1.0, 2.0, 2.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn single_tap() {
+        let mut fir = Fir::new([0.5]);
+        assert_eq!(fir.process(2.0), 1.0);
+        assert_eq!(fir.process(-1.0), -0.5);
+    }
+}
@@ -0,0 +1,143 @@
+//! 4-wide kernels behind the block operations.
+//!
+//! With the `simd` feature (nightly only) they use `core::simd`, otherwise the loops are
+//! unrolled by hand into four independent accumulators. Either way the FPU pipeline
+//! doesn't stall on a single accumulator and the compiler is free to vectorize.
+
+#[cfg(feature = "simd")]
+use core::simd::{f32x4, num::SimdFloat};
+
+/// Sum of the products of `a` and `b`, both have to be of the same length
+#[inline]
+pub(crate) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len());
+
+    let mut a4 = a.chunks_exact(4);
+    let mut b4 = b.chunks_exact(4);
+
+    #[cfg(feature = "simd")]
+    let mut sum = {
+        let mut acc = f32x4::splat(0.0);
+
+        for (x, y) in (&mut a4).zip(&mut b4) {
+            acc += f32x4::from_slice(x) * f32x4::from_slice(y);
+        }
+
+        acc.reduce_sum()
+    };
+
+    #[cfg(not(feature = "simd"))]
+    let mut sum = {
+        let mut acc = [0.0; 4];
+
+        for (x, y) in (&mut a4).zip(&mut b4) {
+            acc[0] += x[0] * y[0];
+            acc[1] += x[1] * y[1];
+            acc[2] += x[2] * y[2];
+            acc[3] += x[3] * y[3];
+        }
+
+        (acc[0] + acc[1]) + (acc[2] + acc[3])
+    };
+
+    for (x, y) in a4.remainder().iter().zip(b4.remainder()) {
+        sum += x * y;
+    }
+
+    sum
+}
+
+#[inline]
+pub(crate) fn sum(samples: &[f32]) -> f32 {
+    let mut chunks = samples.chunks_exact(4);
+
+    #[cfg(feature = "simd")]
+    let mut sum = {
+        let mut acc = f32x4::splat(0.0);
+
+        for x in &mut chunks {
+            acc += f32x4::from_slice(x);
+        }
+
+        acc.reduce_sum()
+    };
+
+    #[cfg(not(feature = "simd"))]
+    let mut sum = {
+        let mut acc = [0.0; 4];
+
+        for x in &mut chunks {
+            acc[0] += x[0];
+            acc[1] += x[1];
+            acc[2] += x[2];
+            acc[3] += x[3];
+        }
+
+        (acc[0] + acc[1]) + (acc[2] + acc[3])
+    };
+
+    for x in chunks.remainder() {
+        sum += x;
+    }
+
+    sum
+}
+
+#[inline]
+pub(crate) fn scale(block: &mut [f32], gain: f32) {
+    let mut chunks = block.chunks_exact_mut(4);
+
+    #[cfg(feature = "simd")]
+    {
+        let gain4 = f32x4::splat(gain);
+
+        for x in &mut chunks {
+            (f32x4::from_slice(x) * gain4).copy_to_slice(x);
+        }
+    }
+
+    #[cfg(not(feature = "simd"))]
+    for x in &mut chunks {
+        x[0] *= gain;
+        x[1] *= gain;
+        x[2] *= gain;
+        x[3] *= gain;
+    }
+
+    for x in chunks.into_remainder() {
+        *x *= gain;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp<const N: usize>() -> [f32; N] {
+        core::array::from_fn(|i| i as f32 * 0.5 - 1.0)
+    }
+
+    #[test]
+    fn all_remainders() {
+        let a: [f32; 11] = ramp();
+        let b: [f32; 11] = core::array::from_fn(|i| 1.0 - i as f32 * 0.25);
+
+        for len in 0..a.len() {
+            let (a, b) = (&a[..len], &b[..len]);
+
+            let expected_dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+            let expected_sum: f32 = a.iter().sum();
+
+            assert!((dot(a, b) - expected_dot).abs() < 1e-5);
+            assert!((sum(a) - expected_sum).abs() < 1e-5);
+
+            let mut scaled = [0.0; 11];
+            scaled[..len].copy_from_slice(a);
+            scale(&mut scaled[..len], -2.0);
+
+            for (x, y) in scaled[..len].iter().zip(a) {
+                assert_eq!(*x, y * -2.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+/*!
+Block operations, the hot loops of filters and mixers.
+
+Everything has a portable pure Rust implementation. With the `arm-dsp` feature on
+32-bit ARM targets (Cortex-M4/M7) they are routed to CMSIS-DSP instead, which uses
+the SIMD (`SMLALD`) and FPU multiply-accumulate (`VMLA`) instructions. The application
+has to link the CMSIS-DSP library matching its core then, e.g. `libarm_cortexM4lf_math.a`.
+
+Functions taking two slices only work on the length of the shorter one.
+
+### Example
+
+```rust
+use embedded_audio_tools::block::{dot, scale};
+
+let mut block = [0.5, -0.5, 0.25, 1.0];
+scale(&mut block, 0.5);
+
+assert_eq!(block, [0.25, -0.25, 0.125, 0.5]);
+assert_eq!(dot(&block, &[1.0, 1.0, 0.0, 0.0]), 0.0);
+```
+*/
+
+pub mod biquad_cascade;
+pub mod fir;
+
+pub(crate) mod kernels;
+
+#[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+mod cmsis;
+
+pub use biquad_cascade::BiquadCascade;
+pub use fir::Fir;
+
+use crate::fixed_point::Q15;
+
+/// Copies `src` into `dst`
+#[inline]
+pub fn copy(src: &[f32], dst: &mut [f32]) {
+    let len = src.len().min(dst.len());
+
+    #[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+    unsafe {
+        cmsis::arm_copy_f32(src.as_ptr(), dst.as_mut_ptr(), len as u32);
+    }
+
+    #[cfg(not(all(feature = "arm-dsp", target_arch = "arm")))]
+    dst[..len].copy_from_slice(&src[..len]);
+}
+
+/// Multiplies every sample of `block` with `gain`
+#[inline]
+pub fn scale(block: &mut [f32], gain: f32) {
+    #[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+    unsafe {
+        // CMSIS-DSP explicitly allows in-place operation
+        cmsis::arm_scale_f32(block.as_ptr(), gain, block.as_mut_ptr(), block.len() as u32);
+    }
+
+    #[cfg(not(all(feature = "arm-dsp", target_arch = "arm")))]
+    kernels::scale(block, gain);
+}
+
+/// Sum of the products of `a` and `b`
+#[inline]
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+
+    #[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+    {
+        let mut result = 0.0;
+
+        unsafe {
+            cmsis::arm_dot_prod_f32(a.as_ptr(), b.as_ptr(), len as u32, &mut result);
+        }
+
+        result
+    }
+
+    #[cfg(not(all(feature = "arm-dsp", target_arch = "arm")))]
+    kernels::dot(&a[..len], &b[..len])
+}
+
+/// `dot` over a ring buffer whose oldest sample sits at `oldest`, so `coeffs` are
+/// ordered from the oldest to the newest sample
+#[inline(always)]
+pub(crate) fn dot_ring(coeffs: &[f32], ring: &[f32], oldest: usize) -> f32 {
+    let split = ring.len() - oldest;
+
+    dot(&coeffs[..split], &ring[oldest..]) + dot(&coeffs[split..], &ring[..oldest])
+}
+
+/// Sum of the products of `a` and `b` as 34.30 fixed point number, which can't overflow
+/// for fewer than 2^32 samples
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::block::dot_q15;
+/// use embedded_audio_tools::fixed_point::Q15;
+///
+/// let a = [Q15::from_f32(0.5); 4];
+/// let b = [Q15::from_f32(0.5); 4];
+///
+/// // 4 * 0.25
+/// assert_eq!(dot_q15(&a, &b), 1 << 30);
+/// ```
+#[inline]
+pub fn dot_q15(a: &[Q15], b: &[Q15]) -> i64 {
+    let len = a.len().min(b.len());
+
+    #[cfg(all(feature = "arm-dsp", target_arch = "arm"))]
+    {
+        let mut result = 0;
+
+        unsafe {
+            cmsis::arm_dot_prod_q15(
+                a.as_ptr() as *const i16,
+                b.as_ptr() as *const i16,
+                len as u32,
+                &mut result,
+            );
+        }
+
+        result
+    }
+
+    #[cfg(not(all(feature = "arm-dsp", target_arch = "arm")))]
+    a[..len]
+        .iter()
+        .zip(&b[..len])
+        .map(|(a, b)| a.0 as i64 * b.0 as i64)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_shorter_length() {
+        let mut dst = [0.0; 3];
+
+        copy(&[1.0, 2.0], &mut dst);
+        assert_eq!(dst, [1.0, 2.0, 0.0]);
+
+        copy(&[3.0, 4.0, 5.0, 6.0], &mut dst);
+        assert_eq!(dst, [3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn dot_products() {
+        assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+        assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0]), 4.0);
+        assert_eq!(dot(&[], &[4.0]), 0.0);
+
+        assert_eq!(dot_q15(&[Q15::MIN; 2], &[Q15::MIN; 2]), 1 << 31);
+        assert_eq!(dot_q15(&[Q15::MAX], &[Q15::MIN]), -(i16::MAX as i64) << 15);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::float::AdditionalF32Ext;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Limits `sample` to -1 and 1
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::clipping::hard_clip;
+///
+/// assert_eq!(hard_clip(0.5), 0.5);
+/// assert_eq!(hard_clip(-3.0), -1.0);
+/// ```
+#[inline(always)]
+pub fn hard_clip(sample: f32) -> f32 {
+    sample.clamp(-1.0, 1.0)
+}
+
+/// Hard clip with a quadratic knee, linear up to 1 - `knee_width` and limited to ±1 from
+/// 1 + `knee_width` on
+///
+/// The knee blends both regions without a corner, so the typical safety clipper in front
+/// of a DAC leaves low level material untouched. `knee_width` gets clamped between 0 and 1,
+/// 0 equals `hard_clip`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::clipping::knee_clip;
+///
+/// assert_eq!(knee_clip(0.5, 0.2), 0.5);
+/// assert_eq!(knee_clip(1.0, 0.2), 0.95);
+/// assert_eq!(knee_clip(-3.0, 0.2), -1.0);
+/// ```
+#[inline(always)]
+pub fn knee_clip(sample: f32, knee_width: f32) -> f32 {
+    let width = knee_width.clamp(0.0, 1.0);
+    let abs = sample.abs();
+
+    if abs <= 1.0 - width {
+        return sample;
+    }
+
+    if abs >= 1.0 + width {
+        return 1.0_f32.copysign(sample);
+    }
+
+    let over = abs - (1.0 - width);
+    (abs - over * over / (4.0 * width)).copysign(sample)
+}
+
+/// Cubic soft clip 1.5 * (x - x³/3), normalized to reach ±1 at an input of ±1
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::clipping::cubic_clip;
+///
+/// assert_eq!(cubic_clip(0.0), 0.0);
+/// assert_eq!(cubic_clip(1.0), 1.0);
+/// assert_eq!(cubic_clip(-5.0), -1.0);
+/// ```
+#[inline(always)]
+pub fn cubic_clip(sample: f32) -> f32 {
+    poly_clip(sample, 3)
+}
+
+/// Odd polynomial soft clip (n / (n - 1)) * (x - xⁿ / n), normalized to reach ±1 at an
+/// input of ±1
+///
+/// `hardness` is the odd order n of the polynomial: 3 equals `cubic_clip`, higher orders
+/// keep the signal linear for longer (with a gain of n / (n - 1)) and bend more abruptly,
+/// approaching `hard_clip` as n grows. The slope is zero at ±1, so the knee has no corner
+/// for any order. Orders below 3 are treated as 3, even orders are rounded up.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::clipping::{cubic_clip, poly_clip};
+///
+/// assert_eq!(poly_clip(0.5, 3), cubic_clip(0.5));
+/// assert_eq!(poly_clip(0.5, 8), poly_clip(0.5, 9));
+/// assert_eq!(poly_clip(1.0, 9), 1.0);
+/// assert_eq!(poly_clip(-2.0, 9), -1.0);
+/// ```
+#[inline(always)]
+pub fn poly_clip(sample: f32, hardness: u8) -> f32 {
+    if sample.abs() >= 1.0 {
+        return 1.0_f32.copysign(sample);
+    }
+
+    let n = (hardness.max(3) | 1) as i32;
+    let curve = sample.powi(n) / n as f32;
+
+    (n as f32 / (n - 1) as f32) * (sample - curve)
+}
+
+/// Sigmoid soft clip tanh(k * x) / tanh(k), normalized to reach ±1 at an input of ±1
+///
+/// `steepness` k acts as drive: small values are almost linear, large values approach
+/// `hard_clip`. Beyond ±1 the output keeps rising slightly towards ±1 / tanh(k).
+/// Negative steepness is treated as positive, steepness near 0 returns the input.
+///
+/// ## Harmonics
+/// The curve is odd, so only odd harmonics are generated. It is a gain compensated
+/// `tanh` with an input drive of k, so the spectrum equals that of `tanh(k * x)`:
+/// the harmonics fall off faster than with `poly_clip` at moderate drive, since the curve
+/// is smooth everywhere instead of turning into a flat line at ±1, and they build up
+/// gradually with rising k instead of appearing abruptly once the input exceeds ±1.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::clipping::sigmoid_clip;
+///
+/// assert_eq!(sigmoid_clip(0.0, 2.0), 0.0);
+/// assert!((sigmoid_clip(1.0, 2.0) - 1.0).abs() < 1e-4);
+/// assert!(sigmoid_clip(0.5, 4.0) > sigmoid_clip(0.5, 1.0));
+/// ```
+#[inline(always)]
+pub fn sigmoid_clip(sample: f32, steepness: f32) -> f32 {
+    let k = steepness.abs();
+
+    // tanh(k * x) / tanh(k) -> x for k -> 0
+    if k < 1e-3 {
+        return sample;
+    }
+
+    (k * sample).fast_tanh() / k.fast_tanh()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_shape(clip: impl Fn(f32) -> f32) {
+        let mut last = clip(-10.0);
+
+        for i in -10_000..=10_000 {
+            let x = i as f32 * 0.001;
+            let y = clip(x);
+
+            assert!((-1.0..=1.0).contains(&y), "x: {}, y: {}", x, y);
+            assert!(y >= last, "not monotonic at x: {}", x);
+            assert!((y - last).abs() < 0.003, "not continuous at x: {}", x);
+            assert_eq!(clip(-x), -y);
+
+            last = y;
+        }
+    }
+
+    #[test]
+    fn hard_clip_shape() {
+        check_shape(hard_clip);
+    }
+
+    #[test]
+    fn knee_clip_shape() {
+        for width in [0.0, 0.05, 0.2, 0.5, 1.0] {
+            check_shape(|x| knee_clip(x, width));
+
+            for i in -2000..=2000 {
+                let x = i as f32 * 0.001;
+                let y = knee_clip(x, width);
+
+                if x.abs() <= 1.0 - width {
+                    assert_eq!(y, x);
+                } else if x.abs() >= 1.0 + width {
+                    assert_eq!(y, x.signum());
+                } else {
+                    assert!(y.abs() <= x.abs() && y.abs() <= 1.0);
+                }
+            }
+        }
+
+        assert_eq!(knee_clip(1.5, 0.0), hard_clip(1.5));
+        assert_eq!(knee_clip(0.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn cubic_clip_shape() {
+        check_shape(cubic_clip);
+    }
+
+    #[test]
+    fn poly_clip_shape() {
+        for hardness in 0..16 {
+            check_shape(|x| poly_clip(x, hardness));
+            assert_eq!(poly_clip(1.0, hardness), 1.0);
+        }
+    }
+
+    #[test]
+    fn poly_clip_branches() {
+        for hardness in [0, 3, 4, 7, 15, 64, 255] {
+            let n = (hardness.max(3) | 1) as f32;
+            let gain = n / (n - 1.0);
+
+            for i in -2000..=2000 {
+                let x = i as f32 * 0.001;
+                let y = poly_clip(x, hardness);
+
+                if x.abs() >= 1.0 {
+                    assert_eq!(y, x.signum());
+                } else {
+                    let expected = gain as f64 * (x as f64 - (x as f64).powi(n as i32) / n as f64);
+                    assert!((y as f64 - expected).abs() < 1e-5, "x: {}, n: {}", x, n);
+                    assert!(y.abs() <= gain * x.abs());
+                }
+            }
+
+            // no corner at the knee
+            let slope = (poly_clip(1.0, hardness) - poly_clip(0.999, hardness)) / 0.001;
+            assert!(slope < gain * n * 0.001, "n: {}, slope: {}", n, slope);
+        }
+    }
+
+    #[test]
+    fn poly_clip_approaches_hard_clip() {
+        let max_error = |hardness| {
+            (-2000..=2000)
+                .map(|i| i as f32 * 0.001)
+                .map(|x| (poly_clip(x, hardness) - hard_clip(x)).abs())
+                .fold(0.0, f32::max)
+        };
+
+        let mut last = max_error(3);
+        for hardness in [5, 9, 17, 33, 65, 129, 255] {
+            let error = max_error(hardness);
+            assert!(error < last);
+            last = error;
+        }
+
+        assert!(last < 0.01);
+    }
+
+    #[test]
+    fn sigmoid_clip_shape() {
+        for steepness in [0.0, 0.5, 1.0, 2.0, 5.0, 20.0] {
+            let limit = 1.0 / (steepness as f64).tanh().max(1e-3) as f32;
+
+            for i in -2000..=2000 {
+                let x = i as f32 * 0.001;
+                let y = sigmoid_clip(x, steepness);
+                let exact = if steepness == 0.0 {
+                    x as f64
+                } else {
+                    (steepness as f64 * x as f64).tanh() / (steepness as f64).tanh()
+                };
+
+                assert!(
+                    (y as f64 - exact).abs() < 5e-4,
+                    "x: {}, k: {}",
+                    x,
+                    steepness
+                );
+                assert!(y.abs() <= limit + 1e-4);
+                assert_eq!(sigmoid_clip(-x, steepness), -y);
+                assert_eq!(sigmoid_clip(x, -steepness), y);
+            }
+
+            assert!((sigmoid_clip(1.0, steepness) - 1.0).abs() < 5e-4);
+        }
+    }
+
+    #[test]
+    fn sigmoid_clip_drive() {
+        // more steepness, more compression of the upper range
+        let mut last = sigmoid_clip(0.25, 0.5);
+        for steepness in [1.0, 2.0, 4.0, 8.0] {
+            let y = sigmoid_clip(0.25, steepness);
+            assert!(y > last);
+            last = y;
+        }
+
+        assert!((sigmoid_clip(0.25, 20.0) - hard_clip(20.0 * 0.25)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn poly_clip_hardness() {
+        // Higher orders stay linear for longer, with a small gain of n / (n - 1)
+        assert!((poly_clip(0.5, 15) - 0.5 * 15.0 / 14.0).abs() < 1e-4);
+        assert!((poly_clip(0.5, 3) - 0.5 * 1.5).abs() > 0.01);
+    }
+}
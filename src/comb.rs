@@ -2,9 +2,12 @@
 // https://github.com/irh/freeverb-rs/blob/b877287cfaced4c2872f126b0f0e595abb87dbd0/src/freeverb/src/comb.rs
 
 use crate::delay_line::DelayLine;
+use crate::float::flush_denormal;
 use crate::memory::{memory_slice::MemorySlice, Mutable};
 
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct Comb {
     delay_line: DelayLine,
     feedback: f32,
@@ -52,6 +55,13 @@ impl Comb {
 
         output
     }
+
+    /// Flushes the subnormal dampening filter state to zero. Calling this once per
+    /// block is enough to prevent CPU spikes while the comb rings out into silence.
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.filter_state = flush_denormal(self.filter_state);
+    }
 }
 
 #[cfg(test)]
@@ -72,4 +82,14 @@ mod tests {
         assert_eq!(comb.tick(0.0), 0.125);
         assert_eq!(comb.tick(0.0), 0.09375);
     }
+
+    #[test]
+    fn flush_denormals() {
+        let mut buffer = [0.0_f32; 2];
+        let mut comb = Comb::new(from_slice_mut(&mut buffer[..]));
+        comb.filter_state = f32::MIN_POSITIVE / 4.0;
+
+        comb.flush_denormals();
+        assert_eq!(comb.filter_state, 0.0);
+    }
 }
@@ -1,8 +1,68 @@
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
 
+use crate::{
+    float::{lerp_unchecked, AdditionalF32Ext, ToF32Components},
+    oscillator::lookup_tables::{const_exp, const_ln},
+};
+
+use core::f64::consts::{LN_10, LN_2};
 use core::num::FpCategory::{self, *};
 
+const LOOKUP_MIN_DB: f32 = -120.0;
+const LOOKUP_MAX_DB: f32 = 24.0;
+const LOOKUP_STEPS_PER_DB: f32 = 2.0;
+
+/// 0.5 dB steps from -120 dB to +24 dB
+const DB_TO_GAIN: [f32; 289] = db_to_gain_table::<289>(LOOKUP_MIN_DB, LOOKUP_MAX_DB);
+
+/// log2(x) for x from 1 to 2
+const LOG2_MANTISSA: [f32; 65] = log2_mantissa_table::<65>();
+
+/// 20 * log10(2)
+const DB_PER_OCTAVE: f32 = 6.020_6;
+const OCTAVES_PER_DB: f32 = 1.0 / DB_PER_OCTAVE;
+
+/// Generates a table of voltage ratios for `N` equally spaced decibel values from `min_db` to `max_db`
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::db_to_gain_table;
+///
+/// const TABLE: [f32; 3] = db_to_gain_table::<3>(-20.0, 20.0);
+///
+/// assert_eq!(TABLE, [0.1, 1.0, 10.0]);
+/// ```
+pub const fn db_to_gain_table<const N: usize>(min_db: f32, max_db: f32) -> [f32; N] {
+    let mut table = [0.0; N];
+    let step = if N > 1 {
+        (max_db as f64 - min_db as f64) / (N - 1) as f64
+    } else {
+        0.0
+    };
+
+    let mut i = 0;
+    while i < N {
+        let db = min_db as f64 + i as f64 * step;
+        table[i] = const_exp(db * LN_10 / 20.0) as f32;
+        i += 1;
+    }
+
+    table
+}
+
+const fn log2_mantissa_table<const N: usize>() -> [f32; N] {
+    let mut table = [0.0; N];
+
+    let mut i = 0;
+    while i < N {
+        table[i] = (const_ln(1.0 + i as f64 / (N - 1) as f64) / LN_2) as f32;
+        i += 1;
+    }
+
+    table
+}
+
 pub trait Decibels {
     /// Can yield a `-INF` and `NaN`
     fn to_decibels_unchecked(&self) -> f32;
@@ -15,6 +75,26 @@ pub trait Decibels {
 
     /// Outputs a 0.0 if used on a `-INF`.
     fn to_volt_ratio(&self) -> f32;
+
+    /// Interpolated table lookup instead of `log10`. Error stays below 0.001 dB.
+    ///
+    /// Can yield a `-INF` and `NaN`, `NaN` and `INF` pass through.
+    fn to_decibels_lookup(&self) -> f32;
+
+    /// Interpolated table lookup instead of `powf`. Error stays below 0.004 dB.
+    ///
+    /// Outputs a 0.0 below -120 dB and clamps at +24 dB.
+    fn to_volt_ratio_lookup(&self) -> f32;
+
+    /// Based on `fast_log2` instead of `log10`. Error stays below 0.001 dB.
+    ///
+    /// Can yield a `-INF` and `NaN`.
+    fn fast_to_decibels(&self) -> f32;
+
+    /// Based on `fast_exp2` instead of `powf`. Error stays below 0.0001 dB.
+    ///
+    /// Outputs a 0.0 below roughly -758 dB.
+    fn fast_to_volt_ratio(&self) -> f32;
 }
 
 impl Decibels for f32 {
@@ -49,6 +129,68 @@ impl Decibels for f32 {
     fn to_volt_ratio(&self) -> f32 {
         10.0.powf(self / 20.0)
     }
+
+    #[inline(always)]
+    fn to_decibels_lookup(&self) -> f32 {
+        if self.is_nan() || *self == f32::INFINITY {
+            return *self;
+        }
+
+        if *self <= 0.0 {
+            return self.to_decibels_unchecked();
+        }
+
+        let components = self.to_f32_components();
+        let octaves = components.exponent as i32 - 127;
+
+        let f_index =
+            components.mantissa as f32 * ((LOG2_MANTISSA.len() - 1) as f32 / (1 << 23) as f32);
+        let i_index = f_index as usize;
+
+        let fraction = if i_index < LOG2_MANTISSA.len() - 1 {
+            lerp_unchecked(
+                LOG2_MANTISSA[i_index],
+                LOG2_MANTISSA[i_index + 1],
+                f_index - i_index as f32,
+            )
+        } else {
+            LOG2_MANTISSA[i_index]
+        };
+
+        DB_PER_OCTAVE * (octaves as f32 + fraction)
+    }
+
+    #[inline(always)]
+    fn to_volt_ratio_lookup(&self) -> f32 {
+        if *self < LOOKUP_MIN_DB || self.is_nan() {
+            return 0.0;
+        }
+
+        if *self >= LOOKUP_MAX_DB {
+            return DB_TO_GAIN[DB_TO_GAIN.len() - 1];
+        }
+
+        let f_index = (self - LOOKUP_MIN_DB) * LOOKUP_STEPS_PER_DB;
+
+        // Right below the maximum the index can round up to the last entry
+        let i_index = (f_index as usize).min(DB_TO_GAIN.len() - 2);
+
+        lerp_unchecked(
+            DB_TO_GAIN[i_index],
+            DB_TO_GAIN[i_index + 1],
+            f_index - i_index as f32,
+        )
+    }
+
+    #[inline(always)]
+    fn fast_to_decibels(&self) -> f32 {
+        DB_PER_OCTAVE * self.fast_log2()
+    }
+
+    #[inline(always)]
+    fn fast_to_volt_ratio(&self) -> f32 {
+        (self * OCTAVES_PER_DB).fast_exp2()
+    }
 }
 
 #[cfg(test)]
@@ -64,6 +206,62 @@ mod tests {
         assert_eq!(0.0_f32.to_decibels_unchecked(), f32::NEG_INFINITY);
     }
 
+    #[test]
+    fn lookup_conversion() {
+        assert_eq!(1.0_f32.to_decibels_lookup(), 0.0);
+        assert_eq!(0.0_f32.to_decibels_lookup(), f32::NEG_INFINITY);
+        assert!((-1.0_f32).to_decibels_lookup().is_nan());
+
+        assert_eq!(0.0_f32.to_volt_ratio_lookup(), 1.0);
+        assert_eq!(f32::NEG_INFINITY.to_volt_ratio_lookup(), 0.0);
+        assert_eq!(
+            100.0_f32.to_volt_ratio_lookup(),
+            24.0_f32.to_volt_ratio_lookup()
+        );
+
+        let below_max = crate::float::next_down(LOOKUP_MAX_DB).to_volt_ratio_lookup();
+        assert!((below_max - DB_TO_GAIN[DB_TO_GAIN.len() - 1]).abs() < 1e-4);
+
+        assert!(f32::NAN.to_decibels_lookup().is_nan());
+        assert_eq!(f32::INFINITY.to_decibels_lookup(), f32::INFINITY);
+
+        for i in 1..100_000 {
+            let ratio = i as f32 * 0.0001;
+            let exact = 20.0 * (ratio as f64).log10();
+            let error = (ratio.to_decibels_lookup() as f64 - exact).abs();
+            assert!(error < 0.001, "ratio: {}, error: {}", ratio, error);
+        }
+
+        for i in -1200..240 {
+            let db = i as f32 * 0.1;
+            let error = (20.0 * (db.to_volt_ratio_lookup() as f64).log10() - db as f64).abs();
+            assert!(error < 0.004, "db: {}, error: {}", db, error);
+        }
+    }
+
+    #[test]
+    fn fast_conversion() {
+        assert_eq!(1.0_f32.fast_to_decibels(), 0.0);
+        assert_eq!(0.0_f32.fast_to_decibels(), f32::NEG_INFINITY);
+        assert!((-1.0_f32).fast_to_decibels().is_nan());
+
+        assert_eq!(0.0_f32.fast_to_volt_ratio(), 1.0);
+        assert_eq!(f32::NEG_INFINITY.fast_to_volt_ratio(), 0.0);
+
+        for i in 1..100_000 {
+            let ratio = i as f32 * 0.0001;
+            let exact = 20.0 * (ratio as f64).log10();
+            let error = (ratio.fast_to_decibels() as f64 - exact).abs();
+            assert!(error < 0.001, "ratio: {}, error: {}", ratio, error);
+        }
+
+        for i in -1440..240 {
+            let db = i as f32 * 0.1;
+            let error = (20.0 * (db.fast_to_volt_ratio() as f64).log10() - db as f64).abs();
+            assert!(error < 0.0001, "db: {}, error: {}", db, error);
+        }
+    }
+
     #[test]
     fn check_errors() {
         assert_eq!(0.0_f32.to_decibels(), Ok(f32::NEG_INFINITY));
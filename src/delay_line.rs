@@ -3,7 +3,9 @@
 
 use crate::memory::{memory_slice::MemorySlice, Mutable};
 
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct DelayLine {
     buffer: MemorySlice<Mutable>,
     index: usize,
@@ -36,6 +38,10 @@ impl DelayLine {
         self.buffer.lerp_wrapped(self.index as f32 + offset)
     }
 
+    pub fn read_hermite_wrapped_at(&self, offset: f32) -> f32 {
+        self.buffer.hermite_wrapped(self.index as f32 + offset)
+    }
+
     pub fn read_lagrange_wrapped_at(&self, offset: f32) -> f32 {
         self.buffer.lagrange_wrapped(self.index as f32 + offset, 5)
     }
@@ -0,0 +1,388 @@
+use crate::fixed_point::{
+    math::{exp2_q16, log2_q16},
+    Q15,
+};
+use crate::smoothed_param::{SmoothedParam, Smoothing};
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// 20 * log10(2) in Q16.16
+const DB_PER_OCTAVE_Q16: i64 = 394_567;
+
+/// 1.0 in Q16.16
+const UNITY_Q16: i32 = 1 << 16;
+
+/// Sample rate the smoothing times refer to until `set_sr_unchecked`
+const DEFAULT_SR: f32 = 48_000.0;
+
+/**
+Integer only compressor gain computer, e.g. for an intercom AGC on FPU-less targets.
+
+Takes the level of a detector (peak or RMS, as Q15) and returns the gain (as Q15)
+that brings it down according to threshold and ratio. The whole path runs in the
+log2 domain with `log2_q16` and `exp2_q16`.
+
+Threshold, ratio and makeup gain are Q16.16 numbers, so -12 dB is `-12 << 16`.
+Threshold and makeup can glide to new values block by block with the `*_smoothed`
+setters. Only those glides use floats, once per block.
+
+### Example
+
+```rust
+use embedded_audio_tools::dynamics::GainComputer16;
+use embedded_audio_tools::fixed_point::Q15;
+
+// Threshold -12 dB, ratio 4:1
+let computer = GainComputer16::new(-12 << 16, 4 << 16);
+
+// Quiet signals stay untouched
+assert_eq!(computer.gain(Q15::from_f32(0.1)), Q15::MAX);
+
+// 12 dB above the threshold come out 3 dB above it, so the gain is -9 dB
+let gain = computer.gain(Q15::MAX);
+assert!((gain.to_f32() - 0.3548).abs() < 0.002);
+
+// during audio callback
+let output = computer.apply(Q15::from_f32(0.5), gain);
+```
+*/
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct GainComputer16 {
+    threshold_db: i32,
+    threshold_log2: i32,
+    ratio: i32,
+    makeup_db: i32,
+    /// Linear Q16.16
+    makeup_gain: u32,
+
+    threshold_glide: SmoothedParam,
+    makeup_glide: SmoothedParam,
+}
+
+impl GainComputer16 {
+    /// Ratios below 1:1 are treated as 1:1. Starts without makeup gain, smoothing times
+    /// refer to 48 kHz until `set_sr_unchecked`.
+    pub fn new(threshold_db: i32, ratio: i32) -> GainComputer16 {
+        let mut computer = GainComputer16 {
+            threshold_db: 0,
+            threshold_log2: 0,
+            ratio: UNITY_Q16,
+            makeup_db: 0,
+            makeup_gain: UNITY_Q16 as u32,
+            threshold_glide: SmoothedParam::new(0.0, 0.0, DEFAULT_SR, Smoothing::Linear),
+            makeup_glide: SmoothedParam::new(0.0, 0.0, DEFAULT_SR, Smoothing::Linear),
+        };
+
+        computer.set_threshold(threshold_db);
+        computer.set_ratio(ratio);
+        computer
+    }
+
+    /// Q16.16 decibels
+    #[inline(always)]
+    pub fn set_threshold(&mut self, threshold_db: i32) {
+        self.threshold_glide.set_immediate(threshold_db as f32);
+        self.update_threshold(threshold_db);
+    }
+
+    /// Glides linearly to `threshold_db` (Q16.16 decibels) within `time_ms`, applied by
+    /// `update_smoothed`
+    #[inline(always)]
+    pub fn set_threshold_smoothed(&mut self, threshold_db: i32, time_ms: f32) {
+        self.threshold_glide.set_time(time_ms);
+        self.threshold_glide.set_target(threshold_db as f32);
+    }
+
+    /// Q16.16 decibels, applied by `apply`
+    #[inline(always)]
+    pub fn set_makeup(&mut self, makeup_db: i32) {
+        self.makeup_glide.set_immediate(makeup_db as f32);
+        self.update_makeup(makeup_db);
+    }
+
+    /// Glides linearly to `makeup_db` (Q16.16 decibels) within `time_ms`, applied by
+    /// `update_smoothed`
+    #[inline(always)]
+    pub fn set_makeup_smoothed(&mut self, makeup_db: i32, time_ms: f32) {
+        self.makeup_glide.set_time(time_ms);
+        self.makeup_glide.set_target(makeup_db as f32);
+    }
+
+    /// Advances the threshold and makeup glides by `samples`, call once per block
+    pub fn update_smoothed(&mut self, samples: usize) {
+        if self.threshold_glide.is_smoothing() {
+            let threshold_db = self.threshold_glide.skip(samples);
+            self.update_threshold(threshold_db as i32);
+        }
+
+        if self.makeup_glide.is_smoothing() {
+            let makeup_db = self.makeup_glide.skip(samples);
+            self.update_makeup(makeup_db as i32);
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.threshold_glide.set_sr_unchecked(sr);
+        self.makeup_glide.set_sr_unchecked(sr);
+    }
+
+    /// Q16.16 ratio, values below 1:1 are treated as 1:1
+    #[inline(always)]
+    pub fn set_ratio(&mut self, ratio: i32) {
+        self.ratio = ratio.max(UNITY_Q16);
+    }
+
+    /// Q16.16 decibels
+    #[inline(always)]
+    pub fn threshold(&self) -> i32 {
+        self.threshold_db
+    }
+
+    /// Q16.16 ratio
+    #[inline(always)]
+    pub fn ratio(&self) -> i32 {
+        self.ratio
+    }
+
+    /// Q16.16 decibels
+    #[inline(always)]
+    pub fn makeup(&self) -> i32 {
+        self.makeup_db
+    }
+
+    /// Gain for a detector `level`, between 0 and `Q15::MAX`
+    pub fn gain(&self, level: Q15) -> Q15 {
+        if level.0 <= 0 {
+            return Q15::MAX;
+        }
+
+        // Q15 to Q16.16
+        let level_log2 = log2_q16((level.0 as u32) << 1);
+        let over = level_log2 - self.threshold_log2;
+
+        if over <= 0 {
+            return Q15::MAX;
+        }
+
+        // The output only rises by 1/ratio of what the input rises above the threshold
+        let gain_log2 = ((over as i64 * UNITY_Q16 as i64) / self.ratio as i64) as i32 - over;
+
+        // Q16.16 to Q15
+        Q15((exp2_q16(gain_log2) >> 1).min(i16::MAX as u32) as i16)
+    }
+
+    /// Applies `gain` and the makeup gain, saturating at the `Q15` range
+    #[inline(always)]
+    pub fn apply(&self, sample: Q15, gain: Q15) -> Q15 {
+        let out = ((sample * gain).0 as i64 * self.makeup_gain as i64) >> 16;
+        Q15(out.clamp(i16::MIN as i64, i16::MAX as i64) as i16)
+    }
+
+    #[inline(always)]
+    fn update_threshold(&mut self, threshold_db: i32) {
+        self.threshold_db = threshold_db;
+        self.threshold_log2 = to_log2(threshold_db);
+    }
+
+    #[inline(always)]
+    fn update_makeup(&mut self, makeup_db: i32) {
+        self.makeup_db = makeup_db;
+        self.makeup_gain = exp2_q16(to_log2(makeup_db));
+    }
+}
+
+/**
+Peak envelope follower with separate attack and release times, e.g. as the detector of a
+compressor or per band in a `Vocoder`.
+
+### Example
+
+```rust
+use embedded_audio_tools::dynamics::EnvelopeFollower;
+
+let mut follower = EnvelopeFollower::new(5.0, 50.0, 48_000.0);
+
+// during audio callback
+let envelope = follower.tick(-0.5);
+# assert!(envelope > 0.0);
+```
+*/
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct EnvelopeFollower {
+    attack_ms: f32,
+    release_ms: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    sr: f32,
+    envelope: f32,
+}
+
+impl EnvelopeFollower {
+    /// Times are in ms, from silence to about 63 % of a step and back down
+    pub fn new(attack_ms: f32, release_ms: f32, sr: f32) -> EnvelopeFollower {
+        EnvelopeFollower {
+            attack_ms,
+            release_ms,
+            attack_coeff: time_to_coeff(attack_ms, sr),
+            release_coeff: time_to_coeff(release_ms, sr),
+            sr,
+            envelope: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_attack(&mut self, attack_ms: f32) {
+        self.attack_ms = attack_ms;
+        self.attack_coeff = time_to_coeff(attack_ms, self.sr);
+    }
+
+    #[inline(always)]
+    pub fn set_release(&mut self, release_ms: f32) {
+        self.release_ms = release_ms;
+        self.release_coeff = time_to_coeff(release_ms, self.sr);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_attack(self.attack_ms);
+        self.set_release(self.release_ms);
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> f32 {
+        self.envelope
+    }
+
+    /// Follows the rectified `sample` and returns the envelope
+    #[inline(always)]
+    pub fn tick(&mut self, sample: f32) -> f32 {
+        let rectified = sample.abs();
+
+        let coeff = if rectified > self.envelope {
+            self.attack_coeff
+        } else {
+            self.release_coeff
+        };
+
+        self.envelope += (rectified - self.envelope) * coeff;
+        self.envelope
+    }
+}
+
+/// One pole coefficient reaching about 63 % of a step within `time_ms`, 0 ms is instant
+#[inline(always)]
+fn time_to_coeff(time_ms: f32, sr: f32) -> f32 {
+    if time_ms <= 0.0 {
+        return 1.0;
+    }
+
+    1.0 - (-1000.0 / (time_ms * sr)).exp()
+}
+
+/// Q16.16 decibels to Q16.16 octaves
+#[inline(always)]
+fn to_log2(db: i32) -> i32 {
+    ((db as i64 * UNITY_Q16 as i64) / DB_PER_OCTAVE_Q16) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gain_db(computer: &GainComputer16, level_db: f32) -> f32 {
+        let level = Q15::from_f32(10.0_f32.powf(level_db / 20.0));
+        20.0 * computer.gain(level).to_f32().log10()
+    }
+
+    #[test]
+    fn static_curve() {
+        let computer = GainComputer16::new(-20 << 16, 2 << 16);
+
+        assert!(gain_db(&computer, -30.0).abs() < 0.01);
+        assert!(gain_db(&computer, -20.0).abs() < 0.01);
+        assert!((gain_db(&computer, -10.0) + 5.0).abs() < 0.01);
+        assert!((gain_db(&computer, -2.0) + 9.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn unity_ratio() {
+        let computer = GainComputer16::new(-40 << 16, 0);
+        assert_eq!(computer.gain(Q15::MAX), Q15::MAX);
+    }
+
+    #[test]
+    fn makeup() {
+        let mut computer = GainComputer16::new(-40 << 16, 4 << 16);
+        let sample = Q15::from_f32(0.25);
+
+        assert_eq!(computer.apply(sample, Q15::MAX), sample * Q15::MAX);
+
+        computer.set_makeup(6 << 16);
+        let out = computer.apply(sample, Q15::MAX).to_f32();
+        assert!((out - 0.499).abs() < 0.002);
+
+        // Saturates instead of wrapping
+        assert_eq!(computer.apply(Q15::MAX, Q15::MAX), Q15::MAX);
+    }
+
+    #[test]
+    fn smoothed_threshold_and_makeup() {
+        let mut computer = GainComputer16::new(-20 << 16, 2 << 16);
+        computer.set_sr_unchecked(1000.0);
+
+        computer.set_threshold_smoothed(-10 << 16, 10.0);
+        computer.set_makeup_smoothed(4 << 16, 10.0);
+
+        computer.update_smoothed(5);
+        assert_eq!(computer.threshold(), -15 << 16);
+        assert_eq!(computer.makeup(), 2 << 16);
+
+        computer.update_smoothed(5);
+        assert_eq!(computer.threshold(), -10 << 16);
+        assert_eq!(computer.makeup(), 4 << 16);
+        assert!(gain_db(&computer, -10.0).abs() < 0.01);
+
+        // An immediate change ends the glide
+        computer.set_threshold_smoothed(-30 << 16, 10.0);
+        computer.set_threshold(-20 << 16);
+        computer.update_smoothed(5);
+        assert_eq!(computer.threshold(), -20 << 16);
+    }
+
+    #[test]
+    fn follower_attack_release() {
+        let mut follower = EnvelopeFollower::new(10.0, 100.0, 1000.0);
+
+        // One time constant reaches 1 - 1/e of the step, from either side
+        let rise: [f32; 10] = core::array::from_fn(|_| follower.tick(-1.0));
+        assert!((rise[9] - 0.632).abs() < 0.001);
+
+        follower.set_attack(0.0);
+        assert_eq!(follower.tick(1.0), 1.0);
+
+        let fall: [f32; 100] = core::array::from_fn(|_| follower.tick(0.0));
+        assert!((fall[99] - 0.368).abs() < 0.001);
+
+        follower.reset();
+        assert_eq!(follower.value(), 0.0);
+    }
+
+    #[test]
+    fn silence() {
+        let computer = GainComputer16::new(-40 << 16, 4 << 16);
+        assert_eq!(computer.gain(Q15::ZERO), Q15::MAX);
+        assert_eq!(computer.gain(Q15::MIN), Q15::MAX);
+    }
+}
@@ -1,13 +1,16 @@
 use EnvelopeState::*;
 
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
 
 const SHORTEST_TIME_BASE: f32 = 0.5;
 const BIGGEST_SLOPE: f32 = 20.0;
 
-#[derive(Debug, PartialEq)]
-enum EnvelopeState {
+/// Stage of an `AudioRateADSR`
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EnvelopeState {
     Idle,
     Attack,
     Decay,
@@ -15,6 +18,9 @@ enum EnvelopeState {
     Sustain,
 }
 
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub struct AudioRateADSR {
     attack: f32,
     decay: f32,
@@ -83,6 +89,31 @@ impl AudioRateADSR {
         self.sr = sr;
     }
 
+    pub fn attack(&self) -> f32 {
+        get_time_parameter(self.attack, self.sr)
+    }
+
+    pub fn decay(&self) -> f32 {
+        get_time_parameter(self.decay, self.sr)
+    }
+
+    pub fn sustain(&self) -> f32 {
+        self.sustain
+    }
+
+    pub fn release(&self) -> f32 {
+        get_time_parameter(self.release, self.sr)
+    }
+
+    pub fn slope(&self) -> f32 {
+        self.slope
+    }
+
+    /// Current stage, e.g. for logging or voice allocation
+    pub fn state(&self) -> EnvelopeState {
+        self.state
+    }
+
     // =============
     // ADSR USER API
     // =============
@@ -169,6 +200,10 @@ fn set_time_parameter(parameter_in_secs: f32, sr: f32) -> f32 {
     (1.0 / (parameter_in_secs * sr)).clamp(SHORTEST_TIME_BASE / sr, f32::MAX)
 }
 
+fn get_time_parameter(increment: f32, sr: f32) -> f32 {
+    1.0 / (increment * sr)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,4 +339,17 @@ mod tests {
         assert!(adsr.tick() == 0.0);
         assert_eq!(adsr.state, Idle);
     }
+
+    #[test]
+    fn state_getter() {
+        let mut adsr = AudioRateADSR::new(0.001, 0.001, 0.5, 0.001, 1.0, 48_000.0);
+        assert_eq!(adsr.state(), Idle);
+
+        adsr.trigger_on();
+        adsr.tick();
+        assert_eq!(adsr.state(), Attack);
+
+        adsr.trigger_off();
+        assert_eq!(adsr.state(), Release);
+    }
 }
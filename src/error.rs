@@ -0,0 +1,95 @@
+use crate::float::{BitReductionError, InterpolationError};
+use crate::memory::MemSliceError;
+use crate::oscillator::phase_accumulator::FrequencyError;
+use crate::param_map::ParamMapError;
+use crate::pitch_detector::PitchDetectorError;
+use crate::resampler::ResamplerError;
+use crate::stereo::PanningError;
+
+/**
+Crate-wide error, every module error converts into it, so `?` works across modules.
+
+The `try_*` functions return it directly for bad arguments that would otherwise only
+be clamped or ignored.
+
+### Example
+
+```rust
+use embedded_audio_tools::errors::{Error, PanningError};
+use embedded_audio_tools::fixed_point::math::try_sin_i16;
+use embedded_audio_tools::stereo::stereo_pan;
+
+fn setup() -> Result<i16, Error> {
+    let (left, _) = stereo_pan(0.5, (1.0, 1.0))?;
+    let sine = try_sin_i16(i16::MAX, 4)?;
+
+    Ok((sine as f32 * left) as i16)
+}
+
+assert!(setup().is_ok());
+assert_eq!(try_sin_i16(0, 5), Err(Error::InvalidDegree));
+assert_eq!(Error::from(PanningError::TooLeft), Error::Panning(PanningError::TooLeft));
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BitReduction(BitReductionError),
+    Interpolation(InterpolationError),
+    MemSlice(MemSliceError),
+    Frequency(FrequencyError),
+    ParamMap(ParamMapError),
+    PitchDetector(PitchDetectorError),
+    Resampler(ResamplerError),
+    Panning(PanningError),
+    /// Polynomial degree above what the approximation supports
+    InvalidDegree,
+}
+
+macro_rules! from_module_error {
+    ($($variant:ident($error:ty),)*) => {
+        $(
+            impl From<$error> for Error {
+                #[inline(always)]
+                fn from(error: $error) -> Error {
+                    Error::$variant(error)
+                }
+            }
+        )*
+    };
+}
+
+from_module_error! {
+    BitReduction(BitReductionError),
+    Interpolation(InterpolationError),
+    MemSlice(MemSliceError),
+    Frequency(FrequencyError),
+    ParamMap(ParamMapError),
+    PitchDetector(PitchDetectorError),
+    Resampler(ResamplerError),
+    Panning(PanningError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::float::lerp;
+
+    fn lerp_twice(t: f32) -> Result<f32, Error> {
+        let first = lerp(0.0, 1.0, t)?;
+        Ok(lerp(first, 2.0, t)?)
+    }
+
+    #[test]
+    fn converts_module_errors() {
+        assert_eq!(lerp_twice(0.5), Ok(1.25));
+        assert_eq!(
+            lerp_twice(1.5),
+            Err(Error::Interpolation(InterpolationError::InterpolationRange))
+        );
+        assert_eq!(
+            Error::from(ParamMapError::EmptyRange),
+            Error::ParamMap(ParamMapError::EmptyRange)
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! `extern "C"` interface for driving the processors from C/C++ projects.
+//!
+//! All exposed structs are `#[repr(C)]` with this feature, so their size and layout can be
+//! mirrored in a header, e.g. generated by `cbindgen`. The caller owns the memory: every
+//! processor gets initialized in place by its `*_init` function before any other call.
+//!
+//! # Safety
+//!
+//! All pointers have to be valid, aligned and not aliased for the duration of a call.
+//! Buffers handed to delay based processors have to outlive them.
+
+// The safety contract above holds for every function in here
+#![allow(clippy::missing_safety_doc)]
+
+use core::slice;
+
+use crate::all_pass::AllPass;
+use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+use crate::comb::Comb;
+use crate::delay_line::DelayLine;
+use crate::dynamics::GainComputer16;
+use crate::envelope::AudioRateADSR;
+use crate::fixed_point::Q15;
+use crate::haas_widener::HaasWidener;
+use crate::memory::memory_slice::from_slice_mut;
+
+// ======
+// BIQUAD
+// ======
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_init(biquad: *mut Biquad<Butterworth>) {
+    biquad.write(Biquad::new(BiquadCoeffs::new()));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_lowpass(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.lowpass(fc, q, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_highpass(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.highpass(fc, q, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_allpass(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.allpass(fc, q, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_notch(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.notch(fc, q, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_bell(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    gain: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.bell(fc, q, gain, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_low_shelf(
+    biquad: *mut Biquad<Butterworth>,
+    fc: f32,
+    q: f32,
+    gain: f32,
+    sr: f32,
+) {
+    (*biquad).coeffs.low_shelf(fc, q, gain, sr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_process(biquad: *mut Biquad<Butterworth>, sample: f32) -> f32 {
+    (*biquad).process(sample)
+}
+
+/// Processes `len` samples at `block` in place
+#[no_mangle]
+pub unsafe extern "C" fn eat_biquad_process_block(
+    biquad: *mut Biquad<Butterworth>,
+    block: *mut f32,
+    len: usize,
+) {
+    for sample in slice::from_raw_parts_mut(block, len) {
+        *sample = (*biquad).process(*sample);
+    }
+}
+
+// ========
+// ENVELOPE
+// ========
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_init(
+    adsr: *mut AudioRateADSR,
+    attack_in_secs: f32,
+    decay_in_secs: f32,
+    sustain: f32,
+    release_in_secs: f32,
+    slope: f32,
+    sr: f32,
+) {
+    adsr.write(AudioRateADSR::new(
+        attack_in_secs,
+        decay_in_secs,
+        sustain,
+        release_in_secs,
+        slope,
+        sr,
+    ));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_set_attack(adsr: *mut AudioRateADSR, attack_in_secs: f32) {
+    (*adsr).set_attack(attack_in_secs);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_set_decay(adsr: *mut AudioRateADSR, decay_in_secs: f32) {
+    (*adsr).set_decay(decay_in_secs);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_set_sustain(adsr: *mut AudioRateADSR, sustain: f32) {
+    (*adsr).set_sustain(sustain);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_set_release(adsr: *mut AudioRateADSR, release_in_secs: f32) {
+    (*adsr).set_release(release_in_secs);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_set_slope(adsr: *mut AudioRateADSR, slope: f32) {
+    (*adsr).set_slope(slope);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_trigger_on(adsr: *mut AudioRateADSR) {
+    (*adsr).trigger_on();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_trigger_off(adsr: *mut AudioRateADSR) {
+    (*adsr).trigger_off();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_adsr_tick(adsr: *mut AudioRateADSR) -> f32 {
+    (*adsr).tick()
+}
+
+// ==========
+// COMPRESSOR
+// ==========
+
+/// Threshold and ratio are Q16.16 numbers, see `GainComputer16`
+#[no_mangle]
+pub unsafe extern "C" fn eat_gain_computer_init(
+    computer: *mut GainComputer16,
+    threshold_db: i32,
+    ratio: i32,
+) {
+    computer.write(GainComputer16::new(threshold_db, ratio));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_gain_computer_set_threshold(
+    computer: *mut GainComputer16,
+    threshold_db: i32,
+) {
+    (*computer).set_threshold(threshold_db);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_gain_computer_set_ratio(computer: *mut GainComputer16, ratio: i32) {
+    (*computer).set_ratio(ratio);
+}
+
+/// Gain for a Q15 detector `level`, as Q15
+#[no_mangle]
+pub unsafe extern "C" fn eat_gain_computer_gain(
+    computer: *const GainComputer16,
+    level: i16,
+) -> i16 {
+    (*computer).gain(Q15(level)).0
+}
+
+/// Applies a Q15 `gain` to a Q15 `sample`
+#[no_mangle]
+pub unsafe extern "C" fn eat_gain_computer_apply(
+    computer: *const GainComputer16,
+    sample: i16,
+    gain: i16,
+) -> i16 {
+    (*computer).apply(Q15(sample), Q15(gain)).0
+}
+
+// ======
+// DELAYS
+// ======
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_delay_line_init(
+    delay_line: *mut DelayLine,
+    buffer: *mut f32,
+    len: usize,
+) {
+    delay_line.write(DelayLine::new(from_slice_mut(slice::from_raw_parts_mut(
+        buffer, len,
+    ))));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_delay_line_read_lerp(
+    delay_line: *const DelayLine,
+    offset: f32,
+) -> f32 {
+    (*delay_line).read_lerp_wrapped_at(offset)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_delay_line_write_and_advance(delay_line: *mut DelayLine, value: f32) {
+    (*delay_line).write_and_advance(value);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_comb_init(comb: *mut Comb, buffer: *mut f32, len: usize) {
+    comb.write(Comb::new(from_slice_mut(slice::from_raw_parts_mut(
+        buffer, len,
+    ))));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_comb_set_feedback(comb: *mut Comb, feedback: f32) {
+    (*comb).set_feedback(feedback);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_comb_set_dampening(comb: *mut Comb, dampening: f32) {
+    (*comb).set_dampening(dampening);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_comb_tick(comb: *mut Comb, input: f32) -> f32 {
+    (*comb).tick(input)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_all_pass_init(all_pass: *mut AllPass, buffer: *mut f32, len: usize) {
+    all_pass.write(AllPass::new(from_slice_mut(slice::from_raw_parts_mut(
+        buffer, len,
+    ))));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_all_pass_tick(all_pass: *mut AllPass, input: f32) -> f32 {
+    (*all_pass).tick(input)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_haas_widener_init(
+    widener: *mut HaasWidener,
+    buffer: *mut f32,
+    len: usize,
+    sr: f32,
+) {
+    widener.write(HaasWidener::new(
+        from_slice_mut(slice::from_raw_parts_mut(buffer, len)),
+        sr,
+    ));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_haas_widener_set_delay_time(widener: *mut HaasWidener, ms: f32) {
+    (*widener).set_delay_time(ms);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn eat_haas_widener_set_width(widener: *mut HaasWidener, width: f32) {
+    (*widener).set_width(width);
+}
+
+/// Processes one stereo frame in place
+#[no_mangle]
+pub unsafe extern "C" fn eat_haas_widener_tick(
+    widener: *mut HaasWidener,
+    left: *mut f32,
+    right: *mut f32,
+) {
+    let (l, r) = (*widener).tick((*left, *right));
+    *left = l;
+    *right = r;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn biquad_matches_rust_api() {
+        let mut reference: Biquad<Butterworth> = Biquad::new(BiquadCoeffs::new());
+        reference.coeffs.lowpass(1000.0, 0.7, 48_000.0);
+
+        let mut biquad = MaybeUninit::uninit();
+        let mut block = [1.0, 0.0, 0.0, 0.0];
+
+        unsafe {
+            eat_biquad_init(biquad.as_mut_ptr());
+            eat_biquad_lowpass(biquad.as_mut_ptr(), 1000.0, 0.7, 48_000.0);
+            eat_biquad_process_block(biquad.as_mut_ptr(), block.as_mut_ptr(), block.len());
+        }
+
+        assert_eq!(block[0], reference.process(1.0));
+        assert_eq!(block[1], reference.process(0.0));
+    }
+
+    #[test]
+    fn comb_in_caller_memory() {
+        let mut buffer = [0.0_f32; 2];
+        let mut comb = MaybeUninit::uninit();
+
+        unsafe {
+            eat_comb_init(comb.as_mut_ptr(), buffer.as_mut_ptr(), buffer.len());
+
+            assert_eq!(eat_comb_tick(comb.as_mut_ptr(), 1.0), 0.0);
+            assert_eq!(eat_comb_tick(comb.as_mut_ptr(), 0.0), 0.0);
+            assert_eq!(eat_comb_tick(comb.as_mut_ptr(), 0.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn envelope_and_compressor() {
+        let mut adsr = MaybeUninit::uninit();
+        let mut computer = MaybeUninit::uninit();
+
+        unsafe {
+            eat_adsr_init(adsr.as_mut_ptr(), 0.01, 0.1, 0.5, 0.2, 1.0, 48_000.0);
+            assert_eq!(eat_adsr_tick(adsr.as_mut_ptr()), 0.0);
+
+            eat_adsr_trigger_on(adsr.as_mut_ptr());
+            assert!(eat_adsr_tick(adsr.as_mut_ptr()) > 0.0);
+
+            eat_gain_computer_init(computer.as_mut_ptr(), -12 << 16, 4 << 16);
+            assert_eq!(eat_gain_computer_gain(computer.as_ptr(), 1000), i16::MAX);
+            assert!(eat_gain_computer_gain(computer.as_ptr(), i16::MAX) < i16::MAX / 2);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::oscillator::lookup_tables::{const_cos, const_sin};
+
+use core::f64::consts::TAU;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/**
+In-place radix-2 FFT with a twiddle table computed at compile time.
+
+Works on separate real and imaginary arrays, so no complex type and no allocation is
+needed. `N` has to be a power of two, otherwise `new` panics (at compile time when
+used in a `const`).
+
+### Example
+
+```rust
+use embedded_audio_tools::Fft;
+
+const FFT: Fft<64> = Fft::new();
+
+let mut re = [0.0_f32; 64];
+let mut im = [0.0_f32; 64];
+
+for (i, sample) in re.iter_mut().enumerate() {
+    *sample = (core::f32::consts::TAU * 4.0 * i as f32 / 64.0).cos();
+}
+
+FFT.forward(&mut re, &mut im);
+
+// All energy ends up in bin 4 (and its mirror image)
+let mut magnitudes = [0.0_f32; 33];
+FFT.magnitudes(&re, &im, &mut magnitudes);
+assert!((magnitudes[4] - 32.0).abs() < 1e-3);
+assert!(magnitudes[3] < 1e-3);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Fft<const N: usize> {
+    /// cos(2πk/N) in the first half, sin(2πk/N) in the second half, for k < N/2
+    twiddles: [f32; N],
+}
+
+impl<const N: usize> Fft<N> {
+    pub const fn new() -> Fft<N> {
+        assert!(N.is_power_of_two(), "FFT size has to be a power of two");
+
+        let mut twiddles = [0.0; N];
+        let half = N / 2;
+
+        let mut k = 0;
+        while k < half {
+            let angle = TAU * k as f64 / N as f64;
+            twiddles[k] = const_cos(angle) as f32;
+            twiddles[half + k] = const_sin(angle) as f32;
+            k += 1;
+        }
+
+        Fft { twiddles }
+    }
+
+    /// Transforms the time domain signal in `re` and `im` into its spectrum in-place
+    ///
+    /// Not normalized, a cosine with an amplitude of 1 in an exact bin results in N/2.
+    pub fn forward(&self, re: &mut [f32; N], im: &mut [f32; N]) {
+        Self::bit_reverse(re, im);
+
+        let half = N / 2;
+        let mut len = 2;
+
+        while len <= N {
+            let step = N / len;
+
+            for start in (0..N).step_by(len) {
+                for k in 0..len / 2 {
+                    let w_re = self.twiddles[k * step];
+                    let w_im = -self.twiddles[half + k * step];
+
+                    let a = start + k;
+                    let b = a + len / 2;
+
+                    let t_re = re[b] * w_re - im[b] * w_im;
+                    let t_im = re[b] * w_im + im[b] * w_re;
+
+                    re[b] = re[a] - t_re;
+                    im[b] = im[a] - t_im;
+                    re[a] += t_re;
+                    im[a] += t_im;
+                }
+            }
+
+            len <<= 1;
+        }
+    }
+
+    /// Transforms a real signal, overwriting `im` with zeros before the transform
+    #[inline(always)]
+    pub fn forward_real(&self, re: &mut [f32; N], im: &mut [f32; N]) {
+        im.fill(0.0);
+        self.forward(re, im);
+    }
+
+    /// Inverse of `forward`, including the 1/N normalization
+    pub fn inverse(&self, re: &mut [f32; N], im: &mut [f32; N]) {
+        // ifft(x) = conj(fft(conj(x))) / N
+        im.iter_mut().for_each(|val| *val = -*val);
+        self.forward(re, im);
+
+        let norm = 1.0 / N as f32;
+        re.iter_mut().for_each(|val| *val *= norm);
+        im.iter_mut().for_each(|val| *val *= -norm);
+    }
+
+    /// Writes the magnitude of each bin into `out`, as many as fit up to N
+    ///
+    /// For real input only the first N/2 + 1 bins carry information.
+    pub fn magnitudes(&self, re: &[f32; N], im: &[f32; N], out: &mut [f32]) {
+        for ((out, re), im) in out.iter_mut().zip(re).zip(im) {
+            *out = (re * re + im * im).sqrt();
+        }
+    }
+
+    fn bit_reverse(re: &mut [f32; N], im: &mut [f32; N]) {
+        let mut j = 0;
+
+        for i in 1..N {
+            let mut bit = N >> 1;
+
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for Fft<N> {
+    fn default() -> Self {
+        Fft::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oscillator::WhiteNoise;
+
+    #[test]
+    fn impulse_is_flat() {
+        const FFT: Fft<16> = Fft::new();
+
+        let mut re = [0.0; 16];
+        let mut im = [0.0; 16];
+        re[0] = 1.0;
+
+        FFT.forward(&mut re, &mut im);
+
+        assert_eq!(re, [1.0; 16]);
+        assert_eq!(im, [0.0; 16]);
+    }
+
+    #[test]
+    fn matches_dft() {
+        const FFT: Fft<32> = Fft::new();
+
+        let mut noise = WhiteNoise::default();
+        let mut re = [0.0; 32];
+        let mut im = [0.0; 32];
+
+        re.iter_mut().for_each(|val| *val = noise.tick());
+        im.iter_mut().for_each(|val| *val = noise.tick());
+
+        let (input_re, input_im) = (re, im);
+        FFT.forward(&mut re, &mut im);
+
+        for bin in 0..32 {
+            let (mut dft_re, mut dft_im) = (0.0_f64, 0.0_f64);
+
+            for n in 0..32 {
+                let angle = -TAU * (bin * n) as f64 / 32.0;
+                let (sin, cos) = (angle.sin(), angle.cos());
+
+                dft_re += input_re[n] as f64 * cos - input_im[n] as f64 * sin;
+                dft_im += input_re[n] as f64 * sin + input_im[n] as f64 * cos;
+            }
+
+            assert!((re[bin] as f64 - dft_re).abs() < 1e-4, "bin: {}", bin);
+            assert!((im[bin] as f64 - dft_im).abs() < 1e-4, "bin: {}", bin);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        const FFT: Fft<256> = Fft::new();
+
+        let mut noise = WhiteNoise::default();
+        let mut re = [0.0; 256];
+        let mut im = [0.0; 256];
+
+        re.iter_mut().for_each(|val| *val = noise.tick());
+        let input = re;
+
+        FFT.forward_real(&mut re, &mut im);
+        FFT.inverse(&mut re, &mut im);
+
+        for (output, input) in re.iter().zip(input.iter()) {
+            assert!((output - input).abs() < 1e-5);
+        }
+        assert!(im.iter().all(|val| val.abs() < 1e-5));
+    }
+}
@@ -0,0 +1,200 @@
+use super::math::lerp_wrapped_q15;
+
+/**
+Delay line over an `i16` (Q15) buffer, needs half the memory of the `f32` `DelayLine`.
+
+Fractional delays are interpolated in integer arithmetic, the offset is a signed
+Q16.16 number of samples. An empty buffer reads 0 and ignores writes.
+
+### Example
+
+```rust
+use embedded_audio_tools::fixed_point::DelayLine16;
+
+let mut buffer = [0_i16; 480];
+let mut delay_line = DelayLine16::new(&mut buffer[..]);
+
+// during audio callback
+delay_line.write_and_advance(1000);
+delay_line.write_and_advance(2000);
+
+// Half way between the last two samples
+assert_eq!(delay_line.read_lerp_wrapped_at(-(3 << 15)), 1500);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct DelayLine16 {
+    ptr: *mut i16,
+    length: usize,
+    index: usize,
+}
+
+// Same contract as `MemorySlice`, only use it on statically allocated memory
+unsafe impl Send for DelayLine16 {}
+
+impl DelayLine16 {
+    /// Keeps a raw pointer to `buffer` instead of borrowing it.
+    ///
+    /// ## Safety
+    /// Like `MemorySlice`, it only works safely on statically allocated memory, which
+    /// outlives the delay line and can be sent along with it to another thread.
+    pub fn new(buffer: &mut [i16]) -> Self {
+        Self {
+            ptr: buffer.as_mut_ptr(),
+            length: buffer.len(),
+            index: 0,
+        }
+    }
+
+    /// Overwrites the current buffer and restarts at its beginning. Useful, if you want to
+    /// assign a buffer in runtime.
+    ///
+    /// ## Safety
+    /// Same contract as `new`, only use this on statically allocated memory.
+    #[inline(always)]
+    pub fn change_buffer(&mut self, buffer: &mut [i16]) {
+        self.ptr = buffer.as_mut_ptr();
+        self.length = buffer.len();
+        self.index = 0;
+    }
+
+    #[inline(always)]
+    pub fn get_ptr_slice_mut(&mut self) -> *mut [i16] {
+        core::ptr::slice_from_raw_parts_mut(self.ptr, self.length)
+    }
+
+    pub fn read(&self) -> i16 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        unsafe { *self.ptr.add(self.index) }
+    }
+
+    pub fn read_wrapped_at(&self, offset: isize) -> i16 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let index = (self.index as isize + offset).rem_euclid(self.length as isize) as usize;
+        unsafe { *self.ptr.add(index) }
+    }
+
+    /// `offset` is a signed Q16.16 number of samples
+    pub fn read_lerp_wrapped_at(&self, offset: i32) -> i16 {
+        let int_offset = (offset >> 16) as isize;
+        let frac = ((offset & 0xFFFF) >> 1) as i16;
+
+        let buffer = unsafe { &*core::ptr::slice_from_raw_parts(self.ptr, self.length) };
+        lerp_wrapped_q15(buffer, self.index as isize + int_offset, frac)
+    }
+
+    pub fn write_and_advance(&mut self, value: i16) {
+        if self.is_empty() {
+            return;
+        }
+
+        unsafe {
+            *self.ptr.add(self.index) = value;
+        }
+
+        self.advance();
+    }
+
+    pub fn advance(&mut self) {
+        if self.index + 1 >= self.length {
+            self.index = 0;
+        } else {
+            self.index += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_advance() {
+        let mut buffer = [0_i16; 24];
+        let mut delay_line = DelayLine16::new(&mut buffer[..]);
+
+        for i in 0..24 {
+            delay_line.write_and_advance(i as i16);
+        }
+
+        for (i, val) in buffer.iter().enumerate() {
+            assert_eq!(*val, i as i16);
+        }
+    }
+
+    #[test]
+    fn read_wrapped() {
+        let mut buffer = [0_i16; 4];
+        let mut delay_line = DelayLine16::new(&mut buffer[..]);
+
+        for i in 1..=4 {
+            delay_line.write_and_advance(i * 100);
+        }
+
+        assert_eq!(delay_line.read(), 100);
+        assert_eq!(delay_line.read_wrapped_at(-1), 400);
+        assert_eq!(delay_line.read_wrapped_at(5), 200);
+    }
+
+    #[test]
+    fn change_buffer() {
+        let mut first = [0_i16; 4];
+        let mut second = [0_i16; 2];
+        let mut delay_line = DelayLine16::new(&mut first[..]);
+
+        delay_line.write_and_advance(100);
+        delay_line.change_buffer(&mut second[..]);
+        delay_line.write_and_advance(200);
+        delay_line.write_and_advance(300);
+
+        assert_eq!(delay_line.len(), 2);
+        assert_eq!(delay_line.read(), 200);
+    }
+
+    #[test]
+    fn empty_buffer() {
+        let mut delay_line = DelayLine16::new(&mut []);
+
+        delay_line.write_and_advance(100);
+        delay_line.advance();
+
+        assert!(delay_line.is_empty());
+        assert_eq!(delay_line.read(), 0);
+        assert_eq!(delay_line.read_wrapped_at(-1), 0);
+        assert_eq!(delay_line.read_lerp_wrapped_at(-(3 << 15)), 0);
+
+        let mut buffer = [0_i16; 2];
+        delay_line.change_buffer(&mut buffer[..]);
+        delay_line.write_and_advance(100);
+        assert_eq!(delay_line.read_wrapped_at(-1), 100);
+    }
+
+    #[test]
+    fn fractional_delay() {
+        let mut buffer = [0_i16; 4];
+        let mut delay_line = DelayLine16::new(&mut buffer[..]);
+
+        delay_line.write_and_advance(i16::MIN);
+        delay_line.write_and_advance(i16::MAX);
+
+        assert_eq!(delay_line.read_lerp_wrapped_at(-2 << 16), i16::MIN);
+        assert_eq!(delay_line.read_lerp_wrapped_at(-1 << 16), i16::MAX);
+        assert_eq!(delay_line.read_lerp_wrapped_at(-(3 << 15)), 0);
+        assert_eq!(delay_line.read_lerp_wrapped_at(-(5 << 14)), 16383);
+    }
+}
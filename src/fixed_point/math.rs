@@ -1,17 +1,49 @@
+use crate::error::Error;
+use crate::oscillator::lookup_tables::{const_exp, const_ln};
+
+use core::f64::consts::LN_2;
+
+/// Highest polynomial degree of `sin_i16`
+const MAX_SIN_DEGREE: u8 = 4;
+
+const EXP2_TABLE_BITS: u32 = 6;
+const LOG2_TABLE_BITS: u32 = 8;
+
+/// 2^(k/64) in Q2.30 for k from 0 to 64
+const EXP2_TABLE: [u32; 65] = exp2_table();
+
+/// log2(1 + k/256) in Q16 for k from 0 to 256
+const LOG2_TABLE: [u32; 257] = log2_table();
+
 /// ### Fixed point taylor series expansion of the sine function
 ///
 /// `phase` is the full `i16` range
 /// which corrensponds to sin(x) where x is from `-π/2` to `π/2`
 ///
-/// `degree` of the polynomial approximation. **Highest: 4**
+/// `degree` of the polynomial approximation. **Highest: 4**, higher degrees are clamped
 ///
 /// Returns the full `i16` range
 pub const fn sin_i16(phase: i16, degree: u8) -> i16 {
-    if degree > 4 {
-        unsafe { sin_i16_unchecked(phase, 4) }
-    } else {
-        unsafe { sin_i16_unchecked(phase, degree) }
+    unsafe { sin_i16_unchecked(phase, degree) }
+}
+
+/// ### Fixed point taylor series expansion of the sine function
+///
+/// Same as `sin_i16`, but rejects a `degree` higher than 4 instead of clamping it
+///
+/// ```rust
+/// use embedded_audio_tools::errors::Error;
+/// use embedded_audio_tools::fixed_point::math::try_sin_i16;
+///
+/// assert_eq!(try_sin_i16(0, 3), Ok(0));
+/// assert_eq!(try_sin_i16(0, 5), Err(Error::InvalidDegree));
+/// ```
+pub const fn try_sin_i16(phase: i16, degree: u8) -> Result<i16, Error> {
+    if degree > MAX_SIN_DEGREE {
+        return Err(Error::InvalidDegree);
     }
+
+    Ok(unsafe { sin_i16_unchecked(phase, degree) })
 }
 
 /// ### Fixed point taylor series expansion of the sine function
@@ -25,19 +57,23 @@ pub const fn sin_i16(phase: i16, degree: u8) -> i16 {
 ///
 /// # Safety
 ///
-/// Panics if a degree higher than 4 is being passed into function!
+/// A degree higher than 4 is clamped to 4, use `try_sin_i16` to reject it instead.
 pub const unsafe fn sin_i16_unchecked(phase: i16, degree: u8) -> i16 {
     // Polynomial constants
     const B: [i32; 5] = [102_944, -42_334, 5223, -307, 10];
     const N: u32 = 15;
 
     let z = phase as i32;
-    let mut d = degree as usize;
+    let mut d = if degree > MAX_SIN_DEGREE {
+        MAX_SIN_DEGREE as usize
+    } else {
+        degree as usize
+    };
 
     let mut res: i32 = 0;
 
     while d != 0 {
-        res += B[d as usize];
+        res += B[d];
         res *= z;
         res >>= N;
         res *= z;
@@ -52,3 +88,418 @@ pub const unsafe fn sin_i16_unchecked(phase: i16, degree: u8) -> i16 {
 
     res as i16
 }
+
+/// ### Fixed point taylor series expansion of the sine function in Q31
+///
+/// `phase` is the full `i32` range
+/// which corrensponds to sin(x) where x is from `-π/2` to `π/2`
+///
+/// Goes up to the 15th power with Q30 coefficients and `i64` intermediates,
+/// the error stays within a few LSB of Q31.
+///
+/// Returns the full `i32` range
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::sin_i32;
+///
+/// assert_eq!(sin_i32(0), 0);
+/// assert_eq!(sin_i32(i32::MAX), i32::MAX);
+/// assert_eq!(sin_i32(i32::MIN), i32::MIN);
+/// ```
+pub const fn sin_i32(phase: i32) -> i32 {
+    // sin(π/2 * z) Taylor coefficients in Q30, from z^1 to z^15
+    const B: [i64; 8] = [
+        1_686_629_713,
+        -693_598_668,
+        85_569_306,
+        -5_026_995,
+        172_272,
+        -3864,
+        61,
+        -1,
+    ];
+    const N: u32 = 31;
+
+    const HALF: i64 = 1 << (N - 1);
+
+    let z = phase as i64;
+    let z2 = (z * z + HALF) >> N;
+
+    let mut res = B[7];
+    let mut d = 7;
+
+    while d != 0 {
+        d -= 1;
+        res = B[d] + ((res * z2 + HALF) >> N);
+    }
+
+    // Q30 to Q31
+    res = (res * z + (HALF >> 1)) >> (N - 1);
+
+    if res > i32::MAX as i64 {
+        i32::MAX
+    } else if res < i32::MIN as i64 {
+        i32::MIN
+    } else {
+        res as i32
+    }
+}
+
+/// ### Fixed point cosine, based on `sin_i16`
+///
+/// `phase` is the full `i16` range
+/// which corrensponds to cos(x) where x is from `-π/2` to `π/2`
+///
+/// `degree` of the polynomial approximation. **Highest: 4**
+///
+/// Returns the positive `i16` range
+pub const fn cos_i16(phase: i16, degree: u8) -> i16 {
+    // cos(x) = sin(π/2 - |x|), which stays inside the domain of `sin_i16`
+    sin_i16(i16::MAX - phase.saturating_abs(), degree)
+}
+
+/// ### Fixed point tangent, based on `sin_i16` and `cos_i16`
+///
+/// `phase` is the full `i16` range
+/// which corrensponds to tan(x) where x is from `-π/2` to `π/2`
+///
+/// `degree` of the polynomial approximation. **Highest: 4**
+///
+/// Returns the full `i16` range, saturating where |tan(x)| >= 1 (|x| >= `π/4`)
+pub const fn tan_i16(phase: i16, degree: u8) -> i16 {
+    let sin = sin_i16(phase, degree) as i32;
+    let cos = cos_i16(phase, degree) as i32;
+
+    if sin >= cos {
+        return i16::MAX;
+    }
+
+    if -sin >= cos {
+        return i16::MIN;
+    }
+
+    ((sin << 15) / cos) as i16
+}
+
+/// ### Fixed point 2^x
+///
+/// `x` is a signed Q16.16 number, the result an unsigned Q16.16 number
+///
+/// Uses a lookup table with linear interpolation, relative error is below 3e-5.
+/// Saturates for `x` >= 16 and flushes to 0 below -16.
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::exp2_q16;
+///
+/// assert_eq!(exp2_q16(3 << 16), 8 << 16);
+/// assert_eq!(exp2_q16(-1 << 16), 1 << 15);
+/// assert_eq!(exp2_q16(16 << 16), u32::MAX);
+/// ```
+pub const fn exp2_q16(x: i32) -> u32 {
+    let int_part = x >> 16;
+
+    if int_part >= 16 {
+        return u32::MAX;
+    }
+
+    if int_part < -16 {
+        return 0;
+    }
+
+    let frac = (x & 0xFFFF) as u32;
+    let index = (frac >> (16 - EXP2_TABLE_BITS)) as usize;
+    let sub = frac & ((1 << (16 - EXP2_TABLE_BITS)) - 1);
+
+    let a = EXP2_TABLE[index] as u64;
+    let b = EXP2_TABLE[index + 1] as u64;
+    let mantissa = a + (((b - a) * sub as u64) >> (16 - EXP2_TABLE_BITS));
+
+    // Mantissa is in Q2.30, the result in Q16.16
+    let shift = 14 - int_part;
+    if shift >= 0 {
+        ((mantissa + ((1 << shift) >> 1)) >> shift) as u32
+    } else {
+        (mantissa << -shift) as u32
+    }
+}
+
+/// ### Fixed point log2(x)
+///
+/// `x` is an unsigned Q16.16 number, the result a signed Q16.16 number
+///
+/// Uses a lookup table with linear interpolation, absolute error is below 2.5e-5.
+/// Returns `i32::MIN` for 0.
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::log2_q16;
+///
+/// assert_eq!(log2_q16(8 << 16), 3 << 16);
+/// assert_eq!(log2_q16(1 << 15), -1 << 16);
+/// assert_eq!(log2_q16(0), i32::MIN);
+/// ```
+pub const fn log2_q16(x: u32) -> i32 {
+    if x == 0 {
+        return i32::MIN;
+    }
+
+    let leading_zeros = x.leading_zeros();
+    let int_part = 15 - leading_zeros as i32;
+
+    // Leading one at bit 31, the bits below are the fraction of the mantissa
+    let normalized = x << leading_zeros;
+    let index = ((normalized >> (31 - LOG2_TABLE_BITS)) & ((1 << LOG2_TABLE_BITS) - 1)) as usize;
+    let sub = (normalized >> (31 - LOG2_TABLE_BITS - 10)) & 0x3FF;
+
+    let a = LOG2_TABLE[index];
+    let b = LOG2_TABLE[index + 1];
+    let frac = a + (((b - a) * sub + 0x200) >> 10);
+
+    (int_part << 16) + frac as i32
+}
+
+/// ### Integer square root, rounded down
+///
+/// Bit by bit method, only needs shifts, additions and comparisons
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::sqrt_u32;
+///
+/// assert_eq!(sqrt_u32(16), 4);
+/// assert_eq!(sqrt_u32(17), 4);
+/// assert_eq!(sqrt_u32(u32::MAX), u16::MAX);
+/// ```
+pub const fn sqrt_u32(x: u32) -> u16 {
+    sqrt_u64(x as u64) as u16
+}
+
+/// ### Integer square root, rounded down
+///
+/// Bit by bit method, only needs shifts, additions and comparisons
+pub const fn sqrt_u64(x: u64) -> u32 {
+    let mut remainder = x;
+    let mut res: u64 = 0;
+
+    // Highest power of four not greater than x
+    let mut bit: u64 = if x == 0 {
+        0
+    } else {
+        1 << ((63 - x.leading_zeros()) & !1)
+    };
+
+    while bit != 0 {
+        if remainder >= res + bit {
+            remainder -= res + bit;
+            res = (res >> 1) + bit;
+        } else {
+            res >>= 1;
+        }
+
+        bit >>= 2;
+    }
+
+    res as u32
+}
+
+/// ### Fixed point square root
+///
+/// `x` is a Q15 number, negative inputs yield 0
+///
+/// Returns a Q15 number, rounded down
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::sqrt_q15;
+///
+/// assert_eq!(sqrt_q15(1 << 13), 1 << 14); // sqrt(0.25) = 0.5
+/// assert_eq!(sqrt_q15(-1), 0);
+/// ```
+pub const fn sqrt_q15(x: i16) -> i16 {
+    if x <= 0 {
+        return 0;
+    }
+
+    sqrt_u32((x as u32) << 15) as i16
+}
+
+/// ### Fixed point linear interpolation
+///
+/// `frac` is a Q15 number from 0 (returns `a`) to 1 (`i16::MAX`, returns roughly `b`)
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::lerp_q15;
+///
+/// assert_eq!(lerp_q15(-1000, 1000, 0), -1000);
+/// assert_eq!(lerp_q15(-1000, 1000, 1 << 14), 0);
+/// assert_eq!(lerp_q15(i16::MIN, i16::MAX, i16::MAX), i16::MAX - 2);
+/// ```
+#[inline(always)]
+pub const fn lerp_q15(a: i16, b: i16, frac: i16) -> i16 {
+    let a = a as i32;
+    let b = b as i32;
+
+    (a + (((b - a) * frac as i32 + 0x4000) >> 15)) as i16
+}
+
+/// ### Wrapped, linearly interpolated table lookup
+///
+/// Reads between `table[index]` and `table[index + 1]`, both wrapped around the
+/// length of `table`. `frac` is a Q15 number from 0 to 1. Returns 0 for an empty table.
+///
+/// ```rust
+/// use embedded_audio_tools::fixed_point::math::lerp_wrapped_q15;
+///
+/// let table = [0, 1000, 2000, 3000];
+///
+/// assert_eq!(lerp_wrapped_q15(&table, 1, 1 << 14), 1500);
+/// assert_eq!(lerp_wrapped_q15(&table, 3, 1 << 14), 1500);
+/// assert_eq!(lerp_wrapped_q15(&table, -1, 0), 3000);
+/// ```
+#[inline(always)]
+pub fn lerp_wrapped_q15(table: &[i16], index: isize, frac: i16) -> i16 {
+    if table.is_empty() {
+        return 0;
+    }
+
+    let len = table.len() as isize;
+    let a = index.rem_euclid(len) as usize;
+    let b = if a + 1 == table.len() { 0 } else { a + 1 };
+
+    lerp_q15(table[a], table[b], frac)
+}
+
+const fn exp2_table() -> [u32; 65] {
+    let mut table = [0; 65];
+
+    let mut i = 0;
+    while i < table.len() {
+        let value = const_exp(LN_2 * i as f64 / 64.0);
+        table[i] = (value * (1_u64 << 30) as f64 + 0.5) as u32;
+        i += 1;
+    }
+
+    table
+}
+
+const fn log2_table() -> [u32; 257] {
+    let mut table = [0; 257];
+
+    let mut i = 0;
+    while i < table.len() {
+        let value = const_ln(1.0 + i as f64 / 256.0) / LN_2;
+        table[i] = (value * (1 << 16) as f64 + 0.5) as u32;
+        i += 1;
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::FRAC_PI_2;
+
+    fn to_rads(phase: i16) -> f64 {
+        phase as f64 / i16::MAX as f64 * FRAC_PI_2
+    }
+
+    #[test]
+    fn sin_degree_clamped() {
+        for phase in [i16::MIN, -12_345, 0, 20_000, i16::MAX] {
+            assert_eq!(sin_i16(phase, 200), sin_i16(phase, 4));
+            assert_eq!(unsafe { sin_i16_unchecked(phase, 5) }, sin_i16(phase, 4));
+            assert_eq!(try_sin_i16(phase, 4), Ok(sin_i16(phase, 4)));
+        }
+
+        assert_eq!(try_sin_i16(0, 5), Err(Error::InvalidDegree));
+    }
+
+    #[test]
+    fn exp2() {
+        for x in (-16 << 16..16 << 16).step_by(97) {
+            let exact = (x as f64 / 65536.0).exp2();
+            let result = exp2_q16(x) as f64 / 65536.0;
+
+            // Below 2^-1 the Q16.16 resolution dominates
+            let error = (result - exact).abs() / exact.max(0.5);
+            assert!(error < 3e-5, "x: {}, error: {}", x, error);
+        }
+    }
+
+    #[test]
+    fn log2() {
+        for x in (1..u32::MAX).step_by(9973) {
+            let exact = (x as f64 / 65536.0).log2();
+            let error = (log2_q16(x) as f64 / 65536.0 - exact).abs();
+            assert!(error < 2.5e-5, "x: {}, error: {}", x, error);
+        }
+
+        assert_eq!(log2_q16(u32::MAX), 16 << 16);
+        assert_eq!(log2_q16(1), -16 << 16);
+    }
+
+    #[test]
+    fn integer_sqrt() {
+        for x in (0..u32::MAX).step_by(65_521).chain([u32::MAX, 1, 2, 3, 4]) {
+            let root = sqrt_u32(x) as u64;
+            assert!(root * root <= x as u64 && (root + 1) * (root + 1) > x as u64);
+        }
+
+        assert_eq!(sqrt_u64(u64::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn q15_sqrt() {
+        for x in 0..=i16::MAX {
+            let exact = (x as f64 / 32768.0).sqrt() * 32768.0;
+            assert!(exact - (sqrt_q15(x) as f64) < 1.0, "x: {}", x);
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        for frac in (0..=i16::MAX).step_by(13) {
+            let result = lerp_q15(-20_000, 30_000, frac) as f64;
+            let exact = -20_000.0 + 50_000.0 * frac as f64 / 32768.0;
+            assert!((result - exact).abs() <= 0.5);
+        }
+
+        assert_eq!(lerp_wrapped_q15(&[], 3, 0), 0);
+        assert_eq!(lerp_wrapped_q15(&[7], 3, 100), 7);
+    }
+
+    #[test]
+    fn sine_q31() {
+        let mut max_error = 0.0_f64;
+
+        for phase in (i32::MIN..=i32::MAX).step_by(65_537) {
+            let exact = (phase as f64 / 2_147_483_648.0 * FRAC_PI_2).sin() * 2_147_483_648.0;
+            max_error = max_error.max((sin_i32(phase) as f64 - exact).abs());
+        }
+
+        assert!(max_error < 8.0, "error: {}", max_error);
+        assert_eq!(sin_i32(-12345), -sin_i32(12345));
+    }
+
+    #[test]
+    fn cosine() {
+        for phase in (i16::MIN..=i16::MAX).step_by(7) {
+            let exact = to_rads(phase).cos() * i16::MAX as f64;
+            let error = (cos_i16(phase, 4) as f64 - exact).abs();
+            assert!(error < 4.0, "phase: {}, error: {}", phase, error);
+        }
+
+        assert_eq!(cos_i16(i16::MIN, 4), cos_i16(-i16::MAX, 4));
+    }
+
+    #[test]
+    fn tangent() {
+        for phase in (-15_000..=15_000).step_by(7) {
+            let exact = to_rads(phase).tan() * 32768.0;
+            let error = (tan_i16(phase, 4) as f64 - exact).abs();
+            assert!(error < 8.0, "phase: {}, error: {}", phase, error);
+        }
+
+        assert_eq!(tan_i16(i16::MAX, 4), i16::MAX);
+        assert_eq!(tan_i16(i16::MIN, 4), i16::MIN);
+        assert_eq!(tan_i16(0, 4), 0);
+    }
+}
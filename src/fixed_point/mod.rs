@@ -1 +1,7 @@
+pub mod delay_line;
 pub mod math;
+pub mod q_format;
+pub mod stereo;
+
+pub use delay_line::DelayLine16;
+pub use q_format::{convert_q, Rounding, Q15, Q31};
@@ -0,0 +1,393 @@
+use super::math::sqrt_u64;
+use core::ops::{Add, Mul, Neg, Shl, Shr, Sub};
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// How bits get dropped when converting to fewer fractional bits
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Rounding {
+    /// Rounds towards -INF, the cheapest option, but biased by half a step
+    Floor,
+    /// Rounds to the nearest step, halfway cases towards +INF
+    Nearest,
+}
+
+/// Converts a fixed point number with `from_frac_bits` fractional bits into one with
+/// `to_frac_bits` fractional bits, saturating at the `i32` range
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::fixed_point::q_format::{convert_q, Rounding};
+///
+/// // Q16.16 to Q15
+/// assert_eq!(convert_q(0x8000, 16, 15, Rounding::Floor), 0x4000);
+/// assert_eq!(convert_q(3, 2, 0, Rounding::Floor), 0);
+/// assert_eq!(convert_q(3, 2, 0, Rounding::Nearest), 1);
+///
+/// // Q15 to Q31 saturates for 1.0
+/// assert_eq!(convert_q(1 << 15, 15, 31, Rounding::Nearest), i32::MAX);
+/// ```
+pub const fn convert_q(
+    value: i32,
+    from_frac_bits: u32,
+    to_frac_bits: u32,
+    rounding: Rounding,
+) -> i32 {
+    let value = value as i64;
+
+    let converted = if to_frac_bits >= from_frac_bits {
+        let shift = to_frac_bits - from_frac_bits;
+
+        if shift >= 32 {
+            return if value > 0 {
+                i32::MAX
+            } else if value < 0 {
+                i32::MIN
+            } else {
+                0
+            };
+        }
+
+        value << shift
+    } else {
+        let shift = from_frac_bits - to_frac_bits;
+
+        if shift >= 63 {
+            return if value < 0 && matches!(rounding, Rounding::Floor) {
+                -1
+            } else {
+                0
+            };
+        }
+
+        match rounding {
+            Rounding::Floor => value >> shift,
+            Rounding::Nearest => (value + (1 << (shift - 1))) >> shift,
+        }
+    };
+
+    if converted > i32::MAX as i64 {
+        i32::MAX
+    } else if converted < i32::MIN as i64 {
+        i32::MIN
+    } else {
+        converted as i32
+    }
+}
+
+macro_rules! q_format {
+    ($name:ident, $inner:ty, $wide:ty, $frac_bits:expr) => {
+        impl $name {
+            pub const FRAC_BITS: u32 = $frac_bits;
+
+            /// Largest value, just below 1.0
+            pub const MAX: $name = $name(<$inner>::MAX);
+            /// Exactly -1.0
+            pub const MIN: $name = $name(<$inner>::MIN);
+            pub const ZERO: $name = $name(0);
+
+            #[inline(always)]
+            pub const fn from_bits(bits: $inner) -> $name {
+                $name(bits)
+            }
+
+            #[inline(always)]
+            pub const fn to_bits(self) -> $inner {
+                self.0
+            }
+
+            /// Rounds to the nearest step and saturates outside of -1 to 1
+            #[inline(always)]
+            pub fn from_f32(value: f32) -> $name {
+                // Float to int casts saturate and map NaN to 0
+                $name((value * (1_u64 << $frac_bits) as f32).round() as $inner)
+            }
+
+            #[inline(always)]
+            pub fn to_f32(self) -> f32 {
+                self.0 as f32 / (1_u64 << $frac_bits) as f32
+            }
+
+            #[inline(always)]
+            pub const fn saturating_add(self, rhs: $name) -> $name {
+                $name(self.0.saturating_add(rhs.0))
+            }
+
+            #[inline(always)]
+            pub const fn saturating_sub(self, rhs: $name) -> $name {
+                $name(self.0.saturating_sub(rhs.0))
+            }
+
+            /// Rounds to the nearest step, only -1 * -1 saturates
+            #[inline(always)]
+            pub const fn saturating_mul(self, rhs: $name) -> $name {
+                let product =
+                    (self.0 as $wide * rhs.0 as $wide + (1 << ($frac_bits - 1))) >> $frac_bits;
+
+                if product > <$inner>::MAX as $wide {
+                    $name::MAX
+                } else {
+                    $name(product as $inner)
+                }
+            }
+
+            #[inline(always)]
+            pub const fn saturating_neg(self) -> $name {
+                $name(self.0.saturating_neg())
+            }
+
+            #[inline(always)]
+            pub const fn saturating_abs(self) -> $name {
+                $name(self.0.saturating_abs())
+            }
+
+            /// Square root rounded down, negative values yield 0
+            #[inline(always)]
+            pub const fn sqrt(self) -> $name {
+                if self.0 <= 0 {
+                    return $name::ZERO;
+                }
+
+                $name(sqrt_u64((self.0 as u64) << $frac_bits) as $inner)
+            }
+
+            /// Multiplies by 2^shift and saturates
+            #[inline(always)]
+            pub const fn saturating_shl(self, shift: u32) -> $name {
+                if self.0 == 0 {
+                    return $name::ZERO;
+                }
+
+                if shift >= <$inner>::BITS - 1 || self.0.unsigned_abs().leading_zeros() <= shift {
+                    return if self.0 > 0 { $name::MAX } else { $name::MIN };
+                }
+
+                $name(self.0 << shift)
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn add(self, rhs: $name) -> $name {
+                self.saturating_add(rhs)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn sub(self, rhs: $name) -> $name {
+                self.saturating_sub(rhs)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn mul(self, rhs: $name) -> $name {
+                self.saturating_mul(rhs)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn neg(self) -> $name {
+                self.saturating_neg()
+            }
+        }
+
+        impl Shl<u32> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn shl(self, shift: u32) -> $name {
+                self.saturating_shl(shift)
+            }
+        }
+
+        impl Shr<u32> for $name {
+            type Output = $name;
+
+            /// Arithmetic shift, divides by 2^shift rounding towards -INF
+            #[inline(always)]
+            fn shr(self, shift: u32) -> $name {
+                $name(self.0 >> shift.min(<$inner>::BITS - 1))
+            }
+        }
+    };
+}
+
+/**
+Signed fixed point number with 15 fractional bits, covering -1 to just below 1.
+
+All arithmetic saturates instead of wrapping around, which is what audio wants.
+
+### Example
+
+```rust
+use embedded_audio_tools::fixed_point::Q15;
+
+let a = Q15::from_f32(0.75);
+let b = Q15::from_f32(0.5);
+
+assert_eq!((a * b).to_f32(), 0.375);
+assert_eq!(a + b, Q15::MAX);
+assert_eq!(-Q15::MIN, Q15::MAX);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Q15(pub i16);
+
+/**
+Signed fixed point number with 31 fractional bits, covering -1 to just below 1.
+
+All arithmetic saturates instead of wrapping around, which is what audio wants.
+
+### Example
+
+```rust
+use embedded_audio_tools::fixed_point::Q31;
+
+let a = Q31::from_f32(0.75);
+let b = Q31::from_f32(-0.5);
+
+assert_eq!((a * b).to_f32(), -0.375);
+assert_eq!(b - a, Q31::MIN);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Q31(pub i32);
+
+q_format!(Q15, i16, i32, 15);
+q_format!(Q31, i32, i64, 31);
+
+impl Q31 {
+    /// Drops the lower 16 bits according to `rounding`, saturating at `Q15::MAX`
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::fixed_point::{q_format::Rounding, Q15, Q31};
+    ///
+    /// assert_eq!(Q31::from_f32(0.5).to_q15(Rounding::Floor), Q15::from_f32(0.5));
+    /// assert_eq!(Q31::MAX.to_q15(Rounding::Nearest), Q15::MAX);
+    /// ```
+    #[inline(always)]
+    pub const fn to_q15(self, rounding: Rounding) -> Q15 {
+        let converted = convert_q(self.0, 31, 15, rounding);
+
+        if converted > i16::MAX as i32 {
+            Q15::MAX
+        } else {
+            Q15(converted as i16)
+        }
+    }
+}
+
+impl From<Q15> for Q31 {
+    /// Lossless, only adds fractional bits
+    #[inline(always)]
+    fn from(value: Q15) -> Q31 {
+        Q31((value.0 as i32) << 16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32_conversion() {
+        assert_eq!(Q15::from_f32(0.5), Q15(0x4000));
+        assert_eq!(Q15::from_f32(2.0), Q15::MAX);
+        assert_eq!(Q15::from_f32(-2.0), Q15::MIN);
+        assert_eq!(Q15::from_f32(f32::NAN), Q15::ZERO);
+        assert_eq!(Q15::MIN.to_f32(), -1.0);
+
+        assert_eq!(Q31::from_f32(0.5), Q31(0x4000_0000));
+        assert_eq!(Q31::from_f32(1.0), Q31::MAX);
+        assert_eq!(Q31::MIN.to_f32(), -1.0);
+
+        for i in -100..100 {
+            let x = i as f32 * 0.01;
+            assert!((Q15::from_f32(x).to_f32() - x).abs() <= 0.5 / 32768.0);
+        }
+    }
+
+    #[test]
+    fn saturation() {
+        assert_eq!(Q15::MAX + Q15::MAX, Q15::MAX);
+        assert_eq!(Q15::MIN - Q15::MAX, Q15::MIN);
+        assert_eq!(Q15::MIN * Q15::MIN, Q15::MAX);
+        assert_eq!(Q31::MIN * Q31::MIN, Q31::MAX);
+        assert_eq!(Q15::MIN.saturating_abs(), Q15::MAX);
+    }
+
+    #[test]
+    fn multiplication_rounds() {
+        let half = Q15::from_f32(0.5);
+
+        assert_eq!(Q15(1) * half, Q15(1));
+        assert_eq!(Q15(-1) * half, Q15(0));
+        assert_eq!(Q15::MIN * half, Q15::from_f32(-0.5));
+        assert_eq!(Q31::MAX * Q31::MAX, Q31(i32::MAX - 1));
+    }
+
+    #[test]
+    fn shifts() {
+        let quarter = Q15::from_f32(0.25);
+
+        assert_eq!(quarter << 1, Q15::from_f32(0.5));
+        assert_eq!(quarter << 2, Q15::MAX);
+        assert_eq!(-quarter << 2, Q15::MIN);
+        assert_eq!(-quarter << 1, Q15::from_f32(-0.5));
+        assert_eq!(quarter >> 1, Q15::from_f32(0.125));
+        assert_eq!(Q15::MIN >> 40, Q15(-1));
+    }
+
+    #[test]
+    fn square_root() {
+        assert_eq!(Q15::from_f32(0.25).sqrt(), Q15::from_f32(0.5));
+        assert_eq!(Q31::from_f32(0.25).sqrt(), Q31::from_f32(0.5));
+        assert_eq!(Q31::MIN.sqrt(), Q31::ZERO);
+        assert_eq!(Q31::MAX.sqrt(), Q31::MAX);
+    }
+
+    #[test]
+    fn q_conversion() {
+        assert_eq!(convert_q(-1, 4, 0, Rounding::Floor), -1);
+        assert_eq!(convert_q(-1, 4, 0, Rounding::Nearest), 0);
+        assert_eq!(convert_q(-8, 4, 0, Rounding::Nearest), 0);
+        assert_eq!(convert_q(-9, 4, 0, Rounding::Nearest), -1);
+        assert_eq!(
+            convert_q(i32::MIN, 31, 15, Rounding::Nearest),
+            i16::MIN as i32
+        );
+        assert_eq!(convert_q(-1, 0, 40, Rounding::Nearest), i32::MIN);
+        assert_eq!(convert_q(-1, 70, 0, Rounding::Floor), -1);
+        assert_eq!(convert_q(12345, 8, 8, Rounding::Floor), 12345);
+
+        for bits in i16::MIN..=i16::MAX {
+            let widened = Q31::from(Q15(bits));
+            assert_eq!(widened.to_q15(Rounding::Floor), Q15(bits));
+            assert_eq!(widened.to_q15(Rounding::Nearest), Q15(bits));
+        }
+    }
+
+    #[test]
+    fn widening() {
+        assert_eq!(Q31::from(Q15::from_f32(0.5)), Q31::from_f32(0.5));
+        assert_eq!(Q31::from(Q15::MIN), Q31::MIN);
+    }
+}
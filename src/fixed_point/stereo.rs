@@ -0,0 +1,105 @@
+// Q15 counterparts of the `stereo` module. Since a `Q15` can't leave -1 to 1,
+// there is no need for checked variants.
+
+use super::Q15;
+
+#[inline(always)]
+pub fn stereo_pan(amount: Q15, samples: (Q15, Q15)) -> (Q15, Q15) {
+    let pan = equal_power_pan(amount);
+    (samples.0 * pan.0, samples.1 * pan.1)
+}
+
+#[inline(always)]
+pub fn mono_pan(amount: Q15, sample: Q15) -> (Q15, Q15) {
+    let pan = equal_amplitude_pan(amount);
+    (sample * pan.0, sample * pan.1)
+}
+
+/// Fades from `sample.0` at `amount = -1` to `sample.1` at `amount = 1` with constant amplitude
+#[inline(always)]
+pub fn crossfade_correlated(amount: Q15, sample: (Q15, Q15)) -> Q15 {
+    let pan = equal_amplitude_pan(amount);
+    sample.0 * pan.0 + sample.1 * pan.1
+}
+
+/// Fades from `sample.0` at `amount = -1` to `sample.1` at `amount = 1` with constant power
+#[inline(always)]
+pub fn crossfade_uncorrelated(amount: Q15, sample: (Q15, Q15)) -> Q15 {
+    let pan = equal_power_pan(amount);
+    sample.0 * pan.0 + sample.1 * pan.1
+}
+
+/// Gains `((1 - amount) / 2, (1 + amount) / 2)`
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::fixed_point::{stereo::equal_amplitude_pan, Q15};
+///
+/// assert_eq!(equal_amplitude_pan(Q15::ZERO), (Q15::from_f32(0.5), Q15::from_f32(0.5)));
+/// assert_eq!(equal_amplitude_pan(Q15::MIN), (Q15::MAX, Q15::ZERO));
+/// ```
+#[inline(always)]
+pub fn equal_amplitude_pan(amount: Q15) -> (Q15, Q15) {
+    const ONE: i32 = 1 << 15;
+    let amount = amount.0 as i32;
+
+    (
+        Q15(((ONE - amount) >> 1).min(i16::MAX as i32) as i16),
+        Q15(((ONE + amount) >> 1) as i16),
+    )
+}
+
+/// Square roots of `equal_amplitude_pan`, roughly -3 dB each in the center
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::fixed_point::{stereo::equal_power_pan, Q15};
+///
+/// let (left, right) = equal_power_pan(Q15::ZERO);
+///
+/// assert_eq!(left, right);
+/// assert!((left.to_f32() - 0.70710677).abs() < 1e-4);
+/// ```
+#[inline(always)]
+pub fn equal_power_pan(amount: Q15) -> (Q15, Q15) {
+    let linear = equal_amplitude_pan(amount);
+    (linear.0.sqrt(), linear.1.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_panning() {
+        assert_eq!(equal_amplitude_pan(Q15::MIN), (Q15::MAX, Q15::ZERO));
+        assert_eq!(equal_amplitude_pan(Q15::MAX), (Q15::ZERO, Q15(i16::MAX)));
+    }
+
+    #[test]
+    fn constant_power() {
+        for amount in (i16::MIN..=i16::MAX).step_by(17) {
+            let (left, right) = equal_power_pan(Q15(amount));
+            let power = left.to_f32() * left.to_f32() + right.to_f32() * right.to_f32();
+            assert!((power - 1.0).abs() < 2e-4, "amount: {}", amount);
+        }
+    }
+
+    #[test]
+    fn matches_float() {
+        for amount in (-100..=100).map(|i| i as f32 * 0.01) {
+            let sample = (Q15::from_f32(0.8), Q15::from_f32(-0.4));
+
+            let fixed = stereo_pan(Q15::from_f32(amount), sample);
+            let float = crate::stereo::stereo_pan_unchecked(amount, (0.8, -0.4));
+
+            assert!((fixed.0.to_f32() - float.0).abs() < 1e-3);
+            assert!((fixed.1.to_f32() - float.1).abs() < 1e-3);
+
+            let fixed = crossfade_correlated(Q15::from_f32(amount), sample);
+            let float = crate::stereo::crossfade_correlated_unchecked(amount, (0.8, -0.4));
+
+            assert!((fixed.to_f32() - float).abs() < 1e-3);
+        }
+    }
+}
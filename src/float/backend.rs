@@ -0,0 +1,183 @@
+//! Float math backend, selected by feature flag.
+//!
+//! * `std`: the intrinsics of the standard library
+//! * `libm`: higher accuracy, but slower and bigger
+//! * `micromath` (default): fast approximations
+//!
+//! If several are enabled, the first one in this list wins. Everything in the crate
+//! goes through `F32Ext`, so filters, envelopes and oscillators all share the same
+//! accuracy vs. speed tradeoff.
+
+#[cfg(not(any(feature = "std", feature = "libm", feature = "micromath")))]
+compile_error!("enable one of the `std`, `libm` or `micromath` features as float math backend");
+
+/// Float math for `no_std`, implemented by the selected backend
+pub trait F32Ext {
+    fn abs(self) -> f32;
+    fn atan(self) -> f32;
+    fn atan2(self, other: f32) -> f32;
+    fn ceil(self) -> f32;
+    fn copysign(self, sign: f32) -> f32;
+    fn cos(self) -> f32;
+    fn exp(self) -> f32;
+    fn floor(self) -> f32;
+    fn fract(self) -> f32;
+    fn ln(self) -> f32;
+    fn log2(self) -> f32;
+    fn log10(self) -> f32;
+    fn powf(self, n: f32) -> f32;
+    fn powi(self, n: i32) -> f32;
+    fn recip(self) -> f32;
+    fn rem_euclid(self, other: f32) -> f32;
+    fn round(self) -> f32;
+    fn signum(self) -> f32;
+    fn sin(self) -> f32;
+    fn sqrt(self) -> f32;
+    fn tan(self) -> f32;
+    fn trunc(self) -> f32;
+}
+
+/// Calls `$m!` with the signatures of all `F32Ext` methods
+macro_rules! with_methods {
+    ($m:ident) => {
+        $m! {
+            abs();
+            atan();
+            atan2(other: f32);
+            ceil();
+            copysign(sign: f32);
+            cos();
+            exp();
+            floor();
+            fract();
+            ln();
+            log2();
+            log10();
+            powf(n: f32);
+            powi(n: i32);
+            recip();
+            rem_euclid(other: f32);
+            round();
+            signum();
+            sin();
+            sqrt();
+            tan();
+            trunc();
+        }
+    };
+}
+
+macro_rules! forward {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        impl F32Ext for f32 {
+            $(
+                #[inline(always)]
+                fn $name(self, $($arg: $ty),*) -> f32 {
+                    imp::$name(self, $($arg),*)
+                }
+            )*
+        }
+    };
+}
+
+with_methods!(forward);
+
+#[cfg(feature = "std")]
+mod imp {
+    extern crate std;
+
+    macro_rules! intrinsics {
+        ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+            $(
+                #[inline(always)]
+                pub fn $name(x: f32, $($arg: $ty),*) -> f32 {
+                    f32::$name(x, $($arg),*)
+                }
+            )*
+        };
+    }
+
+    with_methods!(intrinsics);
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+mod imp {
+    pub use libm::{
+        atan2f as atan2, atanf as atan, ceilf as ceil, copysignf as copysign, cosf as cos,
+        expf as exp, fabsf as abs, floorf as floor, log10f as log10, log2f as log2, logf as ln,
+        powf, roundf as round, sinf as sin, sqrtf as sqrt, tanf as tan, truncf as trunc,
+    };
+
+    #[inline(always)]
+    pub fn fract(x: f32) -> f32 {
+        x - trunc(x)
+    }
+
+    #[inline(always)]
+    pub fn powi(x: f32, n: i32) -> f32 {
+        powf(x, n as f32)
+    }
+
+    #[inline(always)]
+    pub fn recip(x: f32) -> f32 {
+        1.0 / x
+    }
+
+    #[inline(always)]
+    pub fn rem_euclid(x: f32, other: f32) -> f32 {
+        let r = libm::fmodf(x, other);
+
+        if r < 0.0 {
+            r + abs(other)
+        } else {
+            r
+        }
+    }
+
+    #[inline(always)]
+    pub fn signum(x: f32) -> f32 {
+        if x.is_nan() {
+            f32::NAN
+        } else {
+            copysign(1.0, x)
+        }
+    }
+}
+
+#[cfg(all(feature = "micromath", not(any(feature = "libm", feature = "std"))))]
+mod imp {
+    use micromath::F32Ext;
+
+    macro_rules! micromath {
+        ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+            $(
+                #[inline(always)]
+                pub fn $name(x: f32, $($arg: $ty),*) -> f32 {
+                    F32Ext::$name(x, $($arg),*)
+                }
+            )*
+        };
+    }
+
+    with_methods!(micromath);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::F32Ext;
+
+    // Fully qualified, otherwise the std intrinsics would shadow the backend in tests
+    #[test]
+    fn backend_is_sane() {
+        assert!((F32Ext::sin(1.0_f32) - 0.841_470_96).abs() < 1e-3);
+        assert!((F32Ext::exp(1.0_f32) - core::f32::consts::E).abs() < 1e-3);
+        assert!((F32Ext::powf(2.0_f32, 0.5) - core::f32::consts::SQRT_2).abs() < 1e-3);
+        assert_eq!(F32Ext::powi(-2.0_f32, 3), -8.0);
+        assert_eq!(F32Ext::floor(-1.5_f32), -2.0);
+        assert_eq!(F32Ext::trunc(-1.5_f32), -1.0);
+        assert!((F32Ext::fract(2.25_f32) - 0.25).abs() < 1e-6);
+        assert!((F32Ext::rem_euclid(-0.25_f32, 1.0) - 0.75).abs() < 1e-6);
+        assert_eq!(F32Ext::signum(-3.0_f32), -1.0);
+        assert_eq!(F32Ext::copysign(2.0_f32, -1.0), -2.0);
+    }
+}
@@ -1,10 +1,16 @@
 use core::ops::Neg;
 
 use super::conversion::{FromF32Components, ToF32Components};
+use crate::oscillator::WhiteNoise;
 use BitReductionError::*;
 
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
 const MAX_RANGE: f32 = 0x7FFFFFFF_u32 as f32;
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
 pub enum BitReductionError {
     OverBitReduction,
     InputExceedsRange,
@@ -76,12 +82,145 @@ pub fn bit_reduce_exp(input: f32, bit_depth: u8) -> Result<f32, BitReductionErro
     Ok(bit_reduce_exp_unchecked(input, bit_depth))
 }
 
+/**
+Bit reduction with TPDF dither and optional first order noise shaping.
+
+Uses the same `bit_depth` as `bit_reduce`, but rounds to the nearest step instead of
+truncating and decorrelates the quantization error from the signal, which turns harsh
+truncation distortion into a constant noise floor. Noise shaping pushes that noise
+floor towards high frequencies.
+
+### Example
+
+```rust
+use embedded_audio_tools::float::Dither;
+
+// Reduce a 32 bit signal to 16 bit
+let mut dither = Dither::new(42);
+dither.set_noise_shaping(true);
+
+// during audio callback
+let output = dither.process_unchecked(0.25, 16);
+# assert!((output - 0.25).abs() < 1e-3);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Dither {
+    noise: WhiteNoise,
+    noise_shaping: bool,
+    error: f32,
+}
+
+impl Dither {
+    pub const fn new(seed: u32) -> Dither {
+        Dither {
+            noise: WhiteNoise::new(seed),
+            noise_shaping: false,
+            error: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_noise_shaping(&mut self, noise_shaping: bool) {
+        self.noise_shaping = noise_shaping;
+        self.error = 0.0;
+    }
+
+    #[inline(always)]
+    pub fn process_unchecked(&mut self, input: f32, bit_depth: u8) -> f32 {
+        if bit_depth == 0 {
+            return input;
+        }
+
+        let step = (1_u32 << bit_depth) as f32 / MAX_RANGE;
+
+        let shaped = if self.noise_shaping {
+            input - self.error
+        } else {
+            input
+        };
+
+        // Sum of two uniform distributions spanning ±1 step
+        let dither = (self.noise.tick() + self.noise.tick()) * 0.5 * step;
+
+        let output = (((shaped + dither) / step).round() * step).clamp(-1.0, 1.0);
+
+        if self.noise_shaping {
+            self.error = output - shaped;
+        }
+
+        output
+    }
+
+    pub fn process(&mut self, input: f32, bit_depth: u8) -> Result<f32, BitReductionError> {
+        if bit_depth > 30 {
+            return Err(OverBitReduction);
+        }
+
+        if !(-1.0..=1.0).contains(&input) {
+            return Err(InputExceedsRange);
+        }
+
+        Ok(self.process_unchecked(input, bit_depth))
+    }
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Dither::new(1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::convert::FromRawBytes;
     use core::f32::consts::PI;
 
+    #[test]
+    fn dither_is_unbiased() {
+        const BITS: u8 = 24;
+        let step = (1_u32 << BITS) as f32 / MAX_RANGE;
+
+        for input in [0.3 * step, -0.3 * step, 2.7 * step] {
+            let mut dither = Dither::default();
+
+            let mut sum = 0.0;
+            for _ in 0..10_000 {
+                let output = dither.process_unchecked(input, BITS);
+                assert_eq!(output, (output / step).round() * step);
+                sum += output;
+            }
+
+            assert!((sum / 10_000.0 - input).abs() < 0.05 * step);
+        }
+    }
+
+    #[test]
+    fn noise_shaping_is_bounded() {
+        const BITS: u8 = 16;
+        let step = (1_u32 << BITS) as f32 / MAX_RANGE;
+
+        let mut dither = Dither::default();
+        dither.set_noise_shaping(true);
+
+        for i in 0..10_000 {
+            let input = 0.5 * (i as f32 * 0.01).sin();
+            let output = dither.process_unchecked(input, BITS);
+            assert!((output - input).abs() < 4.0 * step);
+        }
+    }
+
+    #[test]
+    fn dither_errors() {
+        let mut dither = Dither::default();
+
+        assert!(matches!(dither.process(0.0, 31), Err(OverBitReduction)));
+        assert!(matches!(dither.process(1.5, 16), Err(InputExceedsRange)));
+        assert_eq!(dither.process(0.5, 0).ok(), Some(0.5));
+    }
+
     #[test]
     fn exponential_reduction() {
         assert_eq!(bit_reduce_exp_unchecked(PI, 0), PI);
@@ -9,6 +9,7 @@ register_bitfields! {
             ],
 }
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 pub struct F32Components {
     pub sign: bool,
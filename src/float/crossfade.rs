@@ -0,0 +1,98 @@
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+use core::f32::consts::FRAC_PI_2;
+
+use super::smoothstep;
+
+/// Gain laws for fading from one signal to another
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CrossfadeCurve {
+    /// Constant amplitude, dips by 6 dB in the middle for uncorrelated signals
+    Linear,
+    /// Constant power, suited for uncorrelated signals
+    EqualPower,
+    /// Linear sum with smoothed ends, avoids the sudden start of a linear fade
+    SCurve,
+}
+
+/// Fades from `a` at `t = 0` to `b` at `t = 1` following `curve`
+///
+/// `t` gets clamped between 0 and 1.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{crossfade, CrossfadeCurve};
+///
+/// assert_eq!(crossfade(1.0, 0.0, 0.5, CrossfadeCurve::Linear), 0.5);
+/// assert_eq!(crossfade(1.0, 0.0, 0.0, CrossfadeCurve::EqualPower), 1.0);
+/// assert_eq!(crossfade(1.0, 0.0, 1.0, CrossfadeCurve::SCurve), 0.0);
+/// ```
+#[inline(always)]
+pub fn crossfade(a: f32, b: f32, t: f32, curve: CrossfadeCurve) -> f32 {
+    let gains = crossfade_gains(t, curve);
+    a * gains.0 + b * gains.1
+}
+
+/// Gains `(a, b)` applied by `crossfade`, useful when fading several channels at once
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{crossfade_gains, CrossfadeCurve};
+///
+/// assert_eq!(crossfade_gains(0.25, CrossfadeCurve::Linear), (0.75, 0.25));
+/// ```
+#[inline(always)]
+pub fn crossfade_gains(t: f32, curve: CrossfadeCurve) -> (f32, f32) {
+    let t = t.clamp(0.0, 1.0);
+
+    match curve {
+        CrossfadeCurve::Linear => (1.0 - t, t),
+        CrossfadeCurve::EqualPower => {
+            let angle = t * FRAC_PI_2;
+            (angle.cos(), angle.sin())
+        }
+        CrossfadeCurve::SCurve => {
+            let s = smoothstep(0.0, 1.0, t);
+            (1.0 - s, s)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CrossfadeCurve::*;
+
+    #[test]
+    fn endpoints() {
+        for curve in [Linear, EqualPower, SCurve] {
+            assert_eq!(crossfade(0.3, -0.7, 0.0, curve), 0.3);
+            assert!((crossfade(0.3, -0.7, 1.0, curve) + 0.7).abs() < 1e-6);
+            assert_eq!(crossfade(0.3, -0.7, -1.0, curve), 0.3);
+        }
+    }
+
+    #[test]
+    fn constant_sum() {
+        for i in 0..=100 {
+            let t = i as f32 * 0.01;
+
+            let linear = crossfade_gains(t, Linear);
+            assert!((linear.0 + linear.1 - 1.0).abs() < 1e-6);
+
+            let s_curve = crossfade_gains(t, SCurve);
+            assert!((s_curve.0 + s_curve.1 - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn constant_power() {
+        for i in 0..=100 {
+            let gains = crossfade_gains(i as f32 * 0.01, EqualPower);
+            let power = gains.0 * gains.0 + gains.1 * gains.1;
+            assert!((power - 1.0).abs() < 0.01, "power: {}", power);
+        }
+    }
+}
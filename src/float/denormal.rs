@@ -0,0 +1,45 @@
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Tiny offset that can be added inside feedback paths to keep their state from
+/// decaying into the subnormal range during silence
+///
+/// At roughly -400 dBFS it is far below anything audible but well above the
+/// smallest normal `f32`. Alternate its sign from sample to sample or remove it
+/// with a DC blocker if the offset itself is a concern.
+pub const ANTI_DENORMAL: f32 = 1e-20;
+
+/// Flushes subnormal values to zero
+///
+/// Subnormals in feedback paths can cause massive CPU spikes on FPUs without
+/// hardware support for them.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::flush_denormal;
+///
+/// assert_eq!(flush_denormal(1e-40), 0.0);
+/// assert_eq!(flush_denormal(0.5), 0.5);
+/// ```
+#[inline(always)]
+pub fn flush_denormal(x: f32) -> f32 {
+    if x.abs() < f32::MIN_POSITIVE {
+        0.0
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flush() {
+        assert_eq!(flush_denormal(f32::MIN_POSITIVE / 2.0), 0.0);
+        assert_eq!(flush_denormal(-f32::MIN_POSITIVE / 2.0), 0.0);
+        assert_eq!(flush_denormal(f32::MIN_POSITIVE), f32::MIN_POSITIVE);
+        assert_eq!(flush_denormal(-1.0), -1.0);
+        assert_eq!(flush_denormal(ANTI_DENORMAL), ANTI_DENORMAL);
+    }
+}
@@ -0,0 +1,115 @@
+/// Easing curves mapping a normalized position between 0 and 1 onto 0 to 1
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Easing {
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+}
+
+/// Hermite step between `edge0` and `edge1` with zero slope at both ends
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::smoothstep;
+///
+/// assert_eq!(smoothstep(0.0, 1.0, -1.0), 0.0);
+/// assert_eq!(smoothstep(0.0, 1.0, 0.5), 0.5);
+/// assert_eq!(smoothstep(0.0, 1.0, 2.0), 1.0);
+/// ```
+#[inline(always)]
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Like `smoothstep`, but the second derivative is zero at both ends as well
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::smootherstep;
+///
+/// assert_eq!(smootherstep(0.0, 1.0, -1.0), 0.0);
+/// assert_eq!(smootherstep(0.0, 1.0, 0.5), 0.5);
+/// assert_eq!(smootherstep(0.0, 1.0, 2.0), 1.0);
+/// ```
+#[inline(always)]
+pub fn smootherstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Applies an easing `curve` to `t`, which gets clamped between 0 and 1
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{ease, Easing};
+///
+/// assert_eq!(ease(0.5, Easing::InQuad), 0.25);
+/// assert_eq!(ease(0.5, Easing::OutQuad), 0.75);
+/// ```
+pub fn ease(t: f32, curve: Easing) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+
+    match curve {
+        Easing::Linear => t,
+        Easing::InQuad => t * t,
+        Easing::OutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+        Easing::InOutQuad => {
+            if t < 0.5 {
+                2.0 * t * t
+            } else {
+                1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+            }
+        }
+        Easing::InCubic => t * t * t,
+        Easing::OutCubic => 1.0 - (1.0 - t) * (1.0 - t) * (1.0 - t),
+        Easing::InOutCubic => {
+            if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - 4.0 * (1.0 - t) * (1.0 - t) * (1.0 - t)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Easing::*;
+
+    #[test]
+    fn easing_end_points() {
+        for curve in [
+            Linear, InQuad, OutQuad, InOutQuad, InCubic, OutCubic, InOutCubic,
+        ] {
+            assert_eq!(ease(0.0, curve), 0.0, "{:?}", curve);
+            assert_eq!(ease(1.0, curve), 1.0, "{:?}", curve);
+            assert_eq!(ease(-1.0, curve), 0.0, "{:?}", curve);
+            assert_eq!(ease(2.0, curve), 1.0, "{:?}", curve);
+        }
+
+        assert_eq!(ease(0.5, InOutQuad), 0.5);
+        assert_eq!(ease(0.5, InOutCubic), 0.5);
+    }
+
+    #[test]
+    fn steps_are_monotonic() {
+        let mut last = (0.0, 0.0);
+
+        for i in 0..=100 {
+            let x = i as f32 * 0.01;
+            let next = (smoothstep(0.0, 1.0, x), smootherstep(0.0, 1.0, x));
+
+            assert!(next.0 >= last.0 && next.1 >= last.1);
+            last = next;
+        }
+
+        assert_eq!(smoothstep(2.0, 4.0, 3.0), 0.5);
+    }
+}
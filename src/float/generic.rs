@@ -0,0 +1,160 @@
+//! Float-generic layer shared by the `f32` code and its `f64` variants.
+//!
+//! The `f32` implementation goes through the selected math backend, the `f64` one
+//! (with the `double` feature) through `libm`.
+
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+#[allow(unused_imports)]
+use crate::float::{AdditionalF32Ext, F32Ext};
+
+/// Sample type of the float-generic processors, `f32` or (with the `double` feature) `f64`
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const HALF: Self;
+    const PI: Self;
+    const MIN_POSITIVE: Self;
+
+    fn from_f32(x: f32) -> Self;
+    fn from_usize(x: usize) -> Self;
+    fn from_isize(x: isize) -> Self;
+    /// Truncates towards zero
+    fn to_isize(self) -> isize;
+
+    fn abs(self) -> Self;
+    fn floor(self) -> Self;
+    fn sqrt(self) -> Self;
+    /// Accurate over the whole period, `f32` uses `full_range_tan`
+    fn tan(self) -> Self;
+    fn is_sign_positive(self) -> bool;
+
+    /// Flushes subnormal values to zero, see `flush_denormal`
+    #[inline(always)]
+    fn flush_denormal(self) -> Self {
+        if self.abs() < Self::MIN_POSITIVE {
+            Self::ZERO
+        } else {
+            self
+        }
+    }
+}
+
+impl Float for f32 {
+    const ZERO: f32 = 0.0;
+    const ONE: f32 = 1.0;
+    const TWO: f32 = 2.0;
+    const HALF: f32 = 0.5;
+    const PI: f32 = core::f32::consts::PI;
+    const MIN_POSITIVE: f32 = f32::MIN_POSITIVE;
+
+    #[inline(always)]
+    fn from_f32(x: f32) -> f32 {
+        x
+    }
+
+    #[inline(always)]
+    fn from_usize(x: usize) -> f32 {
+        x as f32
+    }
+
+    #[inline(always)]
+    fn from_isize(x: isize) -> f32 {
+        x as f32
+    }
+
+    #[inline(always)]
+    fn to_isize(self) -> isize {
+        self as isize
+    }
+
+    #[inline(always)]
+    fn abs(self) -> f32 {
+        F32Ext::abs(self)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> f32 {
+        F32Ext::floor(self)
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> f32 {
+        F32Ext::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn tan(self) -> f32 {
+        self.full_range_tan()
+    }
+
+    #[inline(always)]
+    fn is_sign_positive(self) -> bool {
+        f32::is_sign_positive(self)
+    }
+}
+
+#[cfg(feature = "double")]
+impl Float for f64 {
+    const ZERO: f64 = 0.0;
+    const ONE: f64 = 1.0;
+    const TWO: f64 = 2.0;
+    const HALF: f64 = 0.5;
+    const PI: f64 = core::f64::consts::PI;
+    const MIN_POSITIVE: f64 = f64::MIN_POSITIVE;
+
+    #[inline(always)]
+    fn from_f32(x: f32) -> f64 {
+        x as f64
+    }
+
+    #[inline(always)]
+    fn from_usize(x: usize) -> f64 {
+        x as f64
+    }
+
+    #[inline(always)]
+    fn from_isize(x: isize) -> f64 {
+        x as f64
+    }
+
+    #[inline(always)]
+    fn to_isize(self) -> isize {
+        self as isize
+    }
+
+    #[inline(always)]
+    fn abs(self) -> f64 {
+        libm::fabs(self)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    #[inline(always)]
+    fn is_sign_positive(self) -> bool {
+        f64::is_sign_positive(self)
+    }
+}
@@ -0,0 +1,151 @@
+use super::conversion::{FromRawBytes, ToF32Components, ToRawBytes};
+
+const SIGN_MASK: u32 = 0x8000_0000;
+
+/// Number of representable `f32` values between `a` and `b`, `u32::MAX` if one is `NaN`
+///
+/// `0.0` and `-0.0` count as the same value. Handy for robust comparisons in tests.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{next_up, ulp_distance};
+///
+/// assert_eq!(ulp_distance(1.0, 1.0), 0);
+/// assert_eq!(ulp_distance(1.0, next_up(1.0)), 1);
+/// assert_eq!(ulp_distance(-0.0, 0.0), 0);
+/// ```
+pub fn ulp_distance(a: f32, b: f32) -> u32 {
+    if a.is_nan() || b.is_nan() {
+        return u32::MAX;
+    }
+
+    (ordered(a) as i64 - ordered(b) as i64).unsigned_abs() as u32
+}
+
+/// Smallest `f32` greater than `x`. `NaN` and `INF` are returned unchanged.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::next_up;
+///
+/// assert_eq!(next_up(1.0), 1.0 + f32::EPSILON);
+/// assert!(next_up(0.0) > 0.0);
+/// ```
+pub fn next_up(x: f32) -> f32 {
+    if x.is_nan() || x == f32::INFINITY {
+        return x;
+    }
+
+    if x == 0.0 {
+        return f32::from_raw_word(1);
+    }
+
+    let bits = x.to_raw_word();
+
+    if bits & SIGN_MASK == 0 {
+        f32::from_raw_word(bits + 1)
+    } else {
+        f32::from_raw_word(bits - 1)
+    }
+}
+
+/// Largest `f32` less than `x`. `NaN` and `-INF` are returned unchanged.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::next_down;
+///
+/// assert_eq!(next_down(1.0), 1.0 - f32::EPSILON / 2.0);
+/// assert!(next_down(0.0) < 0.0);
+/// ```
+pub fn next_down(x: f32) -> f32 {
+    -next_up(-x)
+}
+
+/// Unbiased exponent of `x`
+///
+/// Zero and subnormal numbers yield -127, `INF` and `NaN` yield 128.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::exponent;
+///
+/// assert_eq!(exponent(1.0), 0);
+/// assert_eq!(exponent(-6.0), 2);
+/// assert_eq!(exponent(0.3), -2);
+/// ```
+pub fn exponent(x: f32) -> i32 {
+    x.to_f32_components().exponent as i32 - 127
+}
+
+/// Mantissa of `x` including the implicit leading bit, between 1 (inclusive) and 2 (exclusive)
+/// for normal numbers
+///
+/// Together with `exponent`, `|x| = mantissa_fraction(x) * 2^exponent(x)` holds for normal numbers.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::mantissa_fraction;
+///
+/// assert_eq!(mantissa_fraction(1.0), 1.0);
+/// assert_eq!(mantissa_fraction(-6.0), 1.5);
+/// ```
+pub fn mantissa_fraction(x: f32) -> f32 {
+    let components = x.to_f32_components();
+    let implicit = if components.exponent == 0 { 0.0 } else { 1.0 };
+
+    implicit + components.mantissa as f32 / (1 << 23) as f32
+}
+
+/// Maps the bit pattern onto integers with the same ordering as the floats
+#[inline(always)]
+fn ordered(x: f32) -> i32 {
+    let bits = x.to_raw_word() as i32;
+
+    if bits < 0 {
+        i32::MIN - bits
+    } else {
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ulp_across_zero() {
+        let smallest = f32::from_raw_word(1);
+
+        assert_eq!(ulp_distance(-smallest, smallest), 2);
+        assert_eq!(ulp_distance(f32::MIN, f32::MAX), 0xFEFF_FFFE);
+        assert_eq!(ulp_distance(f32::NAN, 1.0), u32::MAX);
+    }
+
+    #[test]
+    fn next_up_down_round_trip() {
+        for x in [-1e30, -1.0, -1e-40, 0.0, 1e-40, 0.5, 1.0, 1e30] {
+            assert_eq!(next_down(next_up(x)), x);
+            assert_eq!(ulp_distance(x, next_up(x)), 1);
+            assert_eq!(ulp_distance(x, next_down(x)), 1);
+        }
+
+        assert_eq!(next_up(f32::MAX), f32::INFINITY);
+        assert_eq!(next_down(f32::NEG_INFINITY), f32::NEG_INFINITY);
+        assert!(next_up(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn decomposition() {
+        for x in [0.001, 0.3, 1.0, 3.75, 12345.678] {
+            let mut scale = 1.0;
+            let exp = exponent(x);
+
+            for _ in 0..exp.unsigned_abs() {
+                scale = if exp > 0 { scale * 2.0 } else { scale / 2.0 };
+            }
+
+            assert_eq!(mantissa_fraction(x) * scale, x);
+        }
+    }
+}
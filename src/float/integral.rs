@@ -1,18 +1,52 @@
-pub fn simpsons_rule<const N: usize>(f: fn(f32) -> f32, a: f32, b: f32) -> f32 {
-    let h = (b - a) / (N as f32);
-    let mut x = [0.0; N];
-    for i in 0..N {
-        x[i] = a + (i as f32) * h;
+use crate::float::Float;
+
+/// Integrates `f` from `a` to `b` with the composite Simpson's rule over `N` intervals
+///
+/// `N` has to be even. Works on `f32` and, with the `double` feature, on `f64`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::simpsons_rule;
+///
+/// let area = simpsons_rule::<f32, 8>(|x| x * x, 0.0, 3.0);
+/// assert!((area - 9.0).abs() < 1e-5);
+/// ```
+pub fn simpsons_rule<F: Float, const N: usize>(f: fn(F) -> F, a: F, b: F) -> F {
+    let h = (b - a) / F::from_usize(N);
+
+    let mut odd = F::ZERO;
+    let mut even = F::ZERO;
+
+    for i in 1..N {
+        let y = f(a + F::from_usize(i) * h);
+
+        if i % 2 == 1 {
+            odd += y;
+        } else {
+            even += y;
+        }
     }
-    let mut y = [0.0; N];
-    for i in 0..N {
-        y[i] = f(x[i]);
+
+    h / F::from_f32(3.0) * (f(a) + F::from_f32(4.0) * odd + F::TWO * even + f(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_for_cubics() {
+        let area = simpsons_rule::<f32, 4>(|x| x * x * x - x, 0.0, 2.0);
+        assert!((area - 2.0).abs() < 1e-6);
+
+        let area = simpsons_rule::<f32, 2>(|x| x, -1.0, 3.0);
+        assert!((area - 4.0).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "double")]
+    #[test]
+    fn double_precision() {
+        let area = simpsons_rule::<f64, 1000>(libm::sin, 0.0, core::f64::consts::PI);
+        assert!((area - 2.0).abs() < 1e-10);
     }
-    let integral = h / 3.0
-        * (f(a)
-            + 4.0 * y[1..N].iter().step_by(2).sum::<f32>()
-            + 2.0 * y[2..N - 1].iter().step_by(2).sum::<f32>()
-            + 4.0 * y[1..].iter().step_by(2).sum::<f32>()
-            + if N % 2 == 0 { f(b) } else { 0.0 });
-    integral
 }
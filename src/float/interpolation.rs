@@ -1,4 +1,12 @@
+use crate::oscillator::lookup_tables::{const_cos, const_sin};
+
+use core::f64::consts::PI;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
 /// Raw slice pointer that implements the `Send` trait since it's only acting on static memory
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 pub enum InterpolationError {
     InputNaN,
@@ -6,9 +14,29 @@ pub enum InterpolationError {
     InterpolationRange,
 }
 
+/// Describes how indices outside of a slice are treated
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EdgeMode {
+    /// Continues at the opposite end of the slice
+    Wrap,
+    /// Repeats the first or last element of the slice
+    Clamp,
+}
+
+impl EdgeMode {
+    #[inline(always)]
+    fn resolve(&self, index: isize, len: usize) -> usize {
+        match self {
+            EdgeMode::Wrap => index.rem_euclid(len as isize) as usize,
+            EdgeMode::Clamp => index.clamp(0, len as isize - 1) as usize,
+        }
+    }
+}
+
 #[inline(always)]
 pub fn lerp_unchecked(a: f32, b: f32, interpolate: f32) -> f32 {
-    (a * (1.0 - interpolate)) + (b * interpolate)
+    lerp_kernel(a, b, interpolate)
 }
 
 pub fn lerp(a: f32, b: f32, interpolate: f32) -> Result<f32, InterpolationError> {
@@ -27,22 +55,305 @@ pub fn lerp(a: f32, b: f32, interpolate: f32) -> Result<f32, InterpolationError>
     Ok(lerp_unchecked(a, b, interpolate))
 }
 
-/// Comuptes the lagrange interpolation on the whole set of data points provided.
-pub fn lagrange(array: &[f32], x_point: f32) -> f32 {
-    assert!(x_point <= (array.len() - 1) as f32);
+/// 4-point, 3rd-order Hermite interpolation between `y1` and `y2`
+///
+/// `y0` and `y3` are the neighbouring points used to estimate the slopes.
+#[inline(always)]
+pub fn hermite_unchecked(y0: f32, y1: f32, y2: f32, y3: f32, interpolate: f32) -> f32 {
+    hermite_kernel(y0, y1, y2, y3, interpolate)
+}
 
-    let mut y_point = 0.0_f32;
+pub fn hermite(
+    y0: f32,
+    y1: f32,
+    y2: f32,
+    y3: f32,
+    interpolate: f32,
+) -> Result<f32, InterpolationError> {
+    if y0.is_nan() || y1.is_nan() || y2.is_nan() || y3.is_nan() {
+        return Err(InterpolationError::InputNaN);
+    }
+
+    if y0.is_infinite() || y1.is_infinite() || y2.is_infinite() || y3.is_infinite() {
+        return Err(InterpolationError::InputInfinite);
+    }
+
+    if !(0.0..=1.0).contains(&interpolate) {
+        return Err(InterpolationError::InterpolationRange);
+    }
+
+    Ok(hermite_unchecked(y0, y1, y2, y3, interpolate))
+}
+
+/// Catmull-Rom spline interpolation over a whole slice
+///
+/// Uses the same kernel as `hermite_unchecked`. Points outside of the slice
+/// are resolved with `mode`. An empty slice always yields `0.0`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{catmull_rom, EdgeMode};
+///
+/// let curve = [0.0, 1.0, 4.0, 9.0];
+///
+/// assert_eq!(catmull_rom(&curve, 1.0, EdgeMode::Clamp), 1.0);
+/// assert_eq!(catmull_rom(&curve, 1.5, EdgeMode::Clamp), 2.25);
+/// assert_eq!(catmull_rom(&curve, 4.0, EdgeMode::Wrap), 0.0);
+/// ```
+pub fn catmull_rom(slice: &[f32], index: f32, mode: EdgeMode) -> f32 {
+    if slice.is_empty() {
+        return 0.0;
+    }
+
+    let (points, interpolate) = four_points(slice, index, mode);
+    hermite_kernel(points[0], points[1], points[2], points[3], interpolate)
+}
+
+/// 4-point uniform cubic B-spline interpolation between `y1` and `y2`
+///
+/// Unlike `hermite_unchecked` the curve does not pass through the points but
+/// never overshoots them either. Acts as a slight lowpass, which makes it a good
+/// fit for control-rate signals.
+#[inline(always)]
+pub fn b_spline_unchecked(y0: f32, y1: f32, y2: f32, y3: f32, interpolate: f32) -> f32 {
+    let c0 = (y0 + 4.0 * y1 + y2) * (1.0 / 6.0);
+    let c1 = 0.5 * (y2 - y0);
+    let c2 = 0.5 * (y0 + y2) - y1;
+    let c3 = (y3 - y0) * (1.0 / 6.0) + 0.5 * (y1 - y2);
+
+    ((c3 * interpolate + c2) * interpolate + c1) * interpolate + c0
+}
+
+pub fn b_spline(
+    y0: f32,
+    y1: f32,
+    y2: f32,
+    y3: f32,
+    interpolate: f32,
+) -> Result<f32, InterpolationError> {
+    if y0.is_nan() || y1.is_nan() || y2.is_nan() || y3.is_nan() {
+        return Err(InterpolationError::InputNaN);
+    }
+
+    if y0.is_infinite() || y1.is_infinite() || y2.is_infinite() || y3.is_infinite() {
+        return Err(InterpolationError::InputInfinite);
+    }
+
+    if !(0.0..=1.0).contains(&interpolate) {
+        return Err(InterpolationError::InterpolationRange);
+    }
+
+    Ok(b_spline_unchecked(y0, y1, y2, y3, interpolate))
+}
+
+/// Cubic B-spline interpolation over a whole slice
+///
+/// Points outside of the slice are resolved with `mode`. An empty slice always yields `0.0`.
+pub fn b_spline_slice(slice: &[f32], index: f32, mode: EdgeMode) -> f32 {
+    if slice.is_empty() {
+        return 0.0;
+    }
+
+    let (points, interpolate) = four_points(slice, index, mode);
+    b_spline_unchecked(points[0], points[1], points[2], points[3], interpolate)
+}
+
+/// `f64` variant of `lerp_unchecked`
+#[cfg(feature = "double")]
+#[inline(always)]
+pub fn lerp_unchecked_f64(a: f64, b: f64, interpolate: f64) -> f64 {
+    lerp_kernel(a, b, interpolate)
+}
+
+/// `f64` variant of `hermite_unchecked`
+#[cfg(feature = "double")]
+#[inline(always)]
+pub fn hermite_unchecked_f64(y0: f64, y1: f64, y2: f64, y3: f64, interpolate: f64) -> f64 {
+    hermite_kernel(y0, y1, y2, y3, interpolate)
+}
+
+/// `f64` variant of `catmull_rom`, with `EdgeMode::Wrap` it reads a circular delay
+/// buffer like `DelayLine::read_hermite_wrapped_at`
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{catmull_rom_f64, EdgeMode};
+///
+/// let delay = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+///
+/// assert_eq!(catmull_rom_f64(&delay, 2.25, EdgeMode::Clamp), 2.25);
+/// ```
+#[cfg(feature = "double")]
+pub fn catmull_rom_f64(slice: &[f64], index: f64, mode: EdgeMode) -> f64 {
+    if slice.is_empty() {
+        return 0.0;
+    }
+
+    let (points, interpolate) = four_points(slice, index, mode);
+    hermite_kernel(points[0], points[1], points[2], points[3], interpolate)
+}
+
+/// `f64` variant of `lagrange`
+#[cfg(feature = "double")]
+pub fn lagrange_f64(array: &[f64], x_point: f64) -> f64 {
+    assert!(x_point <= (array.len() - 1) as f64);
+    lagrange_kernel(array, x_point)
+}
+
+#[inline(always)]
+fn lerp_kernel<F: super::Float>(a: F, b: F, interpolate: F) -> F {
+    (a * (F::ONE - interpolate)) + (b * interpolate)
+}
+
+#[inline(always)]
+fn hermite_kernel<F: super::Float>(y0: F, y1: F, y2: F, y3: F, interpolate: F) -> F {
+    let c1 = F::HALF * (y2 - y0);
+    let c2 = y0 - F::from_f32(2.5) * y1 + F::TWO * y2 - F::HALF * y3;
+    let c3 = F::HALF * (y3 - y0) + F::from_f32(1.5) * (y1 - y2);
+
+    ((c3 * interpolate + c2) * interpolate + c1) * interpolate + y1
+}
+
+#[inline(always)]
+fn lagrange_kernel<F: super::Float>(array: &[F], x_point: F) -> F {
+    let mut y_point = F::ZERO;
     for i in 0..array.len() {
         let mut term = array[i];
         for j in 0..array.len() {
             if i != j {
-                term = (term * (x_point - j as f32)) / (i as f32 - j as f32);
+                term =
+                    (term * (x_point - F::from_usize(j))) / (F::from_usize(i) - F::from_usize(j));
             }
         }
         y_point += term;
     }
 
-    return y_point;
+    y_point
+}
+
+/// Collects the four points surrounding `index` and the fractional part in between
+#[inline(always)]
+fn four_points<F: super::Float>(slice: &[F], index: F, mode: EdgeMode) -> ([F; 4], F) {
+    let index = match mode {
+        EdgeMode::Wrap => index,
+        EdgeMode::Clamp => {
+            let last = F::from_usize(slice.len() - 1);
+
+            if index < F::ZERO {
+                F::ZERO
+            } else if index > last {
+                last
+            } else {
+                index
+            }
+        }
+    };
+
+    let int_index = index.floor().to_isize();
+    let point = |offset: isize| slice[mode.resolve(int_index + offset, slice.len())];
+
+    (
+        [point(-1), point(0), point(1), point(2)],
+        index - F::from_isize(int_index),
+    )
+}
+
+/// Polyphase windowed-sinc kernel with `TAPS` coefficients for each of the `PHASES` sub-sample positions
+pub type SincKernel<const TAPS: usize, const PHASES: usize> = [[f32; TAPS]; PHASES];
+
+/// Generates a Hann-windowed sinc kernel for `interpolate_sinc`
+///
+/// `cutoff` is relative to the Nyquist frequency, `1.0` passes the full band.
+/// Lower values are needed to prevent aliasing when reading faster than the
+/// original rate. Every phase is normalized to unity gain at DC.
+///
+/// `TAPS` should be even. Can be evaluated at compile time to bake the table into flash.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::{windowed_sinc_table, SincKernel};
+///
+/// const KERNEL: SincKernel<8, 64> = windowed_sinc_table::<8, 64>(1.0);
+///
+/// assert_eq!(KERNEL[0][3], 1.0);
+/// ```
+pub const fn windowed_sinc_table<const TAPS: usize, const PHASES: usize>(
+    cutoff: f32,
+) -> SincKernel<TAPS, PHASES> {
+    let mut table = [[0.0; TAPS]; PHASES];
+
+    let half = (TAPS / 2) as f64;
+    let cutoff = cutoff as f64;
+
+    let mut phase = 0;
+    while phase < PHASES {
+        let frac = phase as f64 / PHASES as f64;
+
+        let mut row = [0.0_f64; TAPS];
+        let mut sum = 0.0;
+
+        let mut tap = 0;
+        while tap < TAPS {
+            // Distance between this tap and the interpolated position
+            let t = (tap as f64 - (half - 1.0)) - frac;
+
+            let x = PI * cutoff * t;
+            let sinc = if x == 0.0 { 1.0 } else { const_sin(x) / x };
+            let window = 0.5 + 0.5 * const_cos(PI * t / half);
+
+            row[tap] = sinc * window;
+            sum += row[tap];
+            tap += 1;
+        }
+
+        let mut tap = 0;
+        while tap < TAPS {
+            table[phase][tap] = (row[tap] / sum) as f32;
+            tap += 1;
+        }
+
+        phase += 1;
+    }
+
+    table
+}
+
+/// Band-limited interpolation of `slice` at the fractional `index` with a `SincKernel`
+///
+/// The sub-sample position is rounded to the closest kernel phase, so the kernel
+/// should have enough phases for the required precision. Samples outside of the
+/// slice are treated as silence.
+pub fn interpolate_sinc<const TAPS: usize, const PHASES: usize>(
+    slice: &[f32],
+    index: f32,
+    kernel: &SincKernel<TAPS, PHASES>,
+) -> f32 {
+    let mut int_index = index.floor() as isize;
+    let mut phase = ((index - int_index as f32) * PHASES as f32 + 0.5) as usize;
+
+    if phase >= PHASES {
+        int_index += 1;
+        phase = 0;
+    }
+
+    let first = int_index - (TAPS / 2) as isize + 1;
+
+    let mut y_point = 0.0;
+    for (tap, coeff) in kernel[phase].iter().enumerate() {
+        let i = first + tap as isize;
+
+        if i >= 0 && (i as usize) < slice.len() {
+            y_point += slice[i as usize] * coeff;
+        }
+    }
+
+    y_point
+}
+
+/// Comuptes the lagrange interpolation on the whole set of data points provided.
+pub fn lagrange(array: &[f32], x_point: f32) -> f32 {
+    assert!(x_point <= (array.len() - 1) as f32);
+    lagrange_kernel(array, x_point)
 }
 
 #[inline(always)]
@@ -100,4 +411,109 @@ mod tests {
         assert_eq!(lerp(0.0, 0.0, 2.0), Err(InterpolationRange));
         assert_eq!(lerp(0.0, 1.0, 0.5).unwrap(), 0.5);
     }
+
+    #[test]
+    fn hermite_unchecked_points() {
+        assert_eq!(hermite_unchecked(0.0, 1.0, 2.0, 3.0, 0.0), 1.0);
+        assert_eq!(hermite_unchecked(0.0, 1.0, 2.0, 3.0, 1.0), 2.0);
+        assert_eq!(hermite_unchecked(0.0, 1.0, 2.0, 3.0, 0.5), 1.5);
+
+        // Reproduces a parabola exactly
+        let parabola = |x: f32| x * x;
+        assert_eq!(
+            hermite_unchecked(
+                parabola(0.0),
+                parabola(1.0),
+                parabola(2.0),
+                parabola(3.0),
+                0.5
+            ),
+            parabola(1.5)
+        );
+    }
+
+    #[test]
+    fn catmull_rom_edges() {
+        let curve = [0.0, 1.0, 2.0, 3.0];
+
+        assert_eq!(catmull_rom(&[], 1.0, EdgeMode::Wrap), 0.0);
+
+        assert_eq!(catmull_rom(&curve, -1.0, EdgeMode::Clamp), 0.0);
+        assert_eq!(catmull_rom(&curve, 10.0, EdgeMode::Clamp), 3.0);
+        assert_eq!(catmull_rom(&curve, 3.0, EdgeMode::Clamp), 3.0);
+
+        assert_eq!(catmull_rom(&curve, -1.0, EdgeMode::Wrap), 3.0);
+        assert_eq!(catmull_rom(&curve, 5.0, EdgeMode::Wrap), 1.0);
+        assert_eq!(catmull_rom(&curve, 1.5, EdgeMode::Wrap), 1.5);
+    }
+
+    #[test]
+    fn sinc_kernel() {
+        const KERNEL: SincKernel<16, 128> = windowed_sinc_table::<16, 128>(1.0);
+
+        // Integer positions are a single impulse
+        for (tap, coeff) in KERNEL[0].iter().enumerate() {
+            let expected = if tap == 7 { 1.0 } else { 0.0 };
+            assert!((coeff - expected).abs() < 1e-6, "tap: {}", tap);
+        }
+
+        // Unity gain at DC
+        for row in KERNEL.iter() {
+            assert!((row.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn sinc_interpolation() {
+        const KERNEL: SincKernel<16, 256> = windowed_sinc_table::<16, 256>(1.0);
+
+        let mut slow_sine = [0.0_f32; 64];
+        for (i, val) in slow_sine.iter_mut().enumerate() {
+            *val = (i as f32 * 0.2).sin();
+        }
+
+        assert_eq!(interpolate_sinc(&slow_sine, 20.0, &KERNEL), slow_sine[20]);
+
+        for i in 200..400 {
+            let index = i as f32 * 0.1;
+            let error = (interpolate_sinc(&slow_sine, index, &KERNEL) - (index * 0.2).sin()).abs();
+            assert!(error < 0.01, "index: {}, error: {}", index, error);
+        }
+    }
+
+    #[test]
+    fn b_spline_points() {
+        // Constants and lines are reproduced exactly
+        assert_eq!(b_spline_unchecked(1.0, 1.0, 1.0, 1.0, 0.3), 1.0);
+        assert_eq!(b_spline_unchecked(0.0, 1.0, 2.0, 3.0, 0.5), 1.5);
+
+        // Smooths out peaks instead of overshooting
+        let peak = b_spline_unchecked(0.0, 1.0, 0.0, 0.0, 0.0);
+        assert!(peak < 1.0 && peak > 0.0);
+
+        assert_eq!(b_spline(f32::NAN, 0.0, 0.0, 0.0, 0.0), Err(InputNaN));
+        assert_eq!(b_spline(0.0, 0.0, 0.0, 0.0, 2.0), Err(InterpolationRange));
+    }
+
+    #[test]
+    fn b_spline_edges() {
+        let curve = [0.0, 1.0, 2.0, 3.0];
+
+        assert_eq!(b_spline_slice(&[], 1.0, EdgeMode::Wrap), 0.0);
+        assert_eq!(b_spline_slice(&curve, 1.5, EdgeMode::Clamp), 1.5);
+        assert_eq!(b_spline_slice(&[2.0; 4], 10.0, EdgeMode::Clamp), 2.0);
+        assert_eq!(b_spline_slice(&[2.0; 4], -10.0, EdgeMode::Wrap), 2.0);
+    }
+
+    #[test]
+    fn hermite_checked() {
+        assert_eq!(hermite(f32::NAN, 0.0, 0.0, 0.0, 0.0), Err(InputNaN));
+        assert_eq!(
+            hermite(0.0, 0.0, 0.0, f32::INFINITY, 0.0),
+            Err(InputInfinite)
+        );
+        assert_eq!(hermite(0.0, 0.0, 0.0, 0.0, -1.0), Err(InterpolationRange));
+        assert_eq!(hermite(0.0, 0.0, 0.0, 0.0, 2.0), Err(InterpolationRange));
+        assert_eq!(hermite(0.0, 1.0, 2.0, 3.0, 0.5), Ok(1.5));
+    }
 }
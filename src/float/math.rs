@@ -1,16 +1,43 @@
 use crate::{
     fixed_point::math::sin_i16_unchecked,
     float::integral::simpsons_rule,
-    float::lerp_unchecked,
+    float::{lerp_unchecked, wrap_phase},
+    float::{FromF32Components, FromRawBytes, ToF32Components, ToRawBytes},
     memory_access::from_slice,
     oscillator::lookup_tables::{bl_rect::BANDLIMITED_RECT, sine_table},
 };
 
-use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, LOG2_E, PI};
 use core::ops::Neg;
 
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
+
+/// Least squares fit of 2^x - 1 on [0, 1)
+const EXP2_C: [f32; 4] = [0.693_044, 0.241_282_87, 0.052_240_516, 0.013_426_788];
+
+/// Least squares fit of log2(1 + x) on [0, 1)
+const LOG2_C: [f32; 4] = [1.438_637_5, -0.677_739_1, 0.321_871_44, -0.082_855_8];
+
+/// Odd polynomial approximation of atan(x) on [-1, 1]
+const ATAN_C: [f32; 5] = [0.999_866, -0.330_299_5, 0.180_141, -0.085_133, 0.020_835_1];
+
+/// Difference between π/2 and its `f32` representation
+const FRAC_PI_2_LO: f32 = -4.371_139e-8;
+
+/// Magic number and Newton step constants for the inverse square root (Moroz et al., 2018)
+const RSQRT_MAGIC: u32 = 0x5F1F_FFF9;
+const RSQRT_C: [f32; 2] = [0.703_952_25, 2.389_244_6];
+
+/// ln(2) split into an exactly representable and a remainder part (Cody-Waite)
+const LN_2_HI: f32 = 0.693_359_4;
+const LN_2_LO: f32 = -2.121_944_4e-4;
+
+/// Input magnitude where tanh(x) rounds to 1 in `f32`
+const ACCURATE_TANH_SATURATION: f32 = 9.0;
+
+/// Input magnitude where the tanh approximant reaches 1
+const TANH_SATURATION: f32 = 4.97;
 
 /// Extends the feature set of functions for `f32` with `#![no_std]` compatibility:
 ///
@@ -24,8 +51,19 @@ use micromath::F32Ext;
 /// * sin(x) (LUT)
 /// * sin(x) (fixed point Taylor series approximation)
 /// * cos(x) (fixed point Taylor series approximation)
+/// * sin(x) and cos(x) at once (sharing the range reduction)
 /// * tan(x) (Taylor series expansion)
+/// * tan(x) (range reduced Taylor series expansion)
 /// * rect(x) (bandlimiting LUT)
+/// * exp2(x), log2(x) and pow(x, y) (bit manipulation with polynomial correction)
+/// * tanh(x) (Padé approximant)
+/// * atan(x) (polynomial approximation)
+/// * 1/sqrt(x) (bit manipulation with a Newton step)
+///
+/// And some slower, but accurate functions for table generation:
+///
+/// * tanh(x) (range reduced exp)
+/// * parametric sigmoid (range reduced exp)
 pub trait AdditionalF32Ext {
     type Output;
     fn si(&self) -> Self::Output;
@@ -33,10 +71,20 @@ pub trait AdditionalF32Ext {
     fn sinh(&self) -> Self::Output;
     fn cosh(&self) -> Self::Output;
     fn fast_tan(&self) -> Self::Output;
+    fn full_range_tan(&self) -> Self::Output;
+    fn fast_exp2(&self) -> Self::Output;
+    fn fast_log2(&self) -> Self::Output;
+    fn fast_pow(&self, exponent: f32) -> Self::Output;
+    fn fast_tanh(&self) -> Self::Output;
+    fn fast_atan(&self) -> Self::Output;
+    fn fast_rsqrt(&self) -> Self::Output;
+    fn accurate_tanh(&self) -> Self::Output;
+    fn parametric_sigmoid(&self, steepness: f32) -> Self::Output;
     fn lookup_sin(&self) -> Self::Output;
     fn lookup_bl_rect(&self) -> Self::Output;
     fn fixed_point_sin(&self) -> Self::Output;
     fn fixed_point_cos(&self) -> Self::Output;
+    fn sincos(&self) -> (Self::Output, Self::Output);
 }
 
 impl AdditionalF32Ext for f32 {
@@ -96,6 +144,174 @@ impl AdditionalF32Ext for f32 {
         res
     }
 
+    /// Tangent function for any input, unlike `fast_tan` which diverges above roughly 1 rad
+    ///
+    /// Reduces the input to [-π/2, π/2) and uses tan(x) = 1/tan(π/2 - x) above π/4,
+    /// so the Taylor series only has to cover [-π/4, π/4].
+    ///
+    /// ## Accuracy
+    /// Relative error is below 1.5e-5 for |x| < π/2. Larger inputs need a `rem_euclid`,
+    /// whose rounding shows close to the poles.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use core::f32::consts::PI;
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(0.0.full_range_tan(), 0.0);
+    /// assert!((1.5_f32.tan() - 1.5.full_range_tan()).abs() / 1.5_f32.tan() < 1.5e-5);
+    /// assert!(((PI + 1.0).full_range_tan() - 1.0_f32.tan()).abs() < 1e-4);
+    /// ```
+    fn full_range_tan(&self) -> Self::Output {
+        // Only reduce when necessary, since it costs precision for small inputs
+        let reduced = if self.abs() < FRAC_PI_2 {
+            *self
+        } else {
+            (self + FRAC_PI_2).rem_euclid(PI) - FRAC_PI_2
+        };
+
+        if reduced.abs() <= FRAC_PI_4 {
+            reduced.fast_tan()
+        } else {
+            let complement = (FRAC_PI_2 - reduced.abs()) + FRAC_PI_2_LO;
+            complement.fast_tan().recip().copysign(reduced)
+        }
+    }
+
+    /// Computes 2^x by splitting off the integer part into the exponent bits and
+    /// approximating the fractional part with a 4th order polynomial
+    ///
+    /// ## Accuracy
+    ///
+    /// Relative error is below 4e-6 (ca. 0.00003 dB when used for gain). Results
+    /// below the smallest normal number are flushed to zero.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(3.0.fast_exp2(), 8.0);
+    /// assert!((0.5.fast_exp2() - 2.0_f32.sqrt()).abs() < 1e-5);
+    /// assert_eq!((-200.0).fast_exp2(), 0.0);
+    /// ```
+    fn fast_exp2(&self) -> Self::Output {
+        if *self < -126.0 {
+            return 0.0;
+        }
+
+        if *self >= 128.0 {
+            return f32::INFINITY;
+        }
+
+        let int_part = self.floor();
+        let x = self - int_part;
+
+        let mantissa = 1.0 + x * (EXP2_C[0] + x * (EXP2_C[1] + x * (EXP2_C[2] + x * EXP2_C[3])));
+
+        let mut components = mantissa.to_f32_components();
+        components.exponent = (components.exponent as i32 + int_part as i32) as u8;
+        f32::from_f32_components(components)
+    }
+
+    /// Computes log2(x) by reading the exponent bits and approximating the
+    /// logarithm of the mantissa with a 4th order polynomial
+    ///
+    /// ## Accuracy
+    ///
+    /// Absolute error is below 1.3e-4 (ca. 0.0008 dB when used for levels) for
+    /// all positive normal numbers. Yields `-INF` for 0 and `NaN` for negative input.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(8.0.fast_log2(), 3.0);
+    /// assert!((10.0.fast_log2() - 10.0_f32.log2()).abs() < 1.3e-4);
+    /// assert_eq!(0.0.fast_log2(), f32::NEG_INFINITY);
+    /// ```
+    fn fast_log2(&self) -> Self::Output {
+        if *self <= 0.0 {
+            return if *self == 0.0 {
+                f32::NEG_INFINITY
+            } else {
+                f32::NAN
+            };
+        }
+
+        let components = self.to_f32_components();
+        let exponent = components.exponent as i32 - 127;
+        let x = components.mantissa as f32 / (1 << 23) as f32;
+
+        exponent as f32 + x * (LOG2_C[0] + x * (LOG2_C[1] + x * (LOG2_C[2] + x * LOG2_C[3])))
+    }
+
+    /// Computes x^y as 2^(y * log2(x)) with `fast_exp2` and `fast_log2`
+    ///
+    /// ## Accuracy
+    ///
+    /// Only valid for positive bases. The relative error is roughly `|y| * 9e-5`
+    /// on top of the error of `fast_exp2`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert!((10.0.fast_pow(0.5) - 10.0_f32.sqrt()).abs() < 1e-2);
+    /// ```
+    fn fast_pow(&self, exponent: f32) -> Self::Output {
+        (exponent * self.fast_log2()).fast_exp2()
+    }
+
+    /// [7/6] Padé approximant of the hyperbolic tangent
+    ///
+    /// ## Accuracy
+    ///
+    /// Odd, monotonic and saturating at exactly ±1 for |x| >= 4.97.
+    /// Absolute error is below 1e-4 over the whole range.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(0.0.fast_tanh(), 0.0);
+    /// assert!((0.5.fast_tanh() - 0.5_f32.tanh()).abs() < 1e-4);
+    /// assert_eq!(10.0.fast_tanh(), 1.0);
+    /// assert_eq!((-10.0).fast_tanh(), -1.0);
+    /// ```
+    fn fast_tanh(&self) -> Self::Output {
+        if self.abs() >= TANH_SATURATION {
+            return 1.0_f32.copysign(*self);
+        }
+
+        let x2 = self * self;
+        let numerator = 135_135.0 + x2 * (17_325.0 + x2 * (378.0 + x2));
+        let denominator = 135_135.0 + x2 * (62_370.0 + x2 * (3150.0 + 28.0 * x2));
+
+        self * numerator / denominator
+    }
+
+    /// Polynomial approximation of the arcus tangent
+    ///
+    /// ## Accuracy
+    ///
+    /// Absolute error is below 1.2e-5 rad over the whole range.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(0.0.fast_atan(), 0.0);
+    /// assert!((0.5.fast_atan() - 0.5_f32.atan()).abs() < 1.2e-5);
+    /// assert!((100.0.fast_atan() - 100.0_f32.atan()).abs() < 1.2e-5);
+    /// ```
+    fn fast_atan(&self) -> Self::Output {
+        if self.abs() > 1.0 {
+            return FRAC_PI_2.copysign(*self) - __atan_unit_f32(1.0 / self);
+        }
+
+        __atan_unit_f32(*self)
+    }
+
     /// Fixed point approximation of the sine function
     ///
     /// ## Example
@@ -108,12 +324,7 @@ impl AdditionalF32Ext for f32 {
     /// assert_eq!((PI.sin() - PI.fixed_point_sin()).abs(), 0.00000008742278);
     /// ```
     fn fixed_point_sin(&self) -> Self::Output {
-        let normalized_rads = ((self / 2.0 - FRAC_PI_4).rem_euclid(PI) / FRAC_PI_4) - 2.0;
-        let quadrant_rads = normalized_rads.abs() - 1.0;
-
-        unsafe {
-            sin_i16_unchecked((i16::MAX as f32 * quadrant_rads) as i16, 4) as f32 / i16::MAX as f32
-        }
+        __fixed_point_sin_normalized_f32((wrap_phase(self - FRAC_PI_2) / FRAC_PI_2) - 2.0)
     }
 
     /// Fixed point approximation of the sine function
@@ -131,6 +342,40 @@ impl AdditionalF32Ext for f32 {
         (FRAC_PI_2 - self).fixed_point_sin()
     }
 
+    /// Fixed point approximation of the sine and cosine function, returned as `(sin, cos)`
+    ///
+    /// Only does the range reduction once, which makes it roughly twice as fast as
+    /// calling `fixed_point_sin` and `fixed_point_cos` separately.
+    ///
+    /// ## Accuracy
+    /// Same as `fixed_point_sin`, absolute error below 1e-4.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use core::f32::consts::FRAC_PI_3;
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// let (sin, cos) = FRAC_PI_3.sincos();
+    ///
+    /// assert!((sin - FRAC_PI_3.sin()).abs() < 1e-4);
+    /// assert!((cos - FRAC_PI_3.cos()).abs() < 1e-4);
+    /// ```
+    fn sincos(&self) -> (Self::Output, Self::Output) {
+        let normalized_sin = (wrap_phase(self - FRAC_PI_2) / FRAC_PI_2) - 2.0;
+
+        // cos(x) = sin(x + π/2), which is a quarter turn further in the normalized range [-2, 2)
+        let normalized_cos = if normalized_sin < 1.0 {
+            normalized_sin + 1.0
+        } else {
+            normalized_sin - 3.0
+        };
+
+        (
+            __fixed_point_sin_normalized_f32(normalized_sin),
+            __fixed_point_sin_normalized_f32(normalized_cos),
+        )
+    }
+
     /// Accepts values between 0 and 1, otherwise clamps at boundery
     ///
     /// Bandlimited rectangle function
@@ -165,13 +410,77 @@ impl AdditionalF32Ext for f32 {
         return 0.0;
     }
 
+    /// Computes 1/sqrt(x) with the well known bit hack followed by a single Newton step,
+    /// e.g. to turn an RMS value into a normalization gain
+    ///
+    /// ## Accuracy
+    /// Relative error is below 6.6e-4. Only valid for positive normal numbers.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert!((4.0.fast_rsqrt() - 0.5).abs() < 0.5 * 6.6e-4);
+    /// ```
+    fn fast_rsqrt(&self) -> Self::Output {
+        let y = f32::from_raw_word(RSQRT_MAGIC - (self.to_raw_word() >> 1));
+        RSQRT_C[0] * y * (RSQRT_C[1] - self * y * y)
+    }
+
+    /// Hyperbolic tangent based on a range reduced exp, e.g. to build waveshaper tables
+    /// on-device. Slower than `fast_tanh`, but accurate.
+    ///
+    /// ## Accuracy
+    /// Absolute error is below 1e-6.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(0.0.accurate_tanh(), 0.0);
+    /// assert!((0.5.accurate_tanh() - 0.46211716).abs() < 1e-6);
+    /// assert_eq!((-20.0).accurate_tanh(), -1.0);
+    /// ```
+    fn accurate_tanh(&self) -> Self::Output {
+        let abs = self.abs();
+
+        // tanh(x) = x - x^3/3 + ... is exact enough and avoids the cancellation below
+        if abs < 1e-3 {
+            return *self;
+        }
+
+        if abs >= ACCURATE_TANH_SATURATION {
+            return 1.0_f32.copysign(*self);
+        }
+
+        (1.0 - 2.0 / (__exp_f32(2.0 * abs) + 1.0)).copysign(*self)
+    }
+
+    /// Bipolar logistic function 2 / (1 + e^(-steepness * x)) - 1, which maps onto (-1, 1)
+    ///
+    /// A `steepness` of 2 equals `tanh(x)`, higher values approach a hard clip.
+    ///
+    /// ## Accuracy
+    /// Absolute error is below 1e-6.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use embedded_audio_tools::float::AdditionalF32Ext;
+    ///
+    /// assert_eq!(0.0.parametric_sigmoid(4.0), 0.0);
+    /// assert!((0.5.parametric_sigmoid(2.0) - 0.5.accurate_tanh()).abs() < 1e-6);
+    /// ```
+    fn parametric_sigmoid(&self, steepness: f32) -> Self::Output {
+        (self * steepness * 0.5).accurate_tanh()
+    }
+
     /// Interpolated fixed point approximation lookup of the sine function
     ///
     /// Not accurate at all in moment!
     fn lookup_sin(&self) -> Self::Output {
         const SINE_LOOKUP: [i16; 4096] = sine_table::<4096>();
 
-        let normalized_rads = ((self / 2.0 - FRAC_PI_4).rem_euclid(PI) / FRAC_PI_4) - 2.0;
+        let normalized_rads = (wrap_phase(self - FRAC_PI_2) / FRAC_PI_2) - 2.0;
         let quadrant_rads = normalized_rads.abs() / 2.0;
 
         let f_index = (SINE_LOOKUP.len() - 1) as f32 * quadrant_rads;
@@ -195,7 +504,39 @@ impl AdditionalF32Ext for f32 {
 
     /// Computes the sine integral from 0 to `self`. The smaller the number, the more accurate the result.
     fn si(&self) -> Self::Output {
-        simpsons_rule::<1000>(__sinc_f32, 0.0, *self)
+        simpsons_rule::<f32, 1000>(__sinc_f32, 0.0, *self)
+    }
+}
+
+/// Computes atan(x) for f32 with |x| <= 1
+#[inline(always)]
+fn __atan_unit_f32(val: f32) -> f32 {
+    let x2 = val * val;
+    val * (ATAN_C[0] + x2 * (ATAN_C[1] + x2 * (ATAN_C[2] + x2 * (ATAN_C[3] + x2 * ATAN_C[4]))))
+}
+
+/// Computes e^x for f32 with 0 <= x < 88 by splitting off multiples of ln(2)
+/// and approximating the remainder with a 6th order Taylor polynomial
+#[inline(always)]
+fn __exp_f32(val: f32) -> f32 {
+    let k = (val * LOG2_E + 0.5).floor();
+    let r = val - k * LN_2_HI - k * LN_2_LO;
+
+    let poly = 1.0
+        + r * (1.0
+            + r * (1.0 / 2.0
+                + r * (1.0 / 6.0 + r * (1.0 / 24.0 + r * (1.0 / 120.0 + r * (1.0 / 720.0))))));
+
+    poly * f32::from_raw_word(((k as i32 + 127) as u32) << 23)
+}
+
+/// Computes sin(x) for f32 with x mapped onto [-2, 2), where ±1 are the zero crossings
+#[inline(always)]
+fn __fixed_point_sin_normalized_f32(normalized_rads: f32) -> f32 {
+    let quadrant_rads = normalized_rads.abs() - 1.0;
+
+    unsafe {
+        sin_i16_unchecked((i16::MAX as f32 * quadrant_rads) as i16, 4) as f32 / i16::MAX as f32
     }
 }
 
@@ -208,3 +549,120 @@ fn __sinc_f32(val: f32) -> f32 {
         val.fixed_point_sin() / val
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_exp2_error() {
+        for i in -20_000..20_000 {
+            let x = i as f32 * 0.001;
+            let exact = (x as f64).exp2() as f32;
+            let error = ((x.fast_exp2() - exact) / exact).abs();
+            assert!(error < 4e-6, "x: {}, error: {}", x, error);
+        }
+    }
+
+    #[test]
+    fn fast_log2_error() {
+        for i in 1..100_000 {
+            let x = i as f32 * 0.01;
+            let error = (x.fast_log2() - (x as f64).log2() as f32).abs();
+            assert!(error < 1.3e-4, "x: {}, error: {}", x, error);
+        }
+
+        assert!((-1.0).fast_log2().is_nan());
+    }
+
+    #[test]
+    fn fast_tanh_error() {
+        let mut last = -1.0;
+        for i in -10_000..10_000 {
+            let x = i as f32 * 0.001;
+            let y = x.fast_tanh();
+            let error = (y - (x as f64).tanh() as f32).abs();
+            assert!(error < 1e-4, "x: {}, error: {}", x, error);
+            assert!(y >= last, "not monotonic at x: {}", x);
+            last = y;
+        }
+    }
+
+    #[test]
+    fn full_range_tan_error() {
+        for i in -1570..=1570 {
+            let x = i as f32 * 0.001;
+            let exact = (x as f64).tan();
+            let error = ((x.full_range_tan() as f64 - exact) / exact).abs();
+            assert!(x == 0.0 || error < 1.5e-5, "x: {}, error: {}", x, error);
+        }
+
+        for i in -10_000..10_000 {
+            let x = i as f32 * 0.001;
+            let exact = (x as f64).tan();
+
+            if exact.abs() > 10.0 {
+                continue;
+            }
+
+            let error = (x.full_range_tan() as f64 - exact).abs();
+            assert!(error < 1e-4, "x: {}, error: {}", x, error);
+        }
+    }
+
+    #[test]
+    fn fast_rsqrt_error() {
+        for i in 1..100_000 {
+            let x = i as f32 * 0.0137;
+            let exact = 1.0 / (x as f64).sqrt();
+            let error = ((x.fast_rsqrt() as f64 - exact) / exact).abs();
+            assert!(error < 6.6e-4, "x: {}, error: {}", x, error);
+        }
+    }
+
+    #[test]
+    fn accurate_tanh_error() {
+        let mut last = -1.0;
+        for i in -100_000..100_000 {
+            let x = i as f32 * 0.0001;
+            let y = x.accurate_tanh();
+            let error = (y as f64 - (x as f64).tanh()).abs();
+            assert!(error < 1e-6, "x: {}, error: {}", x, error);
+            assert!(y >= last, "not monotonic at x: {}", x);
+            last = y;
+        }
+    }
+
+    #[test]
+    fn parametric_sigmoid_error() {
+        for steepness in [0.5, 1.0, 2.0, 8.0] {
+            for i in -10_000..10_000 {
+                let x = i as f32 * 0.001;
+                let exact = 2.0 / (1.0 + (-(steepness * x) as f64).exp()) - 1.0;
+                let error = (x.parametric_sigmoid(steepness) as f64 - exact).abs();
+                assert!(error < 1e-6, "x: {}, error: {}", x, error);
+            }
+        }
+    }
+
+    #[test]
+    fn sincos_error() {
+        for i in -10_000..10_000 {
+            let x = i as f32 * 0.001;
+            let (sin, cos) = x.sincos();
+
+            assert!((sin - (x as f64).sin() as f32).abs() < 1e-4, "x: {}", x);
+            assert!((cos - (x as f64).cos() as f32).abs() < 1e-4, "x: {}", x);
+            assert_eq!(sin, x.fixed_point_sin());
+        }
+    }
+
+    #[test]
+    fn fast_atan_error() {
+        for i in -10_000..10_000 {
+            let x = i as f32 * 0.01;
+            let error = (x.fast_atan() - (x as f64).atan() as f32).abs();
+            assert!(error < 1.2e-5, "x: {}, error: {}", x, error);
+        }
+    }
+}
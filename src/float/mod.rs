@@ -1,14 +1,31 @@
+mod backend;
 mod bit_manipulation;
 mod conversion;
+mod crossfade;
+mod denormal;
 mod dsp_util;
+mod easing;
+mod generic;
+mod ieee754;
+mod integral;
 mod interpolation;
 mod math;
+mod range;
+mod statistics;
 
-pub(crate) mod integral;
+pub(crate) mod sample_format;
 
+pub use backend::F32Ext;
 pub use bit_manipulation::*;
 pub use conversion::*;
+pub use crossfade::{crossfade, crossfade_gains, CrossfadeCurve};
+pub use denormal::{flush_denormal, ANTI_DENORMAL};
 pub use dsp_util::DSPUtility;
+pub use easing::{ease, smootherstep, smoothstep, Easing};
+pub use generic::Float;
+pub use ieee754::{exponent, mantissa_fraction, next_down, next_up, ulp_distance};
+pub use integral::simpsons_rule;
 pub use interpolation::*;
 pub use math::AdditionalF32Ext;
-pub use micromath::F32Ext;
+pub use range::{fold, mirror, wrap, wrap_phase, wrap_phase_symmetric};
+pub use statistics::{mean, rms, variance, windowed_rms};
@@ -0,0 +1,200 @@
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+use core::f32::consts::{PI, TAU};
+
+/// Wraps `x` into `lo` (inclusive) to `hi` (exclusive), like a phase wrapping around
+///
+/// `hi` has to be greater than `lo`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::wrap;
+///
+/// assert_eq!(wrap(1.25, 0.0, 1.0), 0.25);
+/// assert_eq!(wrap(-0.25, 0.0, 1.0), 0.75);
+/// assert_eq!(wrap(1.0, 0.0, 1.0), 0.0);
+/// ```
+#[inline(always)]
+pub fn wrap(x: f32, lo: f32, hi: f32) -> f32 {
+    let range = hi - lo;
+    let t = (x - lo) / range;
+    let wrapped = lo + (t - t.floor()) * range;
+
+    // Rounding can land exactly on the upper bound
+    if wrapped >= hi {
+        lo
+    } else {
+        wrapped
+    }
+}
+
+/// Folds `x` back into `lo` to `hi` by reflecting it at both bounds, e.g. for wavefolding
+///
+/// `hi` has to be greater than `lo`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::fold;
+///
+/// assert_eq!(fold(1.25, -1.0, 1.0), 0.75);
+/// assert_eq!(fold(-1.5, -1.0, 1.0), -0.5);
+/// assert_eq!(fold(3.5, -1.0, 1.0), -0.5);
+/// ```
+#[inline(always)]
+pub fn fold(x: f32, lo: f32, hi: f32) -> f32 {
+    let range = hi - lo;
+    let t = (x - lo) / (2.0 * range);
+    let triangle = 1.0 - (1.0 - 2.0 * (t - t.floor())).abs();
+
+    lo + triangle * range
+}
+
+/// Reflects everything below `center` to the other side of it
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::mirror;
+///
+/// assert_eq!(mirror(-0.5, 0.0), 0.5);
+/// assert_eq!(mirror(0.25, 0.5), 0.75);
+/// assert_eq!(mirror(0.75, 0.5), 0.75);
+/// ```
+#[inline(always)]
+pub fn mirror(x: f32, center: f32) -> f32 {
+    center + (x - center).abs()
+}
+
+/// Wraps a phase in radians into 0 (inclusive) to TAU (exclusive)
+///
+/// Phases less than one period outside the range only cost a single addition or
+/// subtraction, everything else falls back to `rem_euclid`.
+///
+/// ## Example
+/// ```rust
+/// # use core::f32::consts::{PI, TAU};
+/// use embedded_audio_tools::float::wrap_phase;
+///
+/// assert_eq!(wrap_phase(PI), PI);
+/// assert_eq!(wrap_phase(TAU), 0.0);
+/// assert_eq!(wrap_phase(-PI), PI);
+/// ```
+#[inline(always)]
+pub fn wrap_phase(phase: f32) -> f32 {
+    let wrapped = if phase >= TAU {
+        if phase < 2.0 * TAU {
+            phase - TAU
+        } else {
+            phase.rem_euclid(TAU)
+        }
+    } else if phase < 0.0 {
+        if phase >= -TAU {
+            phase + TAU
+        } else {
+            phase.rem_euclid(TAU)
+        }
+    } else {
+        return phase;
+    };
+
+    // Tiny negative phases round up to TAU
+    if wrapped >= TAU {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// Wraps a phase in radians into -PI (inclusive) to PI (exclusive)
+///
+/// Same fast path as `wrap_phase`, but keeps full precision around zero.
+///
+/// ## Example
+/// ```rust
+/// # use core::f32::consts::{PI, TAU};
+/// use embedded_audio_tools::float::wrap_phase_symmetric;
+///
+/// assert_eq!(wrap_phase_symmetric(1.0), 1.0);
+/// assert_eq!(wrap_phase_symmetric(PI), -PI);
+/// assert!((wrap_phase_symmetric(-PI - 1.0) - (PI - 1.0)).abs() < 1e-6);
+/// ```
+#[inline(always)]
+pub fn wrap_phase_symmetric(phase: f32) -> f32 {
+    if (-PI..PI).contains(&phase) {
+        return phase;
+    }
+
+    let wrapped = if (-PI - TAU..PI + TAU).contains(&phase) {
+        phase - TAU.copysign(phase)
+    } else {
+        wrap_phase(phase + PI) - PI
+    };
+
+    if wrapped >= PI {
+        -PI
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_bounds() {
+        for i in -10_000..10_000 {
+            let x = i as f32 * 0.0137;
+            let val = wrap(x, -0.5, 2.0);
+            assert!((-0.5..2.0).contains(&val), "x: {}, val: {}", x, val);
+        }
+    }
+
+    #[test]
+    fn wrap_identity_in_range() {
+        for i in 0..100 {
+            let x = i as f32 * 0.01;
+            assert!((wrap(x, 0.0, 1.0) - x).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn phase_bounds() {
+        for i in -100_000..100_000 {
+            let x = i as f32 * 0.000_37;
+
+            let val = wrap_phase(x);
+            assert!((0.0..TAU).contains(&val), "x: {}, val: {}", x, val);
+            assert!((val.sin() - x.sin()).abs() < 1e-3, "x: {}", x);
+
+            let val = wrap_phase_symmetric(x);
+            assert!((-PI..PI).contains(&val), "x: {}, val: {}", x, val);
+            assert!((val.sin() - x.sin()).abs() < 1e-3, "x: {}", x);
+        }
+
+        assert_eq!(wrap_phase(-1e-9), 0.0);
+        assert_eq!(wrap_phase_symmetric(1e-9), 1e-9);
+    }
+
+    #[test]
+    fn fold_bounds_and_continuity() {
+        let mut last = fold(-10.0, -1.0, 1.0);
+
+        for i in -9_999..10_000 {
+            let x = i as f32 * 0.001;
+            let val = fold(x, -1.0, 1.0);
+
+            assert!((-1.0..=1.0).contains(&val), "x: {}, val: {}", x, val);
+            assert!((val - last).abs() < 0.0011, "x: {}", x);
+            last = val;
+        }
+    }
+
+    #[test]
+    fn fold_identity_in_range() {
+        for i in -100..=100 {
+            let x = i as f32 * 0.01;
+            assert!((fold(x, -1.0, 1.0) - x).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::Dither;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+const I16_SCALE: f32 = 32_768.0;
+const I24_SCALE: f32 = 8_388_608.0;
+const I24_MAX: i32 = 0x7F_FFFF;
+const I24_MIN: i32 = -0x80_0000;
+
+/// Converts a 16 bit sample to a float between -1 (inclusive) and 1 (exclusive)
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::i16_to_f32;
+///
+/// assert_eq!(i16_to_f32(i16::MIN), -1.0);
+/// assert_eq!(i16_to_f32(16_384), 0.5);
+/// ```
+#[inline(always)]
+pub fn i16_to_f32(sample: i16) -> f32 {
+    sample as f32 / I16_SCALE
+}
+
+/// Converts a float to a 16 bit sample, rounding to the nearest step
+///
+/// -1.0 maps onto `i16::MIN`, everything at or above 32767/32768 saturates at `i16::MAX`.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::f32_to_i16;
+///
+/// assert_eq!(f32_to_i16(-1.0), i16::MIN);
+/// assert_eq!(f32_to_i16(0.5), 16_384);
+/// assert_eq!(f32_to_i16(1.0), i16::MAX);
+/// ```
+#[inline(always)]
+pub fn f32_to_i16(sample: f32) -> i16 {
+    // Float to int casts saturate and map NaN to 0
+    (sample * I16_SCALE).round() as i16
+}
+
+/// Like `f32_to_i16`, but applies TPDF dither (and noise shaping, if enabled) first
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::f32_to_i16_dithered;
+/// use embedded_audio_tools::float::Dither;
+///
+/// let mut dither = Dither::default();
+///
+/// assert!((f32_to_i16_dithered(0.5, &mut dither) - 16_384).abs() <= 1);
+/// ```
+#[inline(always)]
+pub fn f32_to_i16_dithered(sample: f32, dither: &mut Dither) -> i16 {
+    f32_to_i16(dither.process_unchecked(sample, 16))
+}
+
+/// Converts a 24 bit sample, sign extended in an `i32`, to a float between -1 (inclusive)
+/// and 1 (exclusive)
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::i24_to_f32;
+///
+/// assert_eq!(i24_to_f32(-0x80_0000), -1.0);
+/// assert_eq!(i24_to_f32(0x40_0000), 0.5);
+/// ```
+#[inline(always)]
+pub fn i24_to_f32(sample: i32) -> f32 {
+    sample as f32 / I24_SCALE
+}
+
+/// Converts a float to a 24 bit sample, sign extended in an `i32`, rounding to the nearest step
+///
+/// -1.0 maps onto -0x80_0000, everything at or above 0x7F_FFFF/0x80_0000 saturates at 0x7F_FFFF.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::f32_to_i24;
+///
+/// assert_eq!(f32_to_i24(-1.0), -0x80_0000);
+/// assert_eq!(f32_to_i24(0.5), 0x40_0000);
+/// assert_eq!(f32_to_i24(2.0), 0x7F_FFFF);
+/// ```
+#[inline(always)]
+pub fn f32_to_i24(sample: f32) -> i32 {
+    ((sample * I24_SCALE).round() as i32).clamp(I24_MIN, I24_MAX)
+}
+
+/// Like `f32_to_i24`, but applies TPDF dither (and noise shaping, if enabled) first
+#[inline(always)]
+pub fn f32_to_i24_dithered(sample: f32, dither: &mut Dither) -> i32 {
+    f32_to_i24(dither.process_unchecked(sample, 8))
+}
+
+/// Sign extends the lower 24 bits of a raw word, e.g. from an I2S peripheral
+///
+/// For left aligned data, shift it right by 8 bits first.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::convert::sign_extend_i24;
+///
+/// assert_eq!(sign_extend_i24(0x00FF_FFFF), -1);
+/// assert_eq!(sign_extend_i24(0xAA7F_FFFF), 0x7F_FFFF);
+/// ```
+#[inline(always)]
+pub fn sign_extend_i24(raw: u32) -> i32 {
+    ((raw << 8) as i32) >> 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn i16_round_trip() {
+        for sample in i16::MIN..=i16::MAX {
+            assert_eq!(f32_to_i16(i16_to_f32(sample)), sample);
+        }
+
+        assert_eq!(f32_to_i16(f32::NAN), 0);
+        assert_eq!(f32_to_i16(-5.0), i16::MIN);
+    }
+
+    #[test]
+    fn i24_round_trip() {
+        for sample in (I24_MIN..=I24_MAX).step_by(127).chain([I24_MAX]) {
+            assert_eq!(f32_to_i24(i24_to_f32(sample)), sample);
+        }
+
+        assert_eq!(f32_to_i24(-5.0), I24_MIN);
+    }
+
+    #[test]
+    fn dithered_stays_close() {
+        let mut dither = Dither::default();
+
+        for i in -1000..1000 {
+            let sample = i as f32 * 0.000_99;
+
+            let error = f32_to_i16_dithered(sample, &mut dither) as i32 - f32_to_i16(sample) as i32;
+            assert!(error.abs() <= 1);
+
+            let error = f32_to_i24_dithered(sample, &mut dither) - f32_to_i24(sample);
+            assert!(error.abs() <= 1);
+        }
+    }
+}
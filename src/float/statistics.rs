@@ -0,0 +1,121 @@
+use crate::block::kernels;
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Arithmetic mean of `samples`, 0.0 for an empty slice
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::mean;
+///
+/// assert_eq!(mean(&[1.0, 2.0, 3.0, 6.0]), 3.0);
+/// ```
+pub fn mean(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    kernels::sum(samples) / samples.len() as f32
+}
+
+/// Population variance of `samples`, 0.0 for an empty slice
+///
+/// Computed in two passes, which stays accurate for signals with a large DC offset.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::variance;
+///
+/// assert_eq!(variance(&[1.0, -1.0, 1.0, -1.0]), 1.0);
+/// ```
+pub fn variance(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let mean = mean(samples);
+
+    samples
+        .iter()
+        .map(|sample| (sample - mean) * (sample - mean))
+        .sum::<f32>()
+        / samples.len() as f32
+}
+
+/// Root mean square of `samples`, 0.0 for an empty slice
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::rms;
+///
+/// assert_eq!(rms(&[0.5, -0.5, 0.5, -0.5]), 0.5);
+/// ```
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    (kernels::dot(samples, samples) / samples.len() as f32).sqrt()
+}
+
+/// Root mean square of `samples` weighted by `window`, e.g. a Hann window to soften block edges
+///
+/// Only the overlapping part of both slices is used. Returns 0.0 if the window sums up to zero.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::windowed_rms;
+///
+/// // A rectangular window equals the plain RMS
+/// assert_eq!(windowed_rms(&[0.5, -0.5, 0.5, -0.5], &[1.0; 4]), 0.5);
+///
+/// // Only the samples in the middle count
+/// assert_eq!(windowed_rms(&[4.0, 1.0, -1.0, 4.0], &[0.0, 1.0, 1.0, 0.0]), 1.0);
+/// ```
+pub fn windowed_rms(samples: &[f32], window: &[f32]) -> f32 {
+    let (power, weight) = samples
+        .iter()
+        .zip(window)
+        .fold((0.0, 0.0), |(power, weight), (sample, w)| {
+            (power + w * sample * sample, weight + w)
+        });
+
+    if weight == 0.0 {
+        return 0.0;
+    }
+
+    (power / weight).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_slices() {
+        assert_eq!(mean(&[]), 0.0);
+        assert_eq!(variance(&[]), 0.0);
+        assert_eq!(rms(&[]), 0.0);
+        assert_eq!(windowed_rms(&[], &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn dc_offset() {
+        let samples = [1000.5, 999.5, 1000.5, 999.5];
+
+        assert_eq!(mean(&samples), 1000.0);
+        assert_eq!(variance(&samples), 0.25);
+    }
+
+    #[test]
+    fn sine_rms() {
+        let mut samples = [0.0; 480];
+
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample = (core::f32::consts::TAU * i as f32 / 48.0).sin();
+        }
+
+        assert!((rms(&samples) - core::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!((variance(&samples) - 0.5).abs() < 1e-3);
+    }
+}
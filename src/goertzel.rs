@@ -0,0 +1,131 @@
+use core::f32::consts::TAU;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/**
+Single bin DFT for detecting the presence of a tone, e.g. DTMF, pilot tones or
+feedback frequencies. Way cheaper than a full FFT when only a few frequencies matter.
+
+Collects `block_size` samples and then outputs the amplitude of the target frequency,
+so a sine at full scale results in roughly 1.0.
+
+### Example
+
+```rust
+use embedded_audio_tools::Goertzel;
+
+let mut detector = Goertzel::new(1000.0, 480, 48_000.0);
+
+// during audio callback
+for i in 0..480 {
+    let sample = (core::f32::consts::TAU * 1000.0 * i as f32 / 48_000.0).sin();
+
+    if let Some(amplitude) = detector.tick(sample) {
+        assert!((amplitude - 1.0).abs() < 0.01);
+    }
+}
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Goertzel {
+    coeff: f32,
+    s1: f32,
+    s2: f32,
+    block_size: usize,
+    count: usize,
+}
+
+impl Goertzel {
+    /// A `block_size` of 0 is treated as 1
+    pub fn new(freq: f32, block_size: usize, sr: f32) -> Goertzel {
+        let mut goertzel = Goertzel {
+            coeff: 0.0,
+            s1: 0.0,
+            s2: 0.0,
+            block_size: block_size.max(1),
+            count: 0,
+        };
+
+        goertzel.set_freq_unchecked(freq, sr);
+        goertzel
+    }
+
+    /// Takes effect immediately, so it is best called between blocks
+    #[inline(always)]
+    pub fn set_freq_unchecked(&mut self, freq: f32, sr: f32) {
+        self.coeff = 2.0 * (TAU * freq / sr).cos();
+    }
+
+    /// Restarts the current block. A `block_size` of 0 is treated as 1
+    #[inline(always)]
+    pub fn set_block_size(&mut self, block_size: usize) {
+        self.block_size = block_size.max(1);
+        self.reset();
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.s1 = 0.0;
+        self.s2 = 0.0;
+        self.count = 0;
+    }
+
+    /// Returns the amplitude of the target frequency once a block is complete
+    pub fn tick(&mut self, input: f32) -> Option<f32> {
+        let s0 = input + self.coeff * self.s1 - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s0;
+
+        self.count += 1;
+
+        if self.count < self.block_size {
+            return None;
+        }
+
+        let power = self.s1 * self.s1 + self.s2 * self.s2 - self.coeff * self.s1 * self.s2;
+        let amplitude = power.max(0.0).sqrt() * 2.0 / self.block_size as f32;
+
+        self.reset();
+        Some(amplitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 8000.0;
+    const BLOCK: usize = 205;
+
+    fn detect(target: f32, tone: f32) -> f32 {
+        let mut detector = Goertzel::new(target, BLOCK, SR);
+
+        (0..BLOCK)
+            .filter_map(|i| detector.tick(0.5 * (TAU * tone * i as f32 / SR).sin()))
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn detects_target() {
+        // DTMF row and column frequencies
+        assert!((detect(697.0, 697.0) - 0.5).abs() < 0.05);
+        assert!((detect(1209.0, 1209.0) - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn rejects_other_tones() {
+        assert!(detect(697.0, 941.0) < 0.05);
+        assert!(detect(1209.0, 1477.0) < 0.05);
+    }
+
+    #[test]
+    fn output_once_per_block() {
+        let mut detector = Goertzel::new(1000.0, 4, SR);
+
+        let outputs = (0..16).filter_map(|_| detector.tick(1.0)).count();
+        assert_eq!(outputs, 4);
+    }
+}
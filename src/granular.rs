@@ -0,0 +1,321 @@
+use core::f32::consts::TAU;
+
+use crate::memory::{memory_slice::MemorySlice, NonMutable};
+use crate::oscillator::noise::WhiteNoise;
+use crate::stereo::stereo_pan_unchecked;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Shortest grain in samples
+const MIN_GRAIN_LEN: u32 = 2;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+struct Grain {
+    active: bool,
+    /// Read position in the source, in samples
+    pos: f32,
+    rate: f32,
+    remaining: u32,
+
+    /// Hann window as a cosine recurrence, `cos` and `cos_prev` are the last two values
+    cos: f32,
+    cos_prev: f32,
+    coeff: f32,
+
+    gains: (f32, f32),
+}
+
+impl Grain {
+    const IDLE: Grain = Grain {
+        active: false,
+        pos: 0.0,
+        rate: 1.0,
+        remaining: 0,
+        cos: 1.0,
+        cos_prev: 1.0,
+        coeff: 1.0,
+        gains: (0.0, 0.0),
+    };
+
+    fn start(&mut self, pos: f32, rate: f32, len: u32, gains: (f32, f32)) {
+        let step = TAU / len as f32;
+
+        *self = Grain {
+            active: true,
+            pos,
+            rate,
+            remaining: len,
+            // One step before the start, so the first window value is 0
+            cos: 1.0,
+            cos_prev: step.cos(),
+            coeff: 2.0 * step.cos(),
+            gains,
+        };
+    }
+
+    #[inline(always)]
+    fn tick(&mut self, source: &MemorySlice<NonMutable>) -> (f32, f32) {
+        let window = (0.5 - 0.5 * self.cos).max(0.0);
+        let sample = source.hermite_wrapped(self.pos) * window;
+
+        let cos = self.coeff * self.cos - self.cos_prev;
+        self.cos_prev = self.cos;
+        self.cos = cos;
+
+        self.pos += self.rate;
+        self.remaining -= 1;
+        self.active = self.remaining > 0;
+
+        (sample * self.gains.0, sample * self.gains.1)
+    }
+}
+
+/**
+Granular synthesis over a loaded sample, with up to `GRAINS` Hann windowed grains at once.
+
+New grains start `density` times per second around `position` in the source. `jitter`
+scatters their start positions, `spread` their panning. Grains read past the end of the
+source wrap around to its start. When all grains are busy, new ones are skipped.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice, GranularEngine};
+
+static SAMPLE: [f32; 4800] = [0.25; 4800];
+
+let mut engine: GranularEngine<16> = GranularEngine::new(from_slice(&SAMPLE), 48_000.0);
+
+engine.set_position(0.5);
+engine.set_size(80.0);
+engine.set_density(40.0);
+engine.set_pitch(0.5);
+engine.set_jitter(0.1);
+engine.set_spread(0.8);
+
+// during audio callback
+let (left, right) = engine.tick();
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct GranularEngine<const GRAINS: usize> {
+    source: MemorySlice<NonMutable>,
+    grains: [Grain; GRAINS],
+    noise: WhiteNoise,
+    sr: f32,
+
+    position: f32,
+    grain_len: u32,
+    pitch: f32,
+    interval: f32,
+    jitter: f32,
+    spread: f32,
+
+    /// Samples until the next grain starts
+    countdown: f32,
+}
+
+impl<const GRAINS: usize> GranularEngine<GRAINS> {
+    /// Starts at the beginning of the source with 50 ms grains, 20 grains per second,
+    /// original pitch and neither jitter nor spread
+    pub fn new(source: MemorySlice<NonMutable>, sr: f32) -> GranularEngine<GRAINS> {
+        let mut engine = GranularEngine {
+            source,
+            grains: [Grain::IDLE; GRAINS],
+            noise: WhiteNoise::default(),
+            sr,
+            position: 0.0,
+            grain_len: MIN_GRAIN_LEN,
+            pitch: 1.0,
+            interval: 1.0,
+            jitter: 0.0,
+            spread: 0.0,
+            countdown: 0.0,
+        };
+
+        engine.set_size(50.0);
+        engine.set_density(20.0);
+        engine
+    }
+
+    /// Center of the grain start positions, clamps between 0 (start) and 1 (end of the
+    /// source)
+    #[inline(always)]
+    pub fn set_position(&mut self, position: f32) {
+        self.position = position.clamp(0.0, 1.0);
+    }
+
+    /// Grain length in ms, applies to new grains
+    #[inline(always)]
+    pub fn set_size(&mut self, ms: f32) {
+        self.grain_len = ((ms * 0.001 * self.sr) as u32).max(MIN_GRAIN_LEN);
+    }
+
+    /// Playback rate of new grains, 2.0 is an octave up. Negative rates are treated as 0.
+    #[inline(always)]
+    pub fn set_pitch(&mut self, ratio: f32) {
+        self.pitch = ratio.max(0.0);
+    }
+
+    /// New grains per second, values below 0.01 are treated as 0.01
+    #[inline(always)]
+    pub fn set_density(&mut self, grains_per_sec: f32) {
+        self.interval = self.sr / grains_per_sec.max(0.01);
+        self.countdown = self.countdown.min(self.interval);
+    }
+
+    /// Random offset of the start positions, as a share of the source length. Clamps
+    /// between 0 and 1.
+    #[inline(always)]
+    pub fn set_jitter(&mut self, jitter: f32) {
+        self.jitter = jitter.clamp(0.0, 1.0);
+    }
+
+    /// Random panning of new grains, from centered (0) to anywhere between hard left and
+    /// right (1). Clamps between 0 and 1.
+    #[inline(always)]
+    pub fn set_spread(&mut self, spread: f32) {
+        self.spread = spread.clamp(0.0, 1.0);
+    }
+
+    #[inline(always)]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.noise.set_seed(seed);
+    }
+
+    /// Swaps the source, running grains stop
+    pub fn change_source(&mut self, source: MemorySlice<NonMutable>) {
+        self.source = source;
+        self.reset();
+    }
+
+    /// Stops all grains, the next one starts right away
+    pub fn reset(&mut self) {
+        self.grains = [Grain::IDLE; GRAINS];
+        self.countdown = 0.0;
+    }
+
+    /// Number of grains currently playing
+    pub fn active_grains(&self) -> usize {
+        self.grains.iter().filter(|grain| grain.active).count()
+    }
+
+    pub fn tick(&mut self) -> (f32, f32) {
+        if self.source.len() == 0 {
+            return (0.0, 0.0);
+        }
+
+        self.countdown -= 1.0;
+        if self.countdown <= 0.0 {
+            self.countdown += self.interval;
+            self.spawn();
+        }
+
+        let mut out = (0.0, 0.0);
+
+        for grain in self.grains.iter_mut().filter(|grain| grain.active) {
+            let (left, right) = grain.tick(&self.source);
+            out.0 += left;
+            out.1 += right;
+        }
+
+        out
+    }
+
+    fn spawn(&mut self) {
+        let Some(index) = self.grains.iter().position(|grain| !grain.active) else {
+            return;
+        };
+
+        let len = self.source.len() as f32;
+        let offset = self.noise.tick() * self.jitter * len;
+        let pos = (self.position * len + offset).rem_euclid(len);
+
+        let pan = self.noise.tick() * self.spread;
+        let gains = stereo_pan_unchecked(pan, (1.0, 1.0));
+
+        self.grains[index].start(pos, self.pitch, self.grain_len, gains);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::memory_slice::from_slice;
+
+    const SR: f32 = 1000.0;
+
+    static DC: [f32; 1000] = [1.0; 1000];
+
+    #[test]
+    fn hann_window() {
+        let mut engine: GranularEngine<4> = GranularEngine::new(from_slice(&DC), SR);
+        engine.set_size(100.0);
+        engine.set_density(1.0);
+
+        let out: [(f32, f32); 100] = core::array::from_fn(|_| engine.tick());
+
+        assert_eq!(out[0].0, 0.0);
+        assert!((out[50].0 - core::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+        assert!(out.iter().all(|(left, right)| left == right));
+
+        // Grain is done, the next one starts after a second
+        assert_eq!(engine.active_grains(), 0);
+        assert_eq!(engine.tick(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn density_and_capacity() {
+        let mut engine: GranularEngine<4> = GranularEngine::new(from_slice(&DC), SR);
+        engine.set_size(50.0);
+        engine.set_density(40.0);
+
+        // 2 grains overlap on average
+        let mut max_active = 0;
+        for _ in 0..1000 {
+            engine.tick();
+            max_active = max_active.max(engine.active_grains());
+        }
+        assert_eq!(max_active, 2);
+
+        // Far more requested than available
+        engine.set_density(500.0);
+        for _ in 0..100 {
+            engine.tick();
+        }
+        assert_eq!(engine.active_grains(), 4);
+    }
+
+    #[test]
+    fn reads_around_position() {
+        let ramp: [f32; 1000] = core::array::from_fn(|i| i as f32 / 1000.0);
+
+        let mut engine: GranularEngine<1> = GranularEngine::new(from_slice(&ramp), SR);
+        engine.set_position(0.25);
+        engine.set_size(20.0);
+        engine.set_pitch(2.0);
+        engine.tick();
+
+        // Reads from 0.25 upwards at twice the speed, under the window
+        let grain = engine.grains[0];
+        assert_eq!(grain.pos, 252.0);
+        assert_eq!(grain.rate, 2.0);
+    }
+
+    #[test]
+    fn spread_pans() {
+        let mut engine: GranularEngine<8> = GranularEngine::new(from_slice(&DC), SR);
+        engine.set_spread(1.0);
+        engine.set_density(100.0);
+
+        let unequal = (0..200)
+            .map(|_| engine.tick())
+            .filter(|(left, right)| (left - right).abs() > 0.01)
+            .count();
+
+        assert!(unequal > 100);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+use crate::delay_line::DelayLine;
+use crate::memory::{memory_slice::MemorySlice, Mutable};
+use crate::smoothed_param::{SmoothedParam, Smoothing};
+
+const MIN_DELAY_MS: f32 = 5.0;
+const MAX_DELAY_MS: f32 = 30.0;
+const HIGHPASS_FREQ: f32 = 300.0;
+const HIGHPASS_Q: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+/**
+Stereo widener based on the Haas effect. A delayed copy of the mid signal is added to the
+left and subtracted from the right channel.
+
+Since the delayed copy cancels out in the mono sum, the widener is mono compatible. A
+highpass on the delayed path keeps the low end centered.
+
+The buffer should hold at least 30 ms of samples plus one, shorter buffers limit the
+maximum delay time.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice_mut, HaasWidener};
+
+// 30 ms at 48 kHz
+let mut buffer = [0.0_f32; 1441];
+let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), 48_000.0);
+
+widener.set_delay_time(12.0);
+widener.set_width(0.5);
+
+// during audio callback
+let (left, right) = widener.tick((0.5, 0.5));
+# assert_eq!(left + right, 1.0);
+```
+*/
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct HaasWidener {
+    delay_line: DelayLine,
+    highpass: Biquad<Butterworth>,
+    delay_ms: f32,
+    delay_samples: f32,
+    width: SmoothedParam,
+    sr: f32,
+}
+
+impl HaasWidener {
+    /// Starts with a delay time of 15 ms and full width
+    pub fn new(buffer: MemorySlice<Mutable>, sr: f32) -> HaasWidener {
+        let mut widener = HaasWidener {
+            delay_line: DelayLine::new(buffer),
+            highpass: Biquad::new(BiquadCoeffs::new()),
+            delay_ms: 15.0,
+            delay_samples: 0.0,
+            width: SmoothedParam::new(1.0, 0.0, sr, Smoothing::Linear),
+            sr,
+        };
+
+        widener.set_sr_unchecked(sr);
+        widener
+    }
+
+    #[inline(always)]
+    pub fn change_buffer(&mut self, new_slice: MemorySlice<Mutable>) {
+        self.delay_line.change_buffer(new_slice);
+        self.update_delay();
+    }
+
+    pub fn get_ptr_slice_mut(&mut self) -> *mut [f32] {
+        self.delay_line.get_ptr_slice_mut()
+    }
+
+    pub fn tick(&mut self, samples: (f32, f32)) -> (f32, f32) {
+        let delayed = self.delay_line.read_lerp_wrapped_at(-self.delay_samples);
+        let delayed = self.highpass.process(delayed) * self.width.value();
+
+        self.delay_line
+            .write_and_advance((samples.0 + samples.1) * 0.5);
+
+        (samples.0 + delayed, samples.1 - delayed)
+    }
+
+    /// Clamps between 5 and 30 ms, and to the length of the buffer
+    #[inline(always)]
+    pub fn set_delay_time(&mut self, ms: f32) {
+        self.delay_ms = ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS);
+        self.update_delay();
+    }
+
+    /// Clamps between 0 and 1
+    #[inline(always)]
+    pub fn set_width(&mut self, width: f32) {
+        self.width.set_immediate(width.clamp(0.0, 1.0));
+    }
+
+    /// Glides linearly to `width` within `time_ms`, applied by `update_smoothed`
+    #[inline(always)]
+    pub fn set_width_smoothed(&mut self, width: f32, time_ms: f32) {
+        self.width.set_time(time_ms);
+        self.width.set_target(width.clamp(0.0, 1.0));
+    }
+
+    /// Advances the width glide by `samples`, call once per block
+    #[inline(always)]
+    pub fn update_smoothed(&mut self, samples: usize) {
+        self.width.skip(samples);
+    }
+
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.width.set_sr_unchecked(sr);
+        self.highpass.coeffs.highpass(HIGHPASS_FREQ, HIGHPASS_Q, sr);
+        self.update_delay();
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.highpass.flush_denormals();
+    }
+
+    #[inline(always)]
+    fn update_delay(&mut self) {
+        let max = self.delay_line.len() as f32;
+        self.delay_samples = (self.delay_ms * 0.001 * self.sr).min(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::memory_slice::from_slice_mut;
+
+    const SR: f32 = 10_000.0;
+
+    #[test]
+    fn mono_compatible() {
+        let mut buffer = [0.0_f32; 301];
+        let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), SR);
+
+        for i in 0..1000 {
+            let input = ((i * 7) % 13) as f32 / 13.0 - 0.5;
+            let (left, right) = widener.tick((input, input * 0.5));
+            assert!((left + right - input * 1.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn delays_impulse() {
+        let mut buffer = [0.0_f32; 301];
+        let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), SR);
+        widener.set_delay_time(10.0);
+
+        let (left, right) = widener.tick((1.0, 1.0));
+        assert_eq!((left, right), (1.0, 1.0));
+
+        let side: [f32; 101] = core::array::from_fn(|_| {
+            let (left, right) = widener.tick((0.0, 0.0));
+            left - right
+        });
+
+        assert!(side[..98].iter().all(|x| *x == 0.0));
+        assert!(side[99] > 0.5);
+    }
+
+    #[test]
+    fn zero_width_is_transparent() {
+        let mut buffer = [0.0_f32; 301];
+        let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), SR);
+        widener.set_width(0.0);
+
+        for i in 0..1000 {
+            let input = (i % 17) as f32 / 17.0;
+            assert_eq!(widener.tick((input, -input)), (input, -input));
+        }
+    }
+
+    #[test]
+    fn width_glide() {
+        let mut buffer = [0.0_f32; 301];
+        let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), SR);
+        widener.set_width_smoothed(0.0, 10.0);
+
+        widener.update_smoothed(50);
+        assert!((widener.width.value() - 0.5).abs() < 1e-5);
+
+        widener.update_smoothed(50);
+        assert_eq!(widener.width.value(), 0.0);
+
+        for i in 0..100 {
+            let input = (i % 17) as f32 / 17.0;
+            assert_eq!(widener.tick((input, -input)), (input, -input));
+        }
+    }
+
+    #[test]
+    fn delay_clamps() {
+        let mut buffer = [0.0_f32; 64];
+        let mut widener = HaasWidener::new(from_slice_mut(&mut buffer[..]), SR);
+
+        widener.set_delay_time(1.0);
+        assert_eq!(widener.delay_ms, 5.0);
+
+        widener.set_delay_time(100.0);
+        assert_eq!(widener.delay_ms, 30.0);
+        assert_eq!(widener.delay_samples, 64.0);
+    }
+}
@@ -32,6 +32,7 @@ Little suite of filters in a `Biquad` topology.
 | `Lowpass`    |     - [x]     |    - [ ]    |  - [ ]   |
 | `Highpass`   |     - [x]     |    - [ ]    |  - [ ]   |
 | `Allpass`    |     - [x]     |    - [ ]    |  - [ ]   |
+| `Bandpass`   |     - [x]     |    - [ ]    |  - [ ]   |
 | `Notch`      |     - [x]     |    - [ ]    |  - [ ]   |
 | `Bell`       |     - [x]     |    - [ ]    |  - [ ]   |
 | `Lowshelf`   |     - [x]     |    - [ ]    |  - [ ]   |
@@ -50,6 +51,8 @@ biquad.coeffs.lowpass(1000.0, 1.0, 48_000.0); // Cutoff = 1kHz, Q = 1.0, fs = 48
 biquad.process(1.0); // process a sample
 ```
 
+With the `double` feature, `Biquad<Butterworth, f64>` designs and runs the filter in `f64`, e.g. for very low cutoffs or high Q values on hosts with a double precision FPU.
+
 ## Delay Line
 Uses the `MemorySlice` as an underlying building block for buffer handling. Can optionally interpolate in between samples either with `lerp` or `lagrange`.
 
@@ -57,17 +60,67 @@ Uses the `MemorySlice` as an underlying building block for buffer handling. Can
 * `Comb`
 * `Allpass`
 
+## Processing
+The `Process` trait gives filters, delays, waveshapers and plain `fn(f32) -> f32` clippers a common `process` and `process_block` interface. `Chain`, `Parallel` and `Bypass` compose them into a static signal path without dynamic dispatch.
+
+## Block Operations
+Copy, scale and dot products over whole blocks, plus a `Fir` filter and a `BiquadCascade`. With the `arm-dsp` feature they run on CMSIS-DSP on Cortex-M4/M7, otherwise in pure Rust with 4-wide kernels (`core::simd` with the nightly only `simd` feature). `MemorySlice` offers `fill`, `scale` and `copy_from` on top of them.
+
+## Resampling
+`Resampler` converts between any two integer sample rates, e.g. 44.1 kHz and 48 kHz, with a windowed sinc whose polyphase table lives in a caller-provided buffer. The ratio is tracked exactly, so it never drifts.
+
+## Oversampling
+Halfband 2x up- and downsamplers, cascadable for 4x or 8x, and an `Oversampled` wrapper that runs any processor at the higher rate, e.g. a clipper.
+
 ## Floats
 Some common float related stuff:
-* Bitreduction/manipulation
-* Raw IEEE 754 conversion
+* Bitreduction/manipulation, optionally with TPDF dither and noise shaping
+* Raw IEEE 754 conversion, ULP distance and next representable values
+* i16 and 24 bit sample conversion, optionally dithered
 * Various Interpolation Algorithms
+* Smoothstep and easing curves
+* Wrap, fold, mirror and phase wrapping helpers
+* Linear, equal power and S-curve crossfades
+* Mean, variance and (windowed) RMS over slices
+* Compile time Hann, Hamming and Blackman window tables
 * Additional embedded targeted math
-* Decibel to voltage (and back) conversion
+* Float math backend selected by feature: `micromath` (default, fast), `libm` (accurate) or `std`
+* `f64` variants of the interpolation kernels and `simpsons_rule` with the `double` feature, built on the float-generic `Float` trait
+* Decibel to voltage (and back) conversion, also as interpolated table lookup or fast approximation
+
+## Fixed Point
+`Q15` and `Q31` sample types with saturating arithmetic for FPU-less targets, plus integer sine, cosine, tangent, exp2, log2 and square root, Q15 pan and crossfade laws and a `DelayLine16` over `i16` buffers.
+
+## Clipping
+Hard (optionally with a knee), cubic, polynomial and sigmoid clipping with adjustable hardness, all normalized to ±1 at an input of ±1.
+
+## Waveshaper
+`ChebyshevShaper` mixes Chebyshev polynomials to dial in exact harmonic recipes. `AsymmetricSaturator` adds even harmonics with bias and asymmetry controls and keeps the output free of DC. `TableShaper` runs the signal through an arbitrary transfer curve stored in a `MemorySlice`. `Saturator` wraps the clipping curves with drive, output trim and auto gain. `Bitcrusher` reduces bit depth and sample rate. `Exciter` saturates the highpassed top end and blends the new harmonics back into the dry signal.
+
+## Dynamics
+`GainComputer16` is an integer only compressor gain computer working on `Q15` levels. `EnvelopeFollower` tracks the peak level of a signal with separate attack and release times.
+
+## Parameter Mapping
+`ParamMap` converts normalized controls (pots, MIDI CC) to a target range with a linear, exponential or skewed curve and back.
+
+## Smoothing
+`SmoothedParam` glides towards new values with a one pole or linear ramp to avoid zipper noise. `Vca` and `Crossfader` build on it for gain changes and fades. `SmoothedBiquad` (cutoff), `GainComputer16` (threshold and makeup), `HaasWidener` (width) and the oscillators (frequency) offer `set_*_smoothed(target, time_ms)` setters whose glide advances once per block with `update_smoothed`.
+
+## Presets
+`CompressorParams`, `AdsrParams` and `BiquadParams` are plain-data snapshots of processor parameters with `capture` and `apply`. With the `serde` feature they can be serialized, e.g. with `postcard` into EEPROM or flash.
 
 ## Envelope Generator
 Currently only implements an `ADSR` with varying steepness.
 
+## Sample Player
+`SamplePlayer` plays a `MemorySlice` at any rate with Hermite interpolation, start, end and loop points, a crossfade across the loop seam and one-shot, gated or sustain loop modes driven by the same gate as the envelope.
+
+## Granular
+`GranularEngine` layers Hann windowed grains from a loaded sample, out of a fixed pool, with position, size, pitch, density, jitter and stereo spread controls.
+
+## Vocoder
+`Vocoder` is a channel vocoder with a const number of bands. Bandpass biquads split modulator and carrier, and an envelope follower per band imposes the modulator's spectrum onto the carrier.
+
 ## Oscillator
 A very bad audio oscillator (with no anti-aliasing whatsoever), but maybe not a bad LFO. Comes with the common waveforms:
 * `Sine`
@@ -75,13 +128,44 @@ A very bad audio oscillator (with no anti-aliasing whatsoever), but maybe not a
 * `Triangle`
 * `Sawtooth`
 
-It is based on a software phase accumulator which is implemented as a trait bound. In theory, one could implement a hardware accumulator (i.e. timer).
+It is based on a software phase accumulator which is implemented as a trait bound. In theory, one could implement a hardware accumulator (i.e. timer). `FixedPhaseAccumulator` takes an integer increment or an exact frequency ratio instead of a float frequency.
+
+`StereoOscillator` pairs two slightly detuned oscillators with a width control for an instantly wide voice.
+
+`Wavetable8Oscillator` reads banks of 8-bit (`i8` or `u8`) tables with interpolation to `f32` or `Q15`, so multi-table banks fit into the few kB of flash of the smallest parts.
+
+Oscillators can be restarted with `trigger(StartPhase)` from zero, their current phase or a random phase drawn from the `WhiteNoise` source.
+
+## MIDI
+`MidiParser` turns a MIDI byte stream into note, CC and pitch bend events, with running status and real-time bytes handled. Events map to envelope gates and oscillator frequencies, `pitch_bend_to_ratio` converts bends for the frequency setters.
+
+## Tempo
+BPM to samples per beat conversion, note values with dotted and triplet feels, swing offsets and a `ClockDivider`. `TempoSync` turns note values into delay times, LFO frequencies and envelope times at the current tempo.
+
+## Tuning
+MIDI note to frequency conversion (and back) with a configurable A4 reference, plus semitone and cent to ratio conversions.
+
+## Metering
+`RmsMeter` integrates the signal level over a configurable window and reports it in dBFS. `VuMeter` emulates the needle of a classic VU meter. `ClipDetector` counts overs and tracks the remaining headroom. `AWeighting` filters the signal for SPL style measurements.
+
+## Analysis
+`Goertzel` detects single tones (DTMF, pilot tones, feedback) block by block, way cheaper than a full FFT.
+
+`Fft<N>` is an in-place radix-2 FFT without allocation, with its twiddle table computed at compile time.
+
+`PitchDetector` finds the fundamental and a confidence of monophonic signals with the YIN normalized difference function, e.g. for tuners.
 
 ## Stereo
-Panning, balacing and crossfading
+Panning (also onto `N` speakers), balacing, crossfading and energy preserving rotation
+
+`HaasWidener` widens a signal with a short, highpassed delay that cancels out in the mono sum.
+
+## C Interface
+With the `ffi` feature the compressor, envelope, biquad and delay effects get `#[repr(C)]` layouts and `extern "C"` functions (`eat_*`), so they can be driven from C/C++ HAL projects and vendor SDKs. The caller provides the memory and initializes it in place.
 */
 
 #![no_std]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub(crate) mod all_pass;
 pub(crate) mod biquad;
@@ -89,24 +173,64 @@ pub(crate) mod comb;
 pub(crate) mod decibels;
 pub(crate) mod delay_line;
 pub(crate) mod envelope;
+pub(crate) mod error;
+pub(crate) mod fft;
+pub(crate) mod goertzel;
+pub(crate) mod granular;
+pub(crate) mod haas_widener;
 pub(crate) mod memory;
+pub(crate) mod param_map;
+pub(crate) mod pitch_detector;
+pub(crate) mod process;
+pub(crate) mod resampler;
+pub(crate) mod sample_player;
+pub(crate) mod smoothed_param;
+pub(crate) mod vca;
+pub(crate) mod vocoder;
 
+pub mod block;
+pub mod clipping;
+pub mod dynamics;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod fixed_point;
 pub mod float;
+pub mod metering;
+pub mod midi;
 pub mod oscillator;
+pub mod oversampling;
+pub mod params;
 pub mod stereo;
+pub mod tempo;
+pub mod tuning;
+pub mod waveshaper;
 
 pub use all_pass::AllPass;
 pub use biquad::Biquad;
 pub use comb::Comb;
 pub use delay_line::DelayLine;
-pub use envelope::AudioRateADSR;
+pub use envelope::{AudioRateADSR, EnvelopeState};
+pub use fft::Fft;
+pub use goertzel::Goertzel;
+pub use granular::GranularEngine;
+pub use haas_widener::HaasWidener;
 pub use oscillator::{
-    FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator, WavetableOscillator,
+    FixedPhaseAccumulator, FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator,
+    StartPhase, StereoOscillator, Wavetable8Oscillator, WavetableOscillator, WhiteNoise,
 };
+pub use param_map::{ParamCurve, ParamMap};
+pub use pitch_detector::{Pitch, PitchDetector};
+pub use process::{Bypass, Chain, Parallel, Process};
+pub use resampler::Resampler;
+pub use sample_player::{PlayMode, SamplePlayer};
+pub use smoothed_param::{SmoothedParam, Smoothing};
+pub use vca::{Crossfader, Vca};
+pub use vocoder::Vocoder;
 
 pub mod filter {
-    pub use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+    pub use crate::biquad::{
+        butterworth::Butterworth, smoothed::SmoothedBiquad, Biquad, BiquadCoeffs,
+    };
 
     pub mod butterworth {
         pub use crate::biquad::butterworth::ButterworthType;
@@ -120,14 +244,28 @@ pub mod memory_access {
 }
 
 pub mod errors {
+    pub use crate::error::Error;
     pub use crate::float::BitReductionError;
     pub use crate::float::InterpolationError;
     pub use crate::memory::MemSliceError;
     pub use crate::oscillator::phase_accumulator::FrequencyError;
+    pub use crate::param_map::ParamMapError;
+    pub use crate::pitch_detector::PitchDetectorError;
+    pub use crate::resampler::ResamplerError;
     pub use crate::stereo::PanningError;
 }
 
+pub mod tables {
+    pub use crate::oscillator::lookup_tables::{
+        blackman_table, hamming_table, hann_table, sine_table,
+    };
+}
+
 pub mod convert {
-    pub use crate::decibels::Decibels;
+    pub use crate::decibels::{db_to_gain_table, Decibels};
+    pub use crate::float::sample_format::{
+        f32_to_i16, f32_to_i16_dithered, f32_to_i24, f32_to_i24_dithered, i16_to_f32, i24_to_f32,
+        sign_extend_i24,
+    };
     pub use crate::float::{FromF32Components, FromRawBytes, ToF32Components, ToRawBytes};
 }
@@ -1,12 +1,16 @@
 use core::ops::Neg;
 
 use super::{Mutable, NonMutable};
+use crate::block;
 use crate::memory::MemSliceError::{self, *};
 
-use crate::float::{lagrange, lagrange_only_4_elements, lerp_unchecked};
+use crate::float::{
+    b_spline_slice, catmull_rom, hermite_unchecked, lagrange, lagrange_only_4_elements,
+    lerp_unchecked, mean, rms, variance, windowed_rms, EdgeMode,
+};
 
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
 
 ///////////////////////////////////////////////////////////////////////////////
 /// Struct
@@ -41,7 +45,9 @@ assert_eq!(ptr_buffer.as_slice(), mut_slice.as_slice());
 ```
 
 */
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct MemorySlice<Mutability> {
     ptr: Mutability,
     length: usize,
@@ -224,6 +230,42 @@ where
         lerp_unchecked(a, b, index - (int_index as f32))
     }
 
+    ///////////////////////////////////////////////////////////////////////////////
+    /// Hermite Interpolation Data Access
+    ///////////////////////////////////////////////////////////////////////////////
+
+    #[inline(always)]
+    pub fn hermite_wrapped(&self, index: f32) -> f32 {
+        let int_index = index.floor() as isize;
+        let points = self.get_slice_of_four_wrapped(int_index - 1);
+
+        hermite_unchecked(
+            points[0],
+            points[1],
+            points[2],
+            points[3],
+            index - int_index as f32,
+        )
+    }
+
+    /// Catmull-Rom spline interpolation, see `float::catmull_rom`
+    pub fn catmull_rom(&self, index: f32, mode: EdgeMode) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        catmull_rom(unsafe { &*self.as_slice() }, index, mode)
+    }
+
+    /// Cubic B-spline interpolation, see `float::b_spline_slice`
+    pub fn b_spline(&self, index: f32, mode: EdgeMode) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        b_spline_slice(unsafe { &*self.as_slice() }, index, mode)
+    }
+
     ///////////////////////////////////////////////////////////////////////////////
     /// Lagrange Interpolation Data Access
     ///////////////////////////////////////////////////////////////////////////////
@@ -258,6 +300,46 @@ where
         }
     }
 
+    ///////////////////////////////////////////////////////////////////////////////
+    /// Statistics
+    ///////////////////////////////////////////////////////////////////////////////
+
+    /// Arithmetic mean, see `float::mean`
+    pub fn mean(&self) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        mean(unsafe { &*self.as_slice() })
+    }
+
+    /// Population variance, see `float::variance`
+    pub fn variance(&self) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        variance(unsafe { &*self.as_slice() })
+    }
+
+    /// Root mean square, see `float::rms`
+    pub fn rms(&self) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        rms(unsafe { &*self.as_slice() })
+    }
+
+    /// Weighted root mean square, see `float::windowed_rms`
+    pub fn windowed_rms(&self, window: &[f32]) -> f32 {
+        if self.length == 0 {
+            return 0.0;
+        }
+
+        windowed_rms(unsafe { &*self.as_slice() }, window)
+    }
+
     ///////////////////////////////////////////////////////////////////////////////
     /// Data Overwriting
     ///////////////////////////////////////////////////////////////////////////////
@@ -311,6 +393,27 @@ where
         Ok(())
     }
 
+    ///////////////////////////////////////////////////////////////////////////////
+    /// Bulk Operations
+    ///////////////////////////////////////////////////////////////////////////////
+
+    #[inline]
+    pub fn fill(&mut self, value: f32) {
+        unsafe { (*self.as_slice_mut()).fill(value) }
+    }
+
+    /// Multiplies every sample with `gain`, see `block::scale`
+    #[inline]
+    pub fn scale(&mut self, gain: f32) {
+        block::scale(unsafe { &mut *self.as_slice_mut() }, gain);
+    }
+
+    /// Copies as much of `src` as fits from the start on, see `block::copy`
+    #[inline]
+    pub fn copy_from(&mut self, src: &[f32]) {
+        block::copy(src, unsafe { &mut *self.as_slice_mut() });
+    }
+
     ///////////////////////////////////////////////////////////////////////////////
     /// Data Overwriting
     ///////////////////////////////////////////////////////////////////////////////
@@ -479,6 +582,44 @@ mod tests {
         assert_eq!(ptr_buffer.lerp_wrapped(SIZE as f32 + 0.5), 0.5);
     }
 
+    #[test]
+    fn hermite_wrapped() {
+        const SIZE: usize = 24;
+        let mut buffer = [0.0_f32; SIZE];
+        for (i, val) in buffer.iter_mut().enumerate() {
+            *val = i as f32;
+        }
+
+        let ptr_buffer = from_slice(&mut buffer[..]);
+
+        assert_eq!(ptr_buffer.hermite_wrapped(0.0), 0.0);
+        assert_eq!(ptr_buffer.hermite_wrapped(5.5), 5.5);
+        assert_eq!(ptr_buffer.hermite_wrapped(SIZE as f32 + 1.0), 1.0);
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let mut buffer = [0.0_f32, 1.0, 2.0, 3.0];
+        let ptr_buffer = from_slice(&mut buffer[..]);
+
+        assert_eq!(ptr_buffer.catmull_rom(1.5, EdgeMode::Clamp), 1.5);
+        assert_eq!(ptr_buffer.catmull_rom(4.0, EdgeMode::Clamp), 3.0);
+        assert_eq!(ptr_buffer.catmull_rom(4.0, EdgeMode::Wrap), 0.0);
+        assert_eq!(null().catmull_rom(1.0, EdgeMode::Wrap), 0.0);
+    }
+
+    #[test]
+    fn statistics() {
+        let buffer = [1.0_f32, -1.0, 1.0, -1.0];
+        let ptr_buffer = from_slice(&buffer[..]);
+
+        assert_eq!(ptr_buffer.mean(), 0.0);
+        assert_eq!(ptr_buffer.variance(), 1.0);
+        assert_eq!(ptr_buffer.rms(), 1.0);
+        assert_eq!(ptr_buffer.windowed_rms(&[0.0, 1.0, 1.0, 0.0]), 1.0);
+        assert_eq!(null().rms(), 0.0);
+    }
+
     #[test]
     fn lagrange_wrapped() {
         let mut buffer = [0.0_f32, -1.0, 1.0, 0.4];
@@ -3,6 +3,7 @@ pub mod memory_slice;
 use crate::memory::memory_slice::{MutLocation, NonMutLocation};
 
 /// Describes all possible errors that can occur when handling buffer manipulation
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 pub enum MemSliceError {
     IndexOutOfBound,
@@ -12,14 +13,18 @@ pub enum MemSliceError {
 /// Raw pointer that implements the `Send` trait since it's only acting on stack memory
 ///
 /// Should always point at the beginning of your audio buffer in use
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct NonMutable(*const f32);
 unsafe impl Send for NonMutable {}
 
 /// Raw mutable pointer that implements the `Send` trait since it's only acting on stack memory
 ///
 /// Should always point at the beginning of your audio buffer in use
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub struct Mutable(*mut f32);
 unsafe impl Send for Mutable {}
 
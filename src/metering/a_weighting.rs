@@ -0,0 +1,224 @@
+use crate::biquad::{Biquad, BiquadCoeffs};
+use crate::float::AdditionalF32Ext;
+
+use core::f32::consts::PI;
+
+/// Pole frequencies of the IEC 61672 A-curve in Hz
+const F1: f32 = 20.598_997;
+const F2: f32 = 107.652_65;
+const F3: f32 = 737.862_2;
+const F4: f32 = 12_194.217;
+
+/// Highest pole frequency relative to the sample rate, the pre-warped pole of `F4` would
+/// flip its sign past Nyquist below about 24.4 kHz and make the filter unstable
+const MAX_POLE: f32 = 0.45;
+
+/// Normalizes the curve to 0 dB at 1 kHz, 2 dB
+const GAIN_1K: f32 = 1.258_925_4;
+
+/**
+A-weighting filter after IEC 61672 for SPL style measurements, e.g. in front of an
+`RmsMeter`.
+
+The analog curve is mapped onto three biquads with the pre-warped bilinear transform,
+so all poles sit at their exact frequencies for any sample rate. The response stays
+within 0.15 dB of the analog curve up to 2 kHz and within the class 1 tolerances of the
+standard over the whole audio band from 44.1 kHz on. Towards Nyquist it falls off
+faster than the analog curve, e.g. -3 dB off at 16 kHz for 48 kHz. Below about 27 kHz
+the highest pole gets clamped to `0.45 * sr`, so the top end rolls off early there.
+
+### Example
+
+```rust
+use embedded_audio_tools::metering::{AWeighting, RmsMeter};
+
+let mut weighting = AWeighting::new(48_000.0);
+let mut meter = RmsMeter::new(125.0, 48_000.0);
+
+// during audio callback
+meter.process(weighting.process(0.5));
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct AWeighting {
+    sections: [Biquad<()>; 3],
+}
+
+impl AWeighting {
+    pub fn new(sr: f32) -> AWeighting {
+        let mut weighting = AWeighting {
+            sections: [
+                Biquad::new(BiquadCoeffs::from_raw(1.0, 0.0, 0.0, 0.0, 0.0)),
+                Biquad::new(BiquadCoeffs::from_raw(1.0, 0.0, 0.0, 0.0, 0.0)),
+                Biquad::new(BiquadCoeffs::from_raw(1.0, 0.0, 0.0, 0.0, 0.0)),
+            ],
+        };
+
+        weighting.set_sr_unchecked(sr);
+        weighting
+    }
+
+    /// Recalculates the coefficients, keeps the filter state
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        let w1 = prewarp(F1, sr);
+        let w2 = prewarp(F2, sr);
+        let w3 = prewarp(F3, sr);
+        let w4 = prewarp(F4.min(MAX_POLE * sr), sr);
+
+        // s² / (s + w1)²
+        self.sections[0].coeffs = bilinear_highpass(2.0 * w1, w1 * w1);
+        // s² / ((s + w2) * (s + w3))
+        self.sections[1].coeffs = bilinear_highpass(w2 + w3, w2 * w3);
+        // w4² / (s + w4)²
+        self.sections[2].coeffs = bilinear_lowpass(2.0 * w4, w4 * w4, GAIN_1K * w4 * w4);
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let mut out = sample;
+
+        for section in self.sections.iter_mut() {
+            out = section.process(out);
+        }
+
+        out
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.flush_denormals();
+        }
+    }
+}
+
+/// Analog pole frequency in rad/s, normalized to the bilinear constant 2 * sr
+#[inline(always)]
+fn prewarp(freq: f32, sr: f32) -> f32 {
+    (PI * freq / sr).full_range_tan()
+}
+
+/// s² / (s² + p1 * s + p0), with p1 and p0 normalized to 2 * sr
+#[inline(always)]
+fn bilinear_highpass(p1: f32, p0: f32) -> BiquadCoeffs<()> {
+    let norm = 1.0 / (1.0 + p1 + p0);
+
+    BiquadCoeffs::from_raw(
+        norm,
+        -2.0 * norm,
+        norm,
+        2.0 * (p0 - 1.0) * norm,
+        (1.0 - p1 + p0) * norm,
+    )
+}
+
+/// gain / (s² + p1 * s + p0), with all terms normalized to 2 * sr
+#[inline(always)]
+fn bilinear_lowpass(p1: f32, p0: f32, gain: f32) -> BiquadCoeffs<()> {
+    let norm = 1.0 / (1.0 + p1 + p0);
+
+    BiquadCoeffs::from_raw(
+        gain * norm,
+        2.0 * gain * norm,
+        gain * norm,
+        2.0 * (p0 - 1.0) * norm,
+        (1.0 - p1 + p0) * norm,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::TAU;
+
+    /// Analog A-curve in dB
+    fn a_curve(freq: f64) -> f64 {
+        let f2 = freq * freq;
+        let sq = |f: f32| (f as f64) * (f as f64);
+
+        let ra = sq(F4) * f2 * f2
+            / ((f2 + sq(F1)) * ((f2 + sq(F2)) * (f2 + sq(F3))).sqrt() * (f2 + sq(F4)));
+
+        20.0 * ra.log10() + 2.0
+    }
+
+    /// Measured gain of a sine at `freq` in dB
+    fn response(freq: f32, sr: f32) -> f64 {
+        let mut weighting = AWeighting::new(sr);
+        let settle = sr as usize;
+        let length = sr as usize;
+
+        let (mut input, mut output) = (0.0, 0.0);
+        for i in 0..settle + length {
+            let x = (TAU * freq as f64 * i as f64 / sr as f64).sin() as f32;
+            let y = weighting.process(x);
+
+            if i >= settle {
+                input += (x * x) as f64;
+                output += (y * y) as f64;
+            }
+        }
+
+        10.0 * (output / input).log10()
+    }
+
+    #[test]
+    fn unity_at_1k() {
+        assert!(response(1000.0, 48_000.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn follows_curve() {
+        for sr in [44_100.0, 48_000.0, 96_000.0] {
+            for freq in [31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0] {
+                let error = response(freq, sr) - a_curve(freq as f64);
+                assert!(
+                    error.abs() < 0.15,
+                    "sr: {}, freq: {}, error: {}",
+                    sr,
+                    freq,
+                    error
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn class_1_tolerance() {
+        // (frequency, upper limit, lower limit) after IEC 61672-1
+        let tolerances = [
+            (4000.0, 1.0, -1.0),
+            (5000.0, 1.5, -1.5),
+            (6300.0, 1.5, -2.0),
+            (8000.0, 1.5, -2.5),
+            (10_000.0, 2.0, -3.0),
+            (12_500.0, 2.0, -5.0),
+            (16_000.0, 2.5, -16.0),
+        ];
+
+        for sr in [44_100.0, 48_000.0] {
+            for (freq, upper, lower) in tolerances {
+                let error = response(freq, sr) - a_curve(freq as f64);
+                assert!(error < upper && error > lower, "sr: {}, freq: {}", sr, freq);
+            }
+        }
+    }
+
+    #[test]
+    fn stable_at_low_sample_rates() {
+        for sr in [16_000.0, 22_050.0] {
+            assert!(response(1000.0, sr).abs() < 0.5, "sr: {}", sr);
+
+            // The impulse response has to decay
+            let mut weighting = AWeighting::new(sr);
+            let tail = (0..sr as usize)
+                .map(|i| weighting.process(if i == 0 { 1.0 } else { 0.0 }))
+                .skip(sr as usize / 2)
+                .fold(0.0, |max: f32, y| max.max(y.abs()));
+
+            assert!(tail < 1e-3, "sr: {}, tail: {}", sr, tail);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::decibels::Decibels;
+
+use super::rms::METER_FLOOR_DB;
+
+/**
+Over detection for diagnostics pages, counting runs of consecutive samples at or above
+full scale and tracking the remaining headroom.
+
+A run of at least `consecutive` samples counts as one over, no matter how long it lasts.
+The default of 3 samples is common for digital peak meters, since a single full scale
+sample doesn't necessarily mean the signal got clipped.
+
+### Example
+
+```rust
+use embedded_audio_tools::metering::ClipDetector;
+
+let mut detector = ClipDetector::new(3);
+
+// during audio callback
+let overs = detector.process_block(&[0.5, 1.0, 1.0, 1.0, -0.2]);
+assert_eq!(overs, 1);
+
+// in the UI loop
+assert_eq!(detector.headroom_db(), 0.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct ClipDetector {
+    threshold: f32,
+    consecutive: u32,
+    run: u32,
+    overs: u32,
+    peak: f32,
+}
+
+impl ClipDetector {
+    /// A `consecutive` of 0 is treated as 1
+    pub fn new(consecutive: u32) -> ClipDetector {
+        ClipDetector {
+            threshold: 1.0,
+            consecutive: consecutive.max(1),
+            run: 0,
+            overs: 0,
+            peak: 0.0,
+        }
+    }
+
+    /// A `consecutive` of 0 is treated as 1
+    #[inline(always)]
+    pub fn set_consecutive(&mut self, consecutive: u32) {
+        self.consecutive = consecutive.max(1);
+    }
+
+    /// Level counting as full scale, e.g. -0.1 dBFS to catch overs after a later
+    /// conversion stage. Defaults to 0 dBFS
+    #[inline(always)]
+    pub fn set_threshold_db(&mut self, db: f32) {
+        self.threshold = db.fast_to_volt_ratio();
+    }
+
+    /// Returns `true` for the sample completing an over
+    #[inline(always)]
+    pub fn process(&mut self, sample: f32) -> bool {
+        let abs = sample.abs();
+        self.peak = self.peak.max(abs);
+
+        if abs < self.threshold {
+            self.run = 0;
+            return false;
+        }
+
+        self.run = self.run.saturating_add(1);
+        if self.run == self.consecutive {
+            self.overs = self.overs.saturating_add(1);
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns the number of overs completed within `samples`
+    pub fn process_block(&mut self, samples: &[f32]) -> u32 {
+        let mut overs = 0;
+
+        for sample in samples {
+            if self.process(*sample) {
+                overs += 1;
+            }
+        }
+
+        overs
+    }
+
+    /// Overs since the last reset
+    #[inline(always)]
+    pub fn overs(&self) -> u32 {
+        self.overs
+    }
+
+    /// Highest absolute sample since the last reset
+    #[inline(always)]
+    pub fn peak(&self) -> f32 {
+        self.peak
+    }
+
+    /// Distance between the peak and the threshold in dB, limited to the meter floor.
+    /// Negative once the threshold got exceeded
+    #[inline(always)]
+    pub fn headroom_db(&self) -> f32 {
+        let peak_db = self.peak.fast_to_decibels().max(METER_FLOOR_DB);
+        self.threshold.fast_to_decibels() - peak_db
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.run = 0;
+        self.overs = 0;
+        self.peak = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_runs_once() {
+        let mut detector = ClipDetector::new(3);
+
+        assert_eq!(detector.process_block(&[1.0, 1.0, 0.9, 1.0, 1.0]), 0);
+        assert_eq!(detector.process_block(&[-1.0, 1.0, 1.0, 1.0, 1.0]), 1);
+        assert_eq!(detector.process_block(&[1.0, 0.0, 1.0, -1.0, 1.2]), 1);
+        assert_eq!(detector.overs(), 2);
+    }
+
+    #[test]
+    fn single_sample_overs() {
+        let mut detector = ClipDetector::new(0);
+
+        assert!(detector.process(1.0));
+        assert!(!detector.process(1.0));
+        assert!(!detector.process(0.5));
+        assert!(detector.process(-1.5));
+    }
+
+    #[test]
+    fn headroom() {
+        let mut detector = ClipDetector::new(3);
+        assert_eq!(detector.headroom_db(), -METER_FLOOR_DB);
+
+        detector.process(0.5);
+        assert!((detector.headroom_db() - 6.0206).abs() < 0.001);
+
+        detector.process(-2.0);
+        assert!((detector.headroom_db() + 6.0206).abs() < 0.001);
+
+        detector.reset();
+        assert_eq!(detector.peak(), 0.0);
+        assert_eq!(detector.overs(), 0);
+    }
+
+    #[test]
+    fn threshold() {
+        let mut detector = ClipDetector::new(1);
+        detector.set_threshold_db(-6.0);
+
+        assert!(!detector.process(0.4));
+        assert!(detector.process(0.6));
+        assert!(
+            (detector.headroom_db() + 1.56).abs() < 0.01,
+            "{}",
+            detector.headroom_db()
+        );
+    }
+}
@@ -0,0 +1,9 @@
+pub mod a_weighting;
+pub mod clip;
+pub mod rms;
+pub mod vu;
+
+pub use a_weighting::AWeighting;
+pub use clip::ClipDetector;
+pub use rms::{RmsMeter, METER_FLOOR_DB};
+pub use vu::VuMeter;
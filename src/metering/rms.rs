@@ -0,0 +1,153 @@
+use crate::decibels::Decibels;
+use crate::float::flush_denormal;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Lowest reading in dBFS, reported for silence
+pub const METER_FLOOR_DB: f32 = -120.0;
+
+/**
+RMS level meter integrating the mean square with a one pole filter, e.g. with a
+300 ms window for VU-ish level displays or as the detector of an AGC.
+
+Readings are in dBFS relative to a full scale DC level, so a full scale sine reads
+roughly -3 dBFS.
+
+### Example
+
+```rust
+use embedded_audio_tools::metering::RmsMeter;
+
+let mut meter = RmsMeter::new(300.0, 48_000.0);
+
+// during audio callback
+meter.process_block(&[0.5; 64]);
+
+// in the UI loop
+let level = meter.dbfs();
+# assert!(level < -6.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct RmsMeter {
+    mean_square: f32,
+    coeff: f32,
+    window_ms: f32,
+    sr: f32,
+}
+
+impl RmsMeter {
+    pub fn new(window_ms: f32, sr: f32) -> RmsMeter {
+        let mut meter = RmsMeter {
+            mean_square: 0.0,
+            coeff: 0.0,
+            window_ms,
+            sr,
+        };
+
+        meter.set_window(window_ms);
+        meter
+    }
+
+    /// Time constant of the integration, negative times are treated as 0
+    pub fn set_window(&mut self, window_ms: f32) {
+        self.window_ms = window_ms.max(0.0);
+        self.update_coeff();
+    }
+
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_coeff();
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, sample: f32) {
+        self.mean_square += (sample * sample - self.mean_square) * self.coeff;
+    }
+
+    /// Processes a whole block and flushes subnormal state afterwards
+    pub fn process_block(&mut self, samples: &[f32]) {
+        for sample in samples {
+            self.process(*sample);
+        }
+
+        self.mean_square = flush_denormal(self.mean_square);
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.mean_square = 0.0;
+    }
+
+    #[inline(always)]
+    pub fn mean_square(&self) -> f32 {
+        self.mean_square
+    }
+
+    #[inline(always)]
+    pub fn rms(&self) -> f32 {
+        self.mean_square.sqrt()
+    }
+
+    /// Level in dBFS, limited to `METER_FLOOR_DB`
+    #[inline(always)]
+    pub fn dbfs(&self) -> f32 {
+        // 10 * log10(x²) = 20 * log10(x), which saves the square root
+        (0.5 * self.mean_square.fast_to_decibels()).max(METER_FLOOR_DB)
+    }
+
+    #[inline(always)]
+    fn update_coeff(&mut self) {
+        let samples = self.window_ms * 0.001 * self.sr;
+
+        self.coeff = if samples > 0.0 {
+            1.0 - (-1.0 / samples).exp()
+        } else {
+            1.0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn sine_level() {
+        let mut meter = RmsMeter::new(50.0, SR);
+
+        for i in 0..48_000 {
+            let phase = core::f32::consts::TAU * 997.0 * i as f32 / SR;
+            meter.process(0.5 * phase.sin());
+        }
+
+        let exact = 20.0 * (0.5 / 2.0_f64.sqrt()).log10();
+        assert!((meter.dbfs() as f64 - exact).abs() < 0.05);
+        assert!((meter.rms() - 0.353_553_4).abs() < 0.002);
+    }
+
+    #[test]
+    fn window_time_constant() {
+        let mut meter = RmsMeter::new(10.0, 1000.0);
+
+        // 63% of the final mean square after one time constant
+        meter.process_block(&[1.0; 10]);
+        assert!((meter.mean_square() - 0.632).abs() < 0.01);
+    }
+
+    #[test]
+    fn floor() {
+        let mut meter = RmsMeter::new(0.0, SR);
+        assert_eq!(meter.dbfs(), METER_FLOOR_DB);
+
+        meter.process(1.0);
+        assert_eq!(meter.dbfs(), 0.0);
+
+        meter.reset();
+        assert_eq!(meter.rms(), 0.0);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::decibels::Decibels;
+use crate::float::flush_denormal;
+
+use super::rms::METER_FLOOR_DB;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Time constant of each stage, the critically damped pair reaches 99% after 300 ms
+const STAGE_TIME_MS: f32 = 45.17;
+
+/// Reads the RMS value of a sine from its average rectified value, π / (2 * √2)
+const SINE_CALIBRATION: f32 = 1.110_720_7;
+
+/**
+Meter with classic VU ballistics, i.e. the slow needle of analog consoles instead of
+the instant response of peak meters.
+
+The rectified signal runs through a critically damped second order lowpass, which
+reaches 99% of a new level after 300 ms, both rising and falling. It is calibrated
+so a sine reads its RMS level. 0 VU corresponds to a reference level of -18 dBFS
+by default.
+
+### Example
+
+```rust
+use embedded_audio_tools::metering::VuMeter;
+
+let mut meter = VuMeter::new(48_000.0);
+meter.set_reference_db(-20.0);
+
+// during audio callback
+meter.process_block(&[0.1; 64]);
+
+// in the UI loop
+let needle = meter.vu();
+# assert!(needle < 0.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct VuMeter {
+    coeff: f32,
+    stage1: f32,
+    stage2: f32,
+    reference_db: f32,
+}
+
+impl VuMeter {
+    pub fn new(sr: f32) -> VuMeter {
+        let mut meter = VuMeter {
+            coeff: 0.0,
+            stage1: 0.0,
+            stage2: 0.0,
+            reference_db: -18.0,
+        };
+
+        meter.set_sr_unchecked(sr);
+        meter
+    }
+
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.coeff = 1.0 - (-1000.0 / (STAGE_TIME_MS * sr)).exp();
+    }
+
+    /// Level in dBFS reading as 0 VU
+    #[inline(always)]
+    pub fn set_reference_db(&mut self, db: f32) {
+        self.reference_db = db;
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, sample: f32) {
+        let rectified = sample.abs() * SINE_CALIBRATION;
+
+        self.stage1 += (rectified - self.stage1) * self.coeff;
+        self.stage2 += (self.stage1 - self.stage2) * self.coeff;
+    }
+
+    /// Processes a whole block and flushes subnormal state afterwards
+    pub fn process_block(&mut self, samples: &[f32]) {
+        for sample in samples {
+            self.process(*sample);
+        }
+
+        self.stage1 = flush_denormal(self.stage1);
+        self.stage2 = flush_denormal(self.stage2);
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.stage1 = 0.0;
+        self.stage2 = 0.0;
+    }
+
+    /// Current needle level as linear value
+    #[inline(always)]
+    pub fn level(&self) -> f32 {
+        self.stage2
+    }
+
+    /// Current needle level in VU, i.e. dB relative to the reference level.
+    /// Limited to the meter floor
+    #[inline(always)]
+    pub fn vu(&self) -> f32 {
+        self.stage2.fast_to_decibels().max(METER_FLOOR_DB) - self.reference_db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn rise_time() {
+        let mut meter = VuMeter::new(SR);
+        let input = 1.0 / SINE_CALIBRATION;
+
+        for _ in 0..(0.3 * SR) as usize {
+            meter.process(input);
+        }
+        assert!((meter.level() - 0.99).abs() < 0.002);
+
+        for _ in 0..(0.3 * SR) as usize {
+            meter.process(0.0);
+        }
+        assert!((meter.level() - 0.0099).abs() < 0.002);
+    }
+
+    #[test]
+    fn no_overshoot() {
+        let mut meter = VuMeter::new(SR);
+
+        let mut last = 0.0;
+        for _ in 0..SR as usize {
+            meter.process(0.5);
+            assert!(meter.level() >= last && meter.level() <= 0.5 * SINE_CALIBRATION);
+            last = meter.level();
+        }
+    }
+
+    #[test]
+    fn sine_reads_reference() {
+        let mut meter = VuMeter::new(SR);
+
+        // -18 dBFS RMS
+        let amplitude = 2.0_f32.sqrt() * 0.125_892_54;
+        for i in 0..2 * SR as usize {
+            let phase = core::f32::consts::TAU * 1000.0 * i as f32 / SR;
+            meter.process(amplitude * phase.sin());
+        }
+
+        assert!(meter.vu().abs() < 0.05);
+
+        meter.reset();
+        assert_eq!(meter.vu(), METER_FLOOR_DB + 18.0);
+    }
+}
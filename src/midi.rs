@@ -0,0 +1,308 @@
+use crate::tuning::{midi_note_to_freq, semitones_to_ratio};
+
+/// Center of the 14 bit pitch bend range
+const PITCH_BEND_CENTER: i16 = 8192;
+
+/// Channel voice messages, channels count from 0
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MidiEvent {
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    /// Also emitted for note ons with a velocity of 0
+    NoteOff {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// Between -8192 and 8191, 0 is the center
+    PitchBend {
+        channel: u8,
+        value: i16,
+    },
+}
+
+impl MidiEvent {
+    #[inline(always)]
+    pub fn channel(&self) -> u8 {
+        match *self {
+            MidiEvent::NoteOn { channel, .. }
+            | MidiEvent::NoteOff { channel, .. }
+            | MidiEvent::ControlChange { channel, .. }
+            | MidiEvent::PitchBend { channel, .. } => channel,
+        }
+    }
+
+    /// `Some(true)` for note ons and `Some(false)` for note offs, e.g. for
+    /// `AudioRateADSR::trigger_on` and `trigger_off`
+    #[inline(always)]
+    pub fn gate(&self) -> Option<bool> {
+        match self {
+            MidiEvent::NoteOn { .. } => Some(true),
+            MidiEvent::NoteOff { .. } => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Frequency of note ons and offs with `a4` as reference
+    #[inline(always)]
+    pub fn freq(&self, a4: f32) -> Option<f32> {
+        match *self {
+            MidiEvent::NoteOn { note, .. } | MidiEvent::NoteOff { note, .. } => {
+                Some(midi_note_to_freq(note, a4))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Converts a pitch bend value to a frequency ratio, with `range` semitones at full bend
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::midi::pitch_bend_to_ratio;
+///
+/// assert_eq!(pitch_bend_to_ratio(0, 2.0), 1.0);
+/// assert_eq!(pitch_bend_to_ratio(-8192, 12.0), 0.5);
+/// ```
+#[inline(always)]
+pub fn pitch_bend_to_ratio(value: i16, range: f32) -> f32 {
+    semitones_to_ratio(value as f32 / PITCH_BEND_CENTER as f32 * range)
+}
+
+/// Maps a 7 bit value (velocity, CC) between 0 and 1
+#[inline(always)]
+pub fn to_normalized(value: u8) -> f32 {
+    (value & 0x7F) as f32 / 127.0
+}
+
+/**
+Byte by byte MIDI parser, e.g. for a UART receive interrupt. Supports running status and
+ignores real-time bytes in between, as well as SysEx and all messages it doesn't emit.
+
+### Example
+
+```rust
+use embedded_audio_tools::midi::{MidiEvent, MidiParser};
+use embedded_audio_tools::tuning::A4_FREQ;
+
+let mut parser = MidiParser::new();
+
+// Note on, then a second one with running status
+let bytes = [0x90, 69, 100, 72, 0];
+let mut events = bytes.iter().filter_map(|byte| parser.parse(*byte));
+
+let event = events.next().unwrap();
+assert_eq!(event.gate(), Some(true));
+assert_eq!(event.freq(A4_FREQ), Some(440.0));
+
+assert_eq!(
+    events.next(),
+    Some(MidiEvent::NoteOff { channel: 0, note: 72, velocity: 0 })
+);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MidiParser {
+    /// 0 while there is no running status
+    status: u8,
+    data: [u8; 2],
+    len: u8,
+}
+
+impl MidiParser {
+    pub const fn new() -> MidiParser {
+        MidiParser {
+            status: 0,
+            data: [0; 2],
+            len: 0,
+        }
+    }
+
+    /// Returns an event once its last byte arrived
+    pub fn parse(&mut self, byte: u8) -> Option<MidiEvent> {
+        match byte {
+            // Real-time messages may show up anywhere
+            0xF8..=0xFF => None,
+            // System common and SysEx cancel the running status
+            0xF0..=0xF7 => {
+                self.status = 0;
+                self.len = 0;
+                None
+            }
+            0x80..=0xEF => {
+                self.status = byte;
+                self.len = 0;
+                None
+            }
+            _ => {
+                if self.status == 0 {
+                    return None;
+                }
+
+                self.data[self.len as usize] = byte;
+                self.len += 1;
+
+                if self.len < data_len(self.status) {
+                    return None;
+                }
+
+                self.len = 0;
+                self.event()
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn event(&self) -> Option<MidiEvent> {
+        let channel = self.status & 0x0F;
+        let [first, second] = self.data;
+
+        match self.status & 0xF0 {
+            0x80 => Some(MidiEvent::NoteOff {
+                channel,
+                note: first,
+                velocity: second,
+            }),
+            0x90 if second == 0 => Some(MidiEvent::NoteOff {
+                channel,
+                note: first,
+                velocity: 0,
+            }),
+            0x90 => Some(MidiEvent::NoteOn {
+                channel,
+                note: first,
+                velocity: second,
+            }),
+            0xB0 => Some(MidiEvent::ControlChange {
+                channel,
+                controller: first,
+                value: second,
+            }),
+            0xE0 => Some(MidiEvent::PitchBend {
+                channel,
+                value: ((second as i16) << 7 | first as i16) - PITCH_BEND_CENTER,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Number of data bytes following a channel voice status
+#[inline(always)]
+fn data_len(status: u8) -> u8 {
+    match status & 0xF0 {
+        0xC0 | 0xD0 => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_all<const N: usize>(
+        parser: &mut MidiParser,
+        bytes: [u8; N],
+    ) -> [Option<MidiEvent>; N] {
+        bytes.map(|byte| parser.parse(byte))
+    }
+
+    #[test]
+    fn messages() {
+        let mut parser = MidiParser::new();
+
+        let events = parse_all(&mut parser, [0x83, 60, 64, 0xB5, 7, 100, 0xEF, 0x00, 0x40]);
+        assert_eq!(
+            events[2],
+            Some(MidiEvent::NoteOff {
+                channel: 3,
+                note: 60,
+                velocity: 64
+            })
+        );
+        assert_eq!(
+            events[5],
+            Some(MidiEvent::ControlChange {
+                channel: 5,
+                controller: 7,
+                value: 100
+            })
+        );
+        assert_eq!(
+            events[8],
+            Some(MidiEvent::PitchBend {
+                channel: 15,
+                value: 0
+            })
+        );
+    }
+
+    #[test]
+    fn pitch_bend_range() {
+        let mut parser = MidiParser::new();
+
+        let events = parse_all(&mut parser, [0xE0, 0x00, 0x00, 0x7F, 0x7F]);
+        assert_eq!(
+            events[2],
+            Some(MidiEvent::PitchBend {
+                channel: 0,
+                value: -8192
+            })
+        );
+        assert_eq!(
+            events[4],
+            Some(MidiEvent::PitchBend {
+                channel: 0,
+                value: 8191
+            })
+        );
+
+        assert_eq!(
+            pitch_bend_to_ratio(8191, 12.0),
+            semitones_to_ratio(8191.0 / 8192.0 * 12.0)
+        );
+    }
+
+    #[test]
+    fn real_time_and_sysex() {
+        let mut parser = MidiParser::new();
+
+        // Clock in the middle of a note on, then SysEx cancels the running status
+        let events = parse_all(&mut parser, [0x90, 60, 0xF8, 90, 0xF0, 0x01, 0xF7, 60, 90]);
+        assert_eq!(
+            events[3],
+            Some(MidiEvent::NoteOn {
+                channel: 0,
+                note: 60,
+                velocity: 90
+            })
+        );
+        assert!(events[4..].iter().all(|event| event.is_none()));
+    }
+
+    #[test]
+    fn skips_unsupported_messages() {
+        let mut parser = MidiParser::new();
+
+        // Program change, channel pressure and poly aftertouch
+        let events = parse_all(&mut parser, [0xC0, 5, 0xD0, 64, 0xA0, 60, 10, 0x90, 60, 1]);
+        assert!(events[..9].iter().all(|event| event.is_none()));
+        assert_eq!(events[9].and_then(|event| event.gate()), Some(true));
+    }
+
+    #[test]
+    fn normalized() {
+        assert_eq!(to_normalized(0), 0.0);
+        assert_eq!(to_normalized(127), 1.0);
+    }
+}
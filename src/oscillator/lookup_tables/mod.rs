@@ -2,6 +2,8 @@ pub mod bl_rect;
 
 use crate::fixed_point::math::sin_i16_unchecked;
 
+use core::f64::consts::{FRAC_PI_2, LN_2, PI, TAU};
+
 pub const fn sine_table<const N: usize>() -> [i16; N] {
     let min_step = (u16::MAX / N as u16) as usize;
     let mut buffer = [0; N];
@@ -16,3 +18,201 @@ pub const fn sine_table<const N: usize>() -> [i16; N] {
 
     return buffer;
 }
+
+/// Symmetric Hann window, starting and ending at 0
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tables::hann_table;
+///
+/// const WINDOW: [f32; 5] = hann_table::<5>();
+///
+/// assert_eq!(WINDOW, [0.0, 0.5, 1.0, 0.5, 0.0]);
+/// ```
+pub const fn hann_table<const N: usize>() -> [f32; N] {
+    cosine_sum_table::<N>(0.5, 0.5, 0.0)
+}
+
+/// Symmetric Hamming window, starting and ending at 0.08
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tables::hamming_table;
+///
+/// const WINDOW: [f32; 3] = hamming_table::<3>();
+///
+/// assert_eq!(WINDOW, [0.08, 1.0, 0.08]);
+/// ```
+pub const fn hamming_table<const N: usize>() -> [f32; N] {
+    cosine_sum_table::<N>(0.54, 0.46, 0.0)
+}
+
+/// Symmetric Blackman window, starting and ending at 0
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tables::blackman_table;
+///
+/// const WINDOW: [f32; 3] = blackman_table::<3>();
+///
+/// assert_eq!(WINDOW, [0.0, 1.0, 0.0]);
+/// ```
+pub const fn blackman_table<const N: usize>() -> [f32; N] {
+    cosine_sum_table::<N>(0.42, 0.5, 0.08)
+}
+
+/// a0 - a1 * cos(2πn / (N - 1)) + a2 * cos(4πn / (N - 1))
+const fn cosine_sum_table<const N: usize>(a0: f64, a1: f64, a2: f64) -> [f32; N] {
+    let mut table = [0.0; N];
+
+    if N == 1 {
+        table[0] = 1.0;
+        return table;
+    }
+
+    let mut n = 0;
+    while n < N {
+        let angle = TAU * n as f64 / (N - 1) as f64;
+        let value = a0 - a1 * const_cos(angle) + a2 * const_cos(2.0 * angle);
+
+        // Keeps the ends at exactly 0 instead of tiny rounding errors
+        table[n] = if value < 1e-12 { 0.0 } else { value as f32 };
+        n += 1;
+    }
+
+    table
+}
+
+/// Taylor series approximation of sin(x) usable in `const` contexts for table generation
+///
+/// Error is below 1e-12 after range reduction.
+pub(crate) const fn const_sin(x: f64) -> f64 {
+    // Reduce to [-π, π]
+    let mut x = x % TAU;
+    if x > PI {
+        x -= TAU;
+    } else if x < -PI {
+        x += TAU;
+    }
+
+    // Fold to [-π/2, π/2]
+    if x > FRAC_PI_2 {
+        x = PI - x;
+    } else if x < -FRAC_PI_2 {
+        x = -PI - x;
+    }
+
+    let x2 = x * x;
+    let mut res = 1.0;
+    let mut n = 17;
+
+    while n > 1 {
+        res = 1.0 - res * x2 / ((n * (n - 1)) as f64);
+        n -= 2;
+    }
+
+    x * res
+}
+
+pub(crate) const fn const_cos(x: f64) -> f64 {
+    const_sin(x + FRAC_PI_2)
+}
+
+/// Taylor series approximation of e^x usable in `const` contexts for table generation
+pub(crate) const fn const_exp(x: f64) -> f64 {
+    // Split into x = k * ln(2) + r with |r| <= ln(2) / 2
+    let k = if x >= 0.0 {
+        (x / LN_2 + 0.5) as i64
+    } else {
+        (x / LN_2 - 0.5) as i64
+    };
+    let r = x - k as f64 * LN_2;
+
+    let mut res = 1.0;
+    let mut n = 16;
+
+    while n > 0 {
+        res = 1.0 + res * r / n as f64;
+        n -= 1;
+    }
+
+    let mut k = k;
+    while k > 0 {
+        res *= 2.0;
+        k -= 1;
+    }
+    while k < 0 {
+        res *= 0.5;
+        k += 1;
+    }
+
+    res
+}
+
+/// Series approximation of ln(x) usable in `const` contexts for table generation
+///
+/// Only valid for positive numbers.
+pub(crate) const fn const_ln(x: f64) -> f64 {
+    // Split into x = m * 2^e with m in [1, 2)
+    let mut m = x;
+    let mut e = 0;
+
+    while m >= 2.0 {
+        m *= 0.5;
+        e += 1;
+    }
+    while m < 1.0 {
+        m *= 2.0;
+        e -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z)
+    let z = (m - 1.0) / (m + 1.0);
+    let z2 = z * z;
+
+    let mut res = 0.0;
+    let mut n = 31;
+
+    while n > 1 {
+        res = (res + 1.0 / n as f64) * z2;
+        n -= 2;
+    }
+
+    2.0 * z * (1.0 + res) + e as f64 * LN_2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_are_symmetric() {
+        const HANN: [f32; 64] = hann_table::<64>();
+        const HAMMING: [f32; 64] = hamming_table::<64>();
+        const BLACKMAN: [f32; 65] = blackman_table::<65>();
+
+        for n in 0..32 {
+            assert_eq!(HANN[n], HANN[63 - n]);
+            assert_eq!(HAMMING[n], HAMMING[63 - n]);
+            assert_eq!(BLACKMAN[n], BLACKMAN[64 - n]);
+        }
+
+        assert_eq!(BLACKMAN[32], 1.0);
+    }
+
+    #[test]
+    fn windows_match_definition() {
+        const HANN: [f32; 33] = hann_table::<33>();
+
+        for (n, value) in HANN.iter().enumerate() {
+            let exact = 0.5 - 0.5 * (TAU * n as f64 / 32.0).cos();
+            assert!((*value as f64 - exact).abs() < 1e-7, "n: {}", n);
+        }
+    }
+
+    #[test]
+    fn single_point() {
+        assert_eq!(hann_table::<1>(), [1.0]);
+        assert_eq!(blackman_table::<0>(), [0.0_f32; 0]);
+    }
+}
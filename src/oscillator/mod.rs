@@ -1,15 +1,75 @@
 pub(crate) mod lookup_tables;
+pub mod noise;
 pub mod osc_functional;
+pub mod osc_stereo;
 pub mod osc_wavetable;
+pub mod osc_wavetable8;
 pub mod phase_accumulator;
 
+pub use noise::WhiteNoise;
 pub use osc_functional::FunctionalOscillator;
+pub use osc_stereo::StereoOscillator;
 pub use osc_wavetable::WavetableOscillator;
-pub use phase_accumulator::{PhaseAccumulator, SoftPhaseAccumulator};
+pub use osc_wavetable8::{Sample8, Wavetable8Oscillator};
+pub use phase_accumulator::{FixedPhaseAccumulator, PhaseAccumulator, SoftPhaseAccumulator};
 
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::smoothed_param::{SmoothedParam, Smoothing};
+
+/// Seed of the next oscillator's start phase noise, steps by the golden ratio
+static NEXT_SEED: AtomicU32 = AtomicU32::new(0x1234_5678);
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
 pub enum Waveform {
     Sine = 0,
     Rectangle = 1,
     Sawtooth = 2,
     Triangle = 3,
 }
+
+/// Phase an oscillator starts from when it gets triggered
+///
+/// * `Zero` guarantees click-free starts
+/// * `Keep` lets the oscillator free-run
+/// * `Random` avoids the identical attack of percussive voices. Every oscillator draws
+///   from a noise source with a seed of its own, `set_seed` makes it reproducible.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub enum StartPhase {
+    Zero,
+    Keep,
+    Random,
+}
+
+/// Noise source for `StartPhase::Random` with a seed of its own, so voices created
+/// together don't start at the same random phase
+pub(crate) fn start_phase_noise() -> WhiteNoise {
+    // Plain loads and stores build on cores without atomic read-modify-write, at worst
+    // oscillators created at the same time from an interrupt share a seed
+    let seed = NEXT_SEED.load(Ordering::Relaxed);
+    NEXT_SEED.store(seed.wrapping_add(0x9E37_79B9), Ordering::Relaxed);
+
+    WhiteNoise::new(seed)
+}
+
+/// Idle frequency glide, set up by `start_glide`
+pub(crate) fn idle_glide() -> SmoothedParam {
+    SmoothedParam::new(0.0, 0.0, 0.0, Smoothing::Linear)
+}
+
+/// Restarts `glide` from the current frequency of `acc`, so it reaches `freq` after `time_ms`
+///
+/// Without a sample rate of `acc` the glide can't be timed and ends with the first update
+pub(crate) fn start_glide<PA: PhaseAccumulator>(
+    glide: &mut SmoothedParam,
+    acc: &PA,
+    freq: f32,
+    time_ms: f32,
+) {
+    glide.set_sr_unchecked(acc.sr());
+    glide.set_immediate(acc.freq());
+    glide.set_time(time_ms);
+    glide.set_target(freq);
+}
@@ -0,0 +1,86 @@
+const DEFAULT_SEED: u32 = 0x1234_5678;
+
+/// Xorshift based white noise source
+///
+/// Cheap enough to run per sample and fully deterministic for a given seed.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::WhiteNoise;
+///
+/// let mut noise = WhiteNoise::new(42);
+///
+/// let sample = noise.tick();
+/// assert!(sample >= -1.0 && sample < 1.0);
+/// ```
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct WhiteNoise {
+    state: u32,
+}
+
+impl WhiteNoise {
+    /// A seed of `0` would lock up the generator, so it is replaced by a default seed
+    pub const fn new(seed: u32) -> WhiteNoise {
+        WhiteNoise {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_seed(&mut self, seed: u32) {
+        *self = WhiteNoise::new(seed);
+    }
+
+    /// Uniformly distributed over the full `u32` range
+    #[inline(always)]
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// Uniformly distributed between -1 (inclusive) and 1 (exclusive)
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        (self.next_u32() as i32 >> 8) as f32 / (1 << 23) as f32
+    }
+}
+
+impl Default for WhiteNoise {
+    fn default() -> Self {
+        WhiteNoise::new(DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut noise = WhiteNoise::default();
+
+        for i in 0..10_000 {
+            let val = noise.tick();
+            assert!(val >= -1.0 && val < 1.0, "Failed at index: {}", i);
+        }
+    }
+
+    #[test]
+    fn zero_seed() {
+        let mut noise = WhiteNoise::new(0);
+        assert_ne!(noise.next_u32(), 0);
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut a = WhiteNoise::new(7);
+        let mut b = WhiteNoise::new(7);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+    }
+}
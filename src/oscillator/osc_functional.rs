@@ -2,20 +2,28 @@ use core::{f32::consts::TAU, ops::Neg};
 
 use crate::{
     float::{lerp_unchecked, AdditionalF32Ext},
-    oscillator::phase_accumulator::PhaseAccumulator,
+    oscillator::{noise::WhiteNoise, phase_accumulator::PhaseAccumulator},
+    smoothed_param::SmoothedParam,
 };
 
-use super::Waveform::{self, *};
+use super::{
+    idle_glide, start_glide, start_phase_noise, StartPhase,
+    Waveform::{self, *},
+};
 
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub struct FunctionalOscillator<PA>
 where
     PA: PhaseAccumulator,
 {
     acc: PA,
     wave: Waveform,
+    noise: WhiteNoise,
+    glide: SmoothedParam,
 }
 
 impl<PA: PhaseAccumulator> FunctionalOscillator<PA> {
@@ -23,6 +31,17 @@ impl<PA: PhaseAccumulator> FunctionalOscillator<PA> {
         FunctionalOscillator {
             acc: phase_accumulator,
             wave: Sine,
+            noise: start_phase_noise(),
+            glide: idle_glide(),
+        }
+    }
+
+    /// Restarts the oscillator according to `start_phase`, e.g. on a note trigger
+    pub fn trigger(&mut self, start_phase: StartPhase) {
+        match start_phase {
+            StartPhase::Zero => self.acc.set_phase(0),
+            StartPhase::Keep => {}
+            StartPhase::Random => self.acc.set_phase(self.noise.next_u32()),
         }
     }
 
@@ -60,11 +79,27 @@ impl<PA: PhaseAccumulator> FunctionalOscillator<PA> {
         }
     }
 
+    /// Jumps to `freq`, stopping any glide
     #[inline(always)]
     pub fn set_freq_unchecked(&mut self, freq: f32) {
+        self.glide.set_immediate(freq);
         self.acc.set_freq_unchecked(freq);
     }
 
+    /// Glides linearly to `freq` within `time_ms`, applied by `update_smoothed`
+    #[inline(always)]
+    pub fn set_freq_smoothed(&mut self, freq: f32, time_ms: f32) {
+        start_glide(&mut self.glide, &self.acc, freq, time_ms);
+    }
+
+    /// Advances the frequency glide by `samples`, call once per block
+    #[inline(always)]
+    pub fn update_smoothed(&mut self, samples: usize) {
+        if self.glide.is_smoothing() {
+            self.acc.set_freq_unchecked(self.glide.skip(samples));
+        }
+    }
+
     #[inline(always)]
     pub fn set_phase_shift_unchecked(&mut self, shift: f32) {
         self.acc.set_phase_shift((shift * u32::MAX as f32) as u32)
@@ -79,12 +114,19 @@ impl<PA: PhaseAccumulator> FunctionalOscillator<PA> {
     pub fn set_sr_unchecked(&mut self, sr: f32) {
         self.acc.set_sr_unchecked(sr);
     }
+
+    /// Seed of the noise source used for `StartPhase::Random`, every oscillator starts
+    /// with one of its own. Set it for reproducible start phases.
+    #[inline(always)]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.noise.set_seed(seed);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::oscillator::phase_accumulator::SoftPhaseAccumulator;
+    use crate::oscillator::phase_accumulator::{FixedPhaseAccumulator, SoftPhaseAccumulator};
 
     const SR: f32 = 48_000.0;
     const FREQ: f32 = 1000.0;
@@ -130,6 +172,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn trigger_start_phase() {
+        let mut osc = FunctionalOscillator::new(SoftPhaseAccumulator::new(FREQ, SR));
+        osc.set_wave(Sawtooth);
+
+        let first = osc.next();
+        osc.next();
+        osc.next();
+
+        osc.trigger(StartPhase::Zero);
+        assert_eq!(osc.next(), first);
+
+        osc.trigger(StartPhase::Keep);
+        assert_ne!(osc.next(), first);
+
+        osc.trigger(StartPhase::Random);
+        assert_ne!(osc.next(), first);
+    }
+
+    #[test]
+    fn random_start_phase_per_instance() {
+        let mut a = FunctionalOscillator::new(SoftPhaseAccumulator::new(FREQ, SR));
+        let mut b = FunctionalOscillator::new(SoftPhaseAccumulator::new(FREQ, SR));
+        a.set_wave(Sawtooth);
+        b.set_wave(Sawtooth);
+
+        a.trigger(StartPhase::Random);
+        b.trigger(StartPhase::Random);
+        assert_ne!(a.next(), b.next());
+
+        // Unless they share a seed
+        a.set_seed(7);
+        b.set_seed(7);
+        a.trigger(StartPhase::Random);
+        b.trigger(StartPhase::Random);
+        assert_eq!(a.next(), b.next());
+    }
+
+    #[test]
+    fn freq_glide() {
+        let mut osc = FunctionalOscillator::new(SoftPhaseAccumulator::new(FREQ, SR));
+        osc.set_freq_smoothed(2000.0, 10.0);
+
+        // 10 ms in blocks of 48 samples
+        for _ in 0..5 {
+            osc.update_smoothed(48);
+        }
+        assert!((osc.acc.freq() - 1500.0).abs() < 0.1);
+
+        for _ in 0..5 {
+            osc.update_smoothed(48);
+        }
+        assert_eq!(osc.acc.freq(), 2000.0);
+
+        osc.set_freq_smoothed(1000.0, 10.0);
+        osc.set_freq_unchecked(500.0);
+        osc.update_smoothed(48);
+        assert_eq!(osc.acc.freq(), 500.0);
+    }
+
+    #[test]
+    fn freq_glide_without_sr() {
+        let acc = || FixedPhaseAccumulator::from_ratio(1, 48).ok().unwrap();
+        let mut osc = FunctionalOscillator::new(acc());
+        let mut reference = FunctionalOscillator::new(acc());
+
+        // The exact ratio has no sample rate to time the glide with and stays untouched
+        osc.set_freq_smoothed(2000.0, 10.0);
+        osc.update_smoothed(48);
+
+        for _ in 0..96 {
+            assert_eq!(osc.next(), reference.next());
+        }
+    }
+
     #[test]
     fn check_bounds_tri() {
         let mut osc = FunctionalOscillator::new(SoftPhaseAccumulator::new(FREQ, SR));
@@ -1,19 +1,40 @@
 use crate::{
     memory::{memory_slice::MemorySlice, NonMutable},
-    oscillator::phase_accumulator::PhaseAccumulator,
+    oscillator::{noise::WhiteNoise, phase_accumulator::PhaseAccumulator},
+    smoothed_param::SmoothedParam,
 };
 
+use super::{idle_glide, start_glide, start_phase_noise, StartPhase};
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub struct WavetableOscillator<PA>
 where
     PA: PhaseAccumulator,
 {
     lookup_table: MemorySlice<NonMutable>,
     acc: PA,
+    noise: WhiteNoise,
+    glide: SmoothedParam,
 }
 
 impl<PA: PhaseAccumulator> WavetableOscillator<PA> {
     pub fn new(lookup_table: MemorySlice<NonMutable>, acc: PA) -> Self {
-        WavetableOscillator { lookup_table, acc }
+        WavetableOscillator {
+            lookup_table,
+            acc,
+            noise: start_phase_noise(),
+            glide: idle_glide(),
+        }
+    }
+
+    /// Restarts the oscillator according to `start_phase`, e.g. on a note trigger
+    pub fn trigger(&mut self, start_phase: StartPhase) {
+        match start_phase {
+            StartPhase::Zero => self.acc.set_phase(0),
+            StartPhase::Keep => {}
+            StartPhase::Random => self.acc.set_phase(self.noise.next_u32()),
+        }
     }
 
     pub fn next(&mut self) -> f32 {
@@ -27,11 +48,27 @@ impl<PA: PhaseAccumulator> WavetableOscillator<PA> {
         }
     }
 
+    /// Jumps to `freq`, stopping any glide
     #[inline(always)]
     pub fn set_freq_unchecked(&mut self, freq: f32) {
+        self.glide.set_immediate(freq);
         self.acc.set_freq_unchecked(freq);
     }
 
+    /// Glides linearly to `freq` within `time_ms`, applied by `update_smoothed`
+    #[inline(always)]
+    pub fn set_freq_smoothed(&mut self, freq: f32, time_ms: f32) {
+        start_glide(&mut self.glide, &self.acc, freq, time_ms);
+    }
+
+    /// Advances the frequency glide by `samples`, call once per block
+    #[inline(always)]
+    pub fn update_smoothed(&mut self, samples: usize) {
+        if self.glide.is_smoothing() {
+            self.acc.set_freq_unchecked(self.glide.skip(samples));
+        }
+    }
+
     #[inline(always)]
     pub fn set_phase_shift_unchecked(&mut self, shift: f32) {
         self.acc.set_phase_shift((shift * u32::MAX as f32) as u32)
@@ -41,4 +78,11 @@ impl<PA: PhaseAccumulator> WavetableOscillator<PA> {
     pub fn set_sr_unchecked(&mut self, sr: f32) {
         self.acc.set_sr_unchecked(sr);
     }
+
+    /// Seed of the noise source used for `StartPhase::Random`, every oscillator starts
+    /// with one of its own. Set it for reproducible start phases.
+    #[inline(always)]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.noise.set_seed(seed);
+    }
 }
@@ -0,0 +1,246 @@
+use crate::{
+    fixed_point::{math::lerp_q15, Q15},
+    oscillator::{noise::WhiteNoise, phase_accumulator::PhaseAccumulator},
+    smoothed_param::SmoothedParam,
+};
+
+use super::{idle_glide, start_glide, start_phase_noise, StartPhase};
+
+/// 8-bit table sample, scaled to the full `i16` range on readout
+///
+/// * `i8` is two's complement, `0` is silence
+/// * `u8` is offset binary, `128` is silence
+pub trait Sample8: Copy {
+    fn to_i16(self) -> i16;
+}
+
+impl Sample8 for i8 {
+    #[inline(always)]
+    fn to_i16(self) -> i16 {
+        (self as i16) << 8
+    }
+}
+
+impl Sample8 for u8 {
+    #[inline(always)]
+    fn to_i16(self) -> i16 {
+        (self as i16 - 128) << 8
+    }
+}
+
+/**
+Wavetable oscillator reading 8-bit tables, for parts with only a few kB of flash.
+
+`bank` holds one or more single cycle tables of `table_len` samples back to back,
+`select` switches between them. Samples are linearly interpolated in Q15, so the
+output resolution is better than the 8 bits of the tables.
+
+### Example
+
+```rust
+use embedded_audio_tools::{PhaseAccumulator, SoftPhaseAccumulator, Wavetable8Oscillator};
+
+// Two tables of four samples: a coarse triangle and a square
+static BANK: [i8; 8] = [0, 127, 0, -127, 127, 127, -127, -127];
+
+let acc = SoftPhaseAccumulator::new(440.0, 48_000.0);
+let mut osc = Wavetable8Oscillator::new(&BANK, 4, acc);
+osc.select(1);
+
+// during audio callback
+let sample = osc.next();
+# assert!(sample.abs() <= 1.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Wavetable8Oscillator<PA, S>
+where
+    PA: PhaseAccumulator,
+    S: Sample8 + 'static,
+{
+    bank: &'static [S],
+    table_len: usize,
+    offset: usize,
+    acc: PA,
+    noise: WhiteNoise,
+    glide: SmoothedParam,
+}
+
+impl<PA: PhaseAccumulator, S: Sample8 + 'static> Wavetable8Oscillator<PA, S> {
+    /// `table_len` gets clamped to the length of `bank`, the first table is selected
+    pub fn new(bank: &'static [S], table_len: usize, acc: PA) -> Self {
+        Wavetable8Oscillator {
+            bank,
+            table_len: table_len.min(bank.len()),
+            offset: 0,
+            acc,
+            noise: start_phase_noise(),
+            glide: idle_glide(),
+        }
+    }
+
+    /// Number of complete tables in the bank
+    #[inline(always)]
+    pub fn tables(&self) -> usize {
+        self.bank.len().checked_div(self.table_len).unwrap_or(0)
+    }
+
+    /// Selects the table at `index`, clamps to the last table of the bank
+    #[inline(always)]
+    pub fn select(&mut self, index: usize) {
+        let index = index.min(self.tables().saturating_sub(1));
+        self.offset = index * self.table_len;
+    }
+
+    /// Restarts the oscillator according to `start_phase`, e.g. on a note trigger
+    pub fn trigger(&mut self, start_phase: StartPhase) {
+        match start_phase {
+            StartPhase::Zero => self.acc.set_phase(0),
+            StartPhase::Keep => {}
+            StartPhase::Random => self.acc.set_phase(self.noise.next_u32()),
+        }
+    }
+
+    /// Next interpolated sample as `Q15`, works without an FPU
+    pub fn next_q15(&mut self) -> Q15 {
+        if self.table_len == 0 {
+            return Q15::ZERO;
+        }
+
+        // 32.32 position within the table
+        let position = self.acc.next_value() as u64 * self.table_len as u64;
+        let index = (position >> 32) as usize;
+        let frac = ((position >> 17) & 0x7FFF) as i16;
+
+        let next = if index + 1 == self.table_len {
+            0
+        } else {
+            index + 1
+        };
+        let table = &self.bank[self.offset..self.offset + self.table_len];
+
+        Q15(lerp_q15(table[index].to_i16(), table[next].to_i16(), frac))
+    }
+
+    /// Next interpolated sample between -1 and 1
+    #[inline(always)]
+    pub fn next(&mut self) -> f32 {
+        self.next_q15().to_f32()
+    }
+
+    /// Jumps to `freq`, stopping any glide
+    #[inline(always)]
+    pub fn set_freq_unchecked(&mut self, freq: f32) {
+        self.glide.set_immediate(freq);
+        self.acc.set_freq_unchecked(freq);
+    }
+
+    /// Glides linearly to `freq` within `time_ms`, applied by `update_smoothed`
+    #[inline(always)]
+    pub fn set_freq_smoothed(&mut self, freq: f32, time_ms: f32) {
+        start_glide(&mut self.glide, &self.acc, freq, time_ms);
+    }
+
+    /// Advances the frequency glide by `samples`, call once per block
+    #[inline(always)]
+    pub fn update_smoothed(&mut self, samples: usize) {
+        if self.glide.is_smoothing() {
+            self.acc.set_freq_unchecked(self.glide.skip(samples));
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_phase_shift_unchecked(&mut self, shift: f32) {
+        self.acc.set_phase_shift((shift * u32::MAX as f32) as u32)
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.acc.set_sr_unchecked(sr);
+    }
+
+    /// Seed of the noise source used for `StartPhase::Random`, every oscillator starts
+    /// with one of its own. Set it for reproducible start phases.
+    #[inline(always)]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.noise.set_seed(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oscillator::phase_accumulator::FixedPhaseAccumulator;
+
+    static BANK_I8: [i8; 8] = [0, 64, 0, -64, 127, 127, -128, -128];
+    static BANK_U8: [u8; 4] = [128, 192, 128, 64];
+
+    #[test]
+    fn sample_scaling() {
+        assert_eq!(0_i8.to_i16(), 0);
+        assert_eq!(i8::MIN.to_i16(), i16::MIN);
+        assert_eq!(128_u8.to_i16(), 0);
+        assert_eq!(0_u8.to_i16(), i16::MIN);
+        assert_eq!(255_u8.to_i16(), 127 << 8);
+    }
+
+    #[test]
+    fn interpolates_between_samples() {
+        // eight samples per period, two per table entry
+        let acc = FixedPhaseAccumulator::from_increment(1 << 29);
+        let mut osc = Wavetable8Oscillator::new(&BANK_I8, 4, acc);
+
+        let expected = [
+            32 << 8,
+            64 << 8,
+            32 << 8,
+            0,
+            -32 << 8,
+            -64 << 8,
+            -32 << 8,
+            0,
+        ];
+        for value in expected {
+            assert_eq!(osc.next_q15().to_bits(), value);
+        }
+    }
+
+    #[test]
+    fn signed_and_unsigned_match() {
+        let acc = FixedPhaseAccumulator::from_ratio(1, 48).ok().unwrap();
+        let mut signed = Wavetable8Oscillator::new(&BANK_I8, 4, acc);
+        let acc = FixedPhaseAccumulator::from_ratio(1, 48).ok().unwrap();
+        let mut unsigned = Wavetable8Oscillator::new(&BANK_U8, 4, acc);
+
+        for _ in 0..100 {
+            assert_eq!(signed.next_q15(), unsigned.next_q15());
+        }
+    }
+
+    #[test]
+    fn select_clamps_to_bank() {
+        let acc = FixedPhaseAccumulator::from_increment(1 << 30);
+        let mut osc = Wavetable8Oscillator::new(&BANK_I8, 4, acc);
+        assert_eq!(osc.tables(), 2);
+
+        osc.select(5);
+        osc.trigger(StartPhase::Zero);
+
+        let expected = [127 << 8, -128 << 8, -128 << 8, 127 << 8];
+        for value in expected {
+            assert_eq!(osc.next_q15().to_bits(), value);
+        }
+    }
+
+    #[test]
+    fn empty_bank_is_silent() {
+        static EMPTY: [i8; 0] = [];
+
+        let acc = FixedPhaseAccumulator::from_increment(1 << 30);
+        let mut osc = Wavetable8Oscillator::new(&EMPTY, 4, acc);
+
+        assert_eq!(osc.tables(), 0);
+        assert_eq!(osc.next(), 0.0);
+    }
+}
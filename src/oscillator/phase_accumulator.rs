@@ -6,6 +6,24 @@ pub trait PhaseAccumulator {
     fn set_sr_unchecked(&mut self, sr: f32);
     fn set_freq_unchecked(&mut self, freq: f32);
     fn set_phase_shift(&mut self, shift: u32);
+    /// Jumps to `phase`, used by the oscillators' `trigger`. Does nothing by default, so
+    /// `StartPhase::Zero` and `StartPhase::Random` then act like `StartPhase::Keep`.
+    #[inline(always)]
+    fn set_phase(&mut self, _phase: u32) {}
+
+    /// Frequency in Hz, 0 if the accumulator doesn't track it
+    #[inline(always)]
+    fn freq(&self) -> f32 {
+        0.0
+    }
+
+    /// Sample rate in Hz, 0 if the accumulator doesn't track it. Frequency glides need
+    /// it for their timing, without it they jump at the next `update_smoothed`.
+    #[inline(always)]
+    fn sr(&self) -> f32 {
+        0.0
+    }
+
     fn next_value(&mut self) -> u32;
     fn next_value_normalized(&mut self) -> f32;
 }
@@ -37,6 +55,21 @@ impl PhaseAccumulator for SoftPhaseAccumulator {
         self.shift = shift;
     }
 
+    #[inline(always)]
+    fn set_phase(&mut self, phase: u32) {
+        self.counter = phase;
+    }
+
+    #[inline(always)]
+    fn freq(&self) -> f32 {
+        self.freq
+    }
+
+    #[inline(always)]
+    fn sr(&self) -> f32 {
+        u32::MAX as f32 / self.min_step
+    }
+
     #[inline(always)]
     fn next_value(&mut self) -> u32 {
         self.tick();
@@ -50,6 +83,8 @@ impl PhaseAccumulator for SoftPhaseAccumulator {
     }
 }
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
 pub struct SoftPhaseAccumulator {
     counter: u32,
     freq: f32,
@@ -57,6 +92,183 @@ pub struct SoftPhaseAccumulator {
     min_step: f32,
 }
 
+/**
+Phase accumulator programmed with an exact integer increment or a rational
+`freq = (num / den) * sr` relationship.
+
+The fractional part of a rational increment is carried over like in Bresenham's line
+algorithm, so the phase stays exact over arbitrarily long sequences, e.g. for test tones
+or sample rate conversion.
+
+### Example
+
+```rust
+use embedded_audio_tools::{FixedPhaseAccumulator, PhaseAccumulator};
+
+// 1 kHz at 48 kHz, exactly one period every 48 samples
+let mut acc = FixedPhaseAccumulator::from_ratio(1, 48).ok().unwrap();
+
+for _ in 0..47 {
+    acc.next_value();
+}
+assert_eq!(acc.next_value(), 0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct FixedPhaseAccumulator {
+    counter: u32,
+    shift: u32,
+    increment: u32,
+    remainder: u32,
+    den: u32,
+    error: u32,
+    freq: f32,
+    sr: f32,
+}
+
+impl FixedPhaseAccumulator {
+    /// Advances the phase by exactly `increment` / 2^32 periods per sample
+    pub const fn from_increment(increment: u32) -> FixedPhaseAccumulator {
+        FixedPhaseAccumulator {
+            counter: 0,
+            shift: 0,
+            increment,
+            remainder: 0,
+            den: 1,
+            error: 0,
+            freq: 0.0,
+            sr: 0.0,
+        }
+    }
+
+    /// Frequency of exactly `num / den` times the sample rate
+    pub const fn from_ratio(num: u32, den: u32) -> Result<FixedPhaseAccumulator, FrequencyError> {
+        let mut acc = FixedPhaseAccumulator::from_increment(0);
+
+        match acc.set_ratio(num, den) {
+            Ok(()) => Ok(acc),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Changes the frequency to exactly `num / den` times the sample rate
+    pub const fn set_ratio(&mut self, num: u32, den: u32) -> Result<(), FrequencyError> {
+        if num == 0 {
+            return Err(Zero);
+        }
+
+        if den == 0 || num as u64 * 2 > den as u64 {
+            return Err(BiggerThanNyquist);
+        }
+
+        let full_turn = (num as u64) << 32;
+
+        self.increment = (full_turn / den as u64) as u32;
+        self.remainder = (full_turn % den as u64) as u32;
+        self.den = den;
+        self.error = 0;
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_increment(&mut self, increment: u32) {
+        self.increment = increment;
+        self.remainder = 0;
+        self.den = 1;
+        self.error = 0;
+    }
+
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.counter = self.counter.wrapping_add(self.increment);
+
+        // Compared before adding, so a `den` above 2^31 can't overflow `error`
+        let headroom = self.den - self.remainder;
+        if self.error >= headroom {
+            self.error -= headroom;
+            self.counter = self.counter.wrapping_add(1);
+        } else {
+            self.error += self.remainder;
+        }
+    }
+
+    #[inline(always)]
+    fn update_increment(&mut self) {
+        // `from_increment` and `from_ratio` leave no sample rate to convert with
+        if self.sr <= 0.0 {
+            return;
+        }
+
+        // Only as exact as f32 allows, use `set_ratio` for exact frequencies
+        let increment = (self.freq / self.sr * (u32::MAX as f32 + 1.0)) as u32;
+        self.set_increment(increment);
+    }
+}
+
+impl PhaseAccumulator for FixedPhaseAccumulator {
+    type Object = FixedPhaseAccumulator;
+
+    fn new(freq: f32, sr: f32) -> FixedPhaseAccumulator {
+        let mut acc = FixedPhaseAccumulator::from_increment(0);
+        acc.freq = freq;
+        acc.sr = sr;
+        acc.update_increment();
+        acc
+    }
+
+    #[inline(always)]
+    fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_increment();
+    }
+
+    /// Ignored until `set_sr_unchecked` when set up with `from_increment` or `from_ratio`
+    #[inline(always)]
+    fn set_freq_unchecked(&mut self, freq: f32) {
+        self.freq = freq;
+        self.update_increment();
+    }
+
+    #[inline(always)]
+    fn set_phase_shift(&mut self, shift: u32) {
+        self.shift = shift;
+    }
+
+    #[inline(always)]
+    fn set_phase(&mut self, phase: u32) {
+        self.counter = phase;
+        self.error = 0;
+    }
+
+    /// 0 when set up with `from_increment` or `from_ratio`
+    #[inline(always)]
+    fn freq(&self) -> f32 {
+        self.freq
+    }
+
+    /// 0 when set up with `from_increment` or `from_ratio`
+    #[inline(always)]
+    fn sr(&self) -> f32 {
+        self.sr
+    }
+
+    #[inline(always)]
+    fn next_value(&mut self) -> u32 {
+        self.tick();
+        self.counter.wrapping_add(self.shift)
+    }
+
+    #[inline(always)]
+    fn next_value_normalized(&mut self) -> f32 {
+        self.tick();
+        self.counter.wrapping_add(self.shift) as f32 / (u32::MAX as f32 + 1.0)
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
 pub enum FrequencyError {
     Zero,
     Negative,
@@ -83,3 +295,108 @@ impl SoftPhaseAccumulator {
         return Ok(self.set_freq_unchecked(freq));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio_is_phase_exact() {
+        // 440 Hz at 44.1 kHz
+        let mut acc = FixedPhaseAccumulator::from_ratio(440, 44_100).ok().unwrap();
+
+        for _ in 0..100 {
+            for _ in 0..2204 {
+                assert_ne!(acc.next_value(), 0);
+            }
+
+            // 22 periods fit exactly into 2205 samples
+            assert_eq!(acc.next_value(), 0);
+        }
+    }
+
+    #[test]
+    fn large_denominator() {
+        for (num, den) in [(2, 4_000_000_000), (12_345, u32::MAX)] {
+            let mut acc = FixedPhaseAccumulator::from_ratio(num, den).ok().unwrap();
+
+            for n in 1..=10_000_u64 {
+                let exact = ((n * ((num as u64) << 32)) / den as u64) as u32;
+                assert_eq!(acc.next_value(), exact);
+            }
+        }
+    }
+
+    #[test]
+    fn integer_increment() {
+        let mut acc = FixedPhaseAccumulator::from_increment(1 << 30);
+
+        assert_eq!(acc.next_value(), 1 << 30);
+        assert_eq!(acc.next_value(), 1 << 31);
+        acc.next_value();
+        assert_eq!(acc.next_value(), 0);
+    }
+
+    #[test]
+    fn ratio_errors() {
+        assert!(matches!(FixedPhaseAccumulator::from_ratio(0, 1), Err(Zero)));
+        assert!(matches!(
+            FixedPhaseAccumulator::from_ratio(1, 0),
+            Err(BiggerThanNyquist)
+        ));
+        assert!(matches!(
+            FixedPhaseAccumulator::from_ratio(2, 3),
+            Err(BiggerThanNyquist)
+        ));
+        assert!(FixedPhaseAccumulator::from_ratio(1, 2).is_ok());
+    }
+
+    #[test]
+    fn float_frequency_without_sr() {
+        let mut acc = FixedPhaseAccumulator::from_ratio(1, 4).ok().unwrap();
+        acc.set_freq_unchecked(1000.0);
+
+        assert_eq!(acc.next_value(), 1 << 30);
+    }
+
+    #[test]
+    fn float_frequency() {
+        let mut acc = FixedPhaseAccumulator::new(12_000.0, 48_000.0);
+
+        assert_eq!(acc.next_value(), 1 << 30);
+    }
+
+    /// Implements only the required methods, like an accumulator outside this crate
+    struct MinimalAccumulator(u32);
+
+    impl PhaseAccumulator for MinimalAccumulator {
+        type Object = MinimalAccumulator;
+
+        fn new(_freq: f32, _sr: f32) -> MinimalAccumulator {
+            MinimalAccumulator(0)
+        }
+
+        fn set_sr_unchecked(&mut self, _sr: f32) {}
+        fn set_freq_unchecked(&mut self, _freq: f32) {}
+        fn set_phase_shift(&mut self, _shift: u32) {}
+
+        fn next_value(&mut self) -> u32 {
+            self.0 = self.0.wrapping_add(1 << 28);
+            self.0
+        }
+
+        fn next_value_normalized(&mut self) -> f32 {
+            self.next_value() as f32 / u32::MAX as f32
+        }
+    }
+
+    #[test]
+    fn default_methods() {
+        let mut acc = MinimalAccumulator::new(440.0, 48_000.0);
+        assert_eq!((acc.freq(), acc.sr()), (0.0, 0.0));
+
+        acc.next_value();
+        acc.set_phase(0);
+        assert_eq!(acc.next_value(), 2 << 28);
+    }
+}
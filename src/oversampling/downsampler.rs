@@ -0,0 +1,119 @@
+use super::halfband;
+use crate::block::dot_ring;
+
+/**
+Halves the sample rate with a polyphase halfband filter, see the `oversampling` module.
+
+### Example
+
+```rust
+use embedded_audio_tools::oversampling::Downsampler2x;
+
+let mut down: Downsampler2x<16> = Downsampler2x::new();
+
+let input = [0.5; 64];
+let mut output = [0.0; 32];
+
+down.process_block(&input, &mut output);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Downsampler2x<const TAPS: usize> {
+    /// Halved, the odd branch only contributes half of the output
+    coeffs: [f32; TAPS],
+    even: [f32; TAPS],
+    odd: [f32; TAPS],
+    pos: usize,
+}
+
+impl<const TAPS: usize> Downsampler2x<TAPS> {
+    pub const fn new() -> Downsampler2x<TAPS> {
+        let mut coeffs = halfband::<TAPS>();
+
+        let mut j = 0;
+        while j < TAPS {
+            coeffs[j] *= 0.5;
+            j += 1;
+        }
+
+        Downsampler2x {
+            coeffs,
+            even: [0.0; TAPS],
+            odd: [0.0; TAPS],
+            pos: 0,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.even = [0.0; TAPS];
+        self.odd = [0.0; TAPS];
+    }
+
+    /// Returns one sample for two `samples` at the doubled rate
+    pub fn process(&mut self, samples: [f32; 2]) -> f32 {
+        self.even[self.pos] = samples[0];
+        self.odd[self.pos] = samples[1];
+
+        // The oldest sample sits right after the newest one
+        let oldest = if self.pos + 1 == TAPS {
+            0
+        } else {
+            self.pos + 1
+        };
+
+        // The center tap of 0.5 falls onto the even samples, all others onto the odd ones
+        let center = self.even[(oldest + TAPS / 2) % TAPS];
+        let out = 0.5 * center + dot_ring(&self.coeffs, &self.odd, oldest);
+
+        self.pos = oldest;
+        out
+    }
+
+    /// Fills `output` with half as many samples as taken from `input`
+    pub fn process_block(&mut self, input: &[f32], output: &mut [f32]) {
+        for (samples, out) in input.chunks_exact(2).zip(output.iter_mut()) {
+            *out = self.process([samples[0], samples[1]]);
+        }
+    }
+}
+
+impl<const TAPS: usize> Default for Downsampler2x<TAPS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::TAU;
+
+    #[test]
+    fn removes_upper_band() {
+        let mut down: Downsampler2x<32> = Downsampler2x::new();
+
+        // 40 kHz at 96 kHz would alias to 8 kHz at 48 kHz
+        let input: [f32; 2000] =
+            core::array::from_fn(|i| (TAU * 40_000.0 * i as f64 / 96_000.0).sin() as f32);
+        let mut output = [0.0; 1000];
+        down.process_block(&input, &mut output);
+
+        let peak = output[50..]
+            .iter()
+            .fold(0.0_f32, |peak, x| peak.max(x.abs()));
+        assert!(peak < 0.001, "{}", peak);
+    }
+
+    #[test]
+    fn unity_gain_at_dc() {
+        let mut down: Downsampler2x<16> = Downsampler2x::new();
+
+        let input = [0.5; 64];
+        let mut output = [0.0; 32];
+        down.process_block(&input, &mut output);
+
+        assert!((output[31] - 0.5).abs() < 1e-6);
+    }
+}
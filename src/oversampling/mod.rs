@@ -0,0 +1,182 @@
+/*!
+Halfband 2x up- and downsampling, e.g. to run a clipper or waveshaper at a higher rate
+where its harmonics don't fold back into the audible range.
+
+Both are polyphase halfband FIR filters with a Blackman windowed sinc: only every other
+coefficient is non-zero, so `TAPS` (even) multiplications per input or output sample
+cover a filter of `2 * TAPS - 1` taps. At the lower rate, upsampling delays the signal
+by `TAPS / 2` samples and downsampling by `TAPS / 2 - 1`. More taps give a steeper
+transition, 16 to 32 are plenty for most audio uses.
+
+Stages cascade for 4x or 8x, either by hand or by nesting `Oversampled`.
+
+### Example
+
+```rust
+use embedded_audio_tools::clipping::hard_clip;
+use embedded_audio_tools::oversampling::{Downsampler2x, Oversampled, Upsampler2x};
+use embedded_audio_tools::Process;
+
+// Only part of the chain at 2x
+let mut up: Upsampler2x<16> = Upsampler2x::new();
+let mut down: Downsampler2x<16> = Downsampler2x::new();
+
+let [a, b] = up.process(0.5);
+let sample = down.process([hard_clip(a * 4.0), hard_clip(b * 4.0)]);
+
+// or a whole processor at 4x
+let mut clipper: Oversampled<Oversampled<_, 16>, 16> =
+    Oversampled::new(Oversampled::new(|x: f32| hard_clip(x * 4.0)));
+
+let sample = clipper.process(0.5);
+```
+*/
+
+pub mod downsampler;
+pub mod upsampler;
+
+pub use downsampler::Downsampler2x;
+pub use upsampler::Upsampler2x;
+
+use core::f64::consts::PI;
+
+use crate::oscillator::lookup_tables::{const_cos, const_sin};
+use crate::process::Process;
+
+/// Coefficients of the odd branch of a halfband filter, from the oldest to the newest
+/// sample, normalized to a sum of 1
+const fn halfband<const TAPS: usize>() -> [f32; TAPS] {
+    assert!(
+        TAPS >= 2 && TAPS.is_multiple_of(2),
+        "The number of taps has to be even"
+    );
+
+    let half = (TAPS / 2) as f64;
+
+    let mut taps = [0.0; TAPS];
+    let mut sum = 0.0;
+
+    let mut j = 0;
+    while j < TAPS {
+        // Distance to the center, in samples at the lower rate
+        let x = j as f64 - half + 0.5;
+
+        let sinc = const_sin(PI * x) / (PI * x);
+        let window = 0.42 + 0.5 * const_cos(PI * x / half) + 0.08 * const_cos(2.0 * PI * x / half);
+
+        taps[j] = sinc * window;
+        sum += taps[j];
+        j += 1;
+    }
+
+    let mut coeffs = [0.0; TAPS];
+
+    let mut j = 0;
+    while j < TAPS {
+        coeffs[j] = (taps[j] / sum) as f32;
+        j += 1;
+    }
+
+    coeffs
+}
+
+/**
+Runs a processor at twice the sample rate of its surroundings.
+
+Nest it for 4x or 8x: `Oversampled<Oversampled<P, 16>, 16>` runs `P` at 4x.
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Oversampled<P, const TAPS: usize> {
+    pub processor: P,
+    up: Upsampler2x<TAPS>,
+    down: Downsampler2x<TAPS>,
+}
+
+impl<P: Process, const TAPS: usize> Oversampled<P, TAPS> {
+    pub const fn new(processor: P) -> Oversampled<P, TAPS> {
+        Oversampled {
+            processor,
+            up: Upsampler2x::new(),
+            down: Downsampler2x::new(),
+        }
+    }
+
+    /// Latency in samples at the outer rate, not counting the processor's own
+    pub const fn latency(&self) -> usize {
+        TAPS - 1
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.up.reset();
+        self.down.reset();
+    }
+}
+
+impl<P: Process, const TAPS: usize> Process for Oversampled<P, TAPS> {
+    #[inline]
+    fn process(&mut self, sample: f32) -> f32 {
+        let [a, b] = self.up.process(sample);
+        self.down
+            .process([self.processor.process(a), self.processor.process(b)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::TAU;
+
+    const SR: f64 = 48_000.0;
+
+    fn peak_after_settling(freq: f64, mut process: impl FnMut(f32) -> f32) -> f32 {
+        (0..2000)
+            .map(|i| process((TAU * freq * i as f64 / SR).sin() as f32))
+            .skip(100)
+            .fold(0.0, |peak: f32, x| peak.max(x.abs()))
+    }
+
+    #[test]
+    fn halfband_symmetric() {
+        let coeffs = halfband::<16>();
+
+        for j in 0..8 {
+            assert!((coeffs[j] - coeffs[15 - j]).abs() < 1e-7);
+        }
+
+        assert!((coeffs.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn passes_audio_band() {
+        let mut oversampled: Oversampled<_, 16> = Oversampled::new(|x| x);
+
+        for freq in [100.0, 1000.0, 10_000.0] {
+            let peak = peak_after_settling(freq, |x| oversampled.process(x));
+            assert!((peak - 1.0).abs() < 0.01, "{} Hz: {}", freq, peak);
+        }
+    }
+
+    #[test]
+    fn latency() {
+        let mut oversampled: Oversampled<_, 16> = Oversampled::new(|x| x);
+        let latency = oversampled.latency();
+
+        // Step response is centered on the latency
+        let step: [f32; 32] = core::array::from_fn(|_| oversampled.process(1.0));
+
+        assert!(step[latency - 1] < 0.5);
+        assert!(step[latency] >= 0.5);
+        assert!((step[31] - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn nested_4x() {
+        let mut oversampled: Oversampled<Oversampled<_, 16>, 16> =
+            Oversampled::new(Oversampled::new(|x| x));
+
+        let peak = peak_after_settling(1000.0, |x| oversampled.process(x));
+        assert!((peak - 1.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,106 @@
+use super::halfband;
+use crate::block::dot_ring;
+
+/**
+Doubles the sample rate with a polyphase halfband filter, see the `oversampling` module.
+
+### Example
+
+```rust
+use embedded_audio_tools::oversampling::Upsampler2x;
+
+let mut up: Upsampler2x<16> = Upsampler2x::new();
+
+let input = [0.5; 32];
+let mut output = [0.0; 64];
+
+up.process_block(&input, &mut output);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Upsampler2x<const TAPS: usize> {
+    coeffs: [f32; TAPS],
+    history: [f32; TAPS],
+    pos: usize,
+}
+
+impl<const TAPS: usize> Upsampler2x<TAPS> {
+    pub const fn new() -> Upsampler2x<TAPS> {
+        Upsampler2x {
+            coeffs: halfband::<TAPS>(),
+            history: [0.0; TAPS],
+            pos: 0,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.history = [0.0; TAPS];
+    }
+
+    /// Returns two samples at the doubled rate for one `sample`
+    pub fn process(&mut self, sample: f32) -> [f32; 2] {
+        self.history[self.pos] = sample;
+
+        // The oldest sample sits right after the newest one
+        let oldest = if self.pos + 1 == TAPS {
+            0
+        } else {
+            self.pos + 1
+        };
+
+        // The even phase is the delayed input itself, the odd phase lies halfway to the
+        // next sample
+        let center = self.history[(oldest + TAPS / 2 - 1) % TAPS];
+        let between = dot_ring(&self.coeffs, &self.history, oldest);
+
+        self.pos = oldest;
+        [center, between]
+    }
+
+    /// Fills `output` with twice as many samples as taken from `input`
+    pub fn process_block(&mut self, input: &[f32], output: &mut [f32]) {
+        for (sample, out) in input.iter().zip(output.chunks_exact_mut(2)) {
+            out.copy_from_slice(&self.process(*sample));
+        }
+    }
+}
+
+impl<const TAPS: usize> Default for Upsampler2x<TAPS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn even_phase_is_delayed_input() {
+        let mut up: Upsampler2x<8> = Upsampler2x::new();
+
+        let input: [f32; 20] = core::array::from_fn(|i| i as f32 * 0.1 - 1.0);
+        let mut output = [0.0; 40];
+        up.process_block(&input, &mut output);
+
+        for i in 4..input.len() {
+            assert_eq!(output[2 * i], input[i - 4]);
+        }
+    }
+
+    #[test]
+    fn odd_phase_interpolates() {
+        let mut up: Upsampler2x<16> = Upsampler2x::new();
+
+        // A ramp is interpolated linearly by any symmetric filter
+        let input: [f32; 40] = core::array::from_fn(|i| i as f32);
+        let mut output = [0.0; 80];
+        up.process_block(&input, &mut output);
+
+        for i in 16..input.len() {
+            assert!((output[2 * i + 1] - (i as f32 - 7.5)).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::float::AdditionalF32Ext;
+use ParamMapError::*;
+
+/// Describes how the normalized control range gets distributed over the target range
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ParamCurve {
+    Linear,
+    /// Equal ratios per step, e.g. for frequencies. Needs a strictly positive range.
+    Exponential,
+    /// `min + (max - min) * x^skew`, values below 1 expand the lower end
+    Skewed(f32),
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+pub enum ParamMapError {
+    EmptyRange,
+    NonPositiveRange,
+    InvalidSkew,
+}
+
+/**
+Maps a normalized control value between 0 and 1 (pot, MIDI CC, ...) onto a
+target range and back.
+
+## Example
+
+```rust
+use embedded_audio_tools::ParamMap;
+
+// 20 Hz to 20 kHz cutoff
+let cutoff = ParamMap::exponential(20.0, 20_000.0).unwrap();
+
+let freq = cutoff.map(0.5);
+assert!((freq - 632.45).abs() < 0.1);
+assert!((cutoff.unmap(freq) - 0.5).abs() < 1e-4);
+
+// -60 to 0 dB level, linear in dB
+let level = ParamMap::linear(-60.0, 0.0).unwrap();
+assert_eq!(level.map(0.5), -30.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct ParamMap {
+    min: f32,
+    max: f32,
+    curve: ParamCurve,
+    log_ratio: f32,
+}
+
+impl ParamMap {
+    pub fn linear(min: f32, max: f32) -> Result<ParamMap, ParamMapError> {
+        ParamMap::new(min, max, ParamCurve::Linear)
+    }
+
+    pub fn exponential(min: f32, max: f32) -> Result<ParamMap, ParamMapError> {
+        ParamMap::new(min, max, ParamCurve::Exponential)
+    }
+
+    pub fn skewed(min: f32, max: f32, skew: f32) -> Result<ParamMap, ParamMapError> {
+        ParamMap::new(min, max, ParamCurve::Skewed(skew))
+    }
+
+    pub fn new(min: f32, max: f32, curve: ParamCurve) -> Result<ParamMap, ParamMapError> {
+        if min == max {
+            return Err(EmptyRange);
+        }
+
+        match curve {
+            ParamCurve::Exponential if min <= 0.0 || max <= 0.0 => return Err(NonPositiveRange),
+            ParamCurve::Skewed(skew) if skew <= 0.0 || !skew.is_finite() => {
+                return Err(InvalidSkew)
+            }
+            _ => {}
+        }
+
+        Ok(ParamMap {
+            min,
+            max,
+            curve,
+            log_ratio: (max / min).fast_log2(),
+        })
+    }
+
+    /// Maps a normalized value onto the target range. Clamps `normalized` between 0 and 1.
+    pub fn map(&self, normalized: f32) -> f32 {
+        let x = normalized.clamp(0.0, 1.0);
+
+        match self.curve {
+            ParamCurve::Linear => self.min + (self.max - self.min) * x,
+            // Hit the upper bound exactly despite the approximated logarithm
+            ParamCurve::Exponential if x == 1.0 => self.max,
+            ParamCurve::Exponential => self.min * (x * self.log_ratio).fast_exp2(),
+            ParamCurve::Skewed(skew) => self.min + (self.max - self.min) * x.fast_pow(skew),
+        }
+    }
+
+    /// Maps a value of the target range back to a normalized value between 0 and 1
+    pub fn unmap(&self, value: f32) -> f32 {
+        let x = match self.curve {
+            ParamCurve::Linear => (value - self.min) / (self.max - self.min),
+            ParamCurve::Exponential => (value / self.min).fast_log2() / self.log_ratio,
+            ParamCurve::Skewed(skew) => ((value - self.min) / (self.max - self.min))
+                .clamp(0.0, 1.0)
+                .fast_pow(1.0 / skew),
+        };
+
+        if x.is_nan() {
+            return 0.0;
+        }
+
+        x.clamp(0.0, 1.0)
+    }
+
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_errors() {
+        assert_eq!(ParamMap::linear(1.0, 1.0).unwrap_err(), EmptyRange);
+        assert_eq!(
+            ParamMap::exponential(0.0, 1.0).unwrap_err(),
+            NonPositiveRange
+        );
+        assert_eq!(
+            ParamMap::exponential(-1.0, 1.0).unwrap_err(),
+            NonPositiveRange
+        );
+        assert_eq!(ParamMap::skewed(0.0, 1.0, 0.0).unwrap_err(), InvalidSkew);
+    }
+
+    #[test]
+    fn end_points() {
+        for map in [
+            ParamMap::linear(-60.0, 0.0).unwrap(),
+            ParamMap::exponential(20.0, 20_000.0).unwrap(),
+            ParamMap::skewed(0.0, 10.0, 0.3).unwrap(),
+        ] {
+            assert_eq!(map.map(0.0), map.min());
+            assert_eq!(map.map(-1.0), map.min());
+            assert_eq!(map.map(2.0), map.max());
+            assert_eq!(map.unmap(map.min()), 0.0);
+            assert_eq!(map.unmap(map.max() * 2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for map in [
+            ParamMap::linear(-60.0, 0.0).unwrap(),
+            ParamMap::linear(10.0, -10.0).unwrap(),
+            ParamMap::exponential(20.0, 20_000.0).unwrap(),
+            ParamMap::skewed(0.0, 10.0, 0.3).unwrap(),
+            ParamMap::skewed(0.0, 10.0, 3.0).unwrap(),
+        ] {
+            for i in 1..100 {
+                let x = i as f32 * 0.01;
+                let error = (map.unmap(map.map(x)) - x).abs();
+                assert!(error < 1e-3, "{:?} x: {}, error: {}", map, x, error);
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+/*!
+Plain-data snapshots of processor parameters, e.g. for presets in EEPROM or flash.
+
+Every struct can `capture` the parameters of a running processor and `apply` them
+again later. With the `serde` feature they derive `Serialize` and `Deserialize`,
+so they can be stored compactly with `postcard`.
+
+### Example
+
+```rust
+use embedded_audio_tools::params::AdsrParams;
+use embedded_audio_tools::AudioRateADSR;
+
+let mut adsr = AudioRateADSR::new(0.01, 0.1, 0.5, 0.2, 1.0, 48_000.0);
+
+// store the current sound
+let preset = AdsrParams::capture(&adsr);
+
+adsr.set_sustain(0.8);
+
+// and recall it later
+preset.apply(&mut adsr);
+assert_eq!(adsr.sustain(), 0.5);
+```
+*/
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::biquad::{Biquad, BiquadCoeffs};
+use crate::dynamics::GainComputer16;
+use crate::envelope::AudioRateADSR;
+
+/// Parameters of a `GainComputer16`, all as Q16.16 numbers
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressorParams {
+    pub threshold_db: i32,
+    pub ratio: i32,
+    pub makeup_db: i32,
+}
+
+impl CompressorParams {
+    pub fn capture(computer: &GainComputer16) -> CompressorParams {
+        CompressorParams {
+            threshold_db: computer.threshold(),
+            ratio: computer.ratio(),
+            makeup_db: computer.makeup(),
+        }
+    }
+
+    pub fn apply(&self, computer: &mut GainComputer16) {
+        computer.set_threshold(self.threshold_db);
+        computer.set_ratio(self.ratio);
+        computer.set_makeup(self.makeup_db);
+    }
+}
+
+/// Parameters of an `AudioRateADSR`, times in seconds
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdsrParams {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+    pub slope: f32,
+}
+
+impl AdsrParams {
+    pub fn capture(adsr: &AudioRateADSR) -> AdsrParams {
+        AdsrParams {
+            attack: adsr.attack(),
+            decay: adsr.decay(),
+            sustain: adsr.sustain(),
+            release: adsr.release(),
+            slope: adsr.slope(),
+        }
+    }
+
+    /// Doesn't touch the envelope state, so a running note keeps going
+    pub fn apply(&self, adsr: &mut AudioRateADSR) {
+        adsr.set_attack(self.attack);
+        adsr.set_decay(self.decay);
+        adsr.set_sustain(self.sustain);
+        adsr.set_release(self.release);
+        adsr.set_slope(self.slope);
+    }
+}
+
+/// Coefficients of a `Biquad`, normalized to `a0 = 1`
+///
+/// Storing the coefficients instead of the design parameters saves recalculating
+/// them on boot and works for any filter type.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadParams {
+    pub b0: f32,
+    pub b1: f32,
+    pub b2: f32,
+    pub a1: f32,
+    pub a2: f32,
+}
+
+impl BiquadParams {
+    pub fn capture<T>(biquad: &Biquad<T>) -> BiquadParams {
+        BiquadParams {
+            b0: biquad.coeffs.b0,
+            b1: biquad.coeffs.b1,
+            b2: biquad.coeffs.b2,
+            a1: biquad.coeffs.a1,
+            a2: biquad.coeffs.a2,
+        }
+    }
+
+    /// Keeps the filter state, so coefficients can be swapped while running
+    pub fn apply<T>(&self, biquad: &mut Biquad<T>) {
+        biquad.coeffs = BiquadCoeffs::from_raw(self.b0, self.b1, self.b2, self.a1, self.a2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::biquad::butterworth::Butterworth;
+
+    #[test]
+    fn compressor_round_trip() {
+        let mut computer = GainComputer16::new(-12 << 16, 4 << 16);
+        computer.set_makeup(6 << 16);
+        let params = CompressorParams::capture(&computer);
+
+        assert_eq!(params.threshold_db, -12 << 16);
+        assert_eq!(params.ratio, 4 << 16);
+        assert_eq!(params.makeup_db, 6 << 16);
+
+        computer.set_threshold(0);
+        computer.set_ratio(1 << 16);
+        computer.set_makeup(0);
+        params.apply(&mut computer);
+
+        assert_eq!(CompressorParams::capture(&computer), params);
+    }
+
+    #[test]
+    fn adsr_round_trip() {
+        let mut adsr = AudioRateADSR::new(0.01, 0.1, 0.5, 0.2, 2.0, 48_000.0);
+        let params = AdsrParams::capture(&adsr);
+
+        assert!((params.attack - 0.01).abs() < 1e-6);
+        assert!((params.decay - 0.1).abs() < 1e-6);
+        assert!((params.release - 0.2).abs() < 1e-6);
+        assert_eq!(params.sustain, 0.5);
+        assert_eq!(params.slope, 2.0);
+
+        let mut other = AudioRateADSR::new(1.0, 1.0, 1.0, 1.0, 1.0, 48_000.0);
+        params.apply(&mut other);
+
+        adsr.trigger_on();
+        other.trigger_on();
+
+        for _ in 0..1000 {
+            assert!((adsr.tick() - other.tick()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn biquad_round_trip() {
+        let mut biquad: Biquad<Butterworth> = Biquad::new(BiquadCoeffs::new());
+        biquad.coeffs.bell(1000.0, 0.7, 6.0, 48_000.0);
+
+        let params = BiquadParams::capture(&biquad);
+
+        let mut other: Biquad<Butterworth> = Biquad::new(BiquadCoeffs::new());
+        params.apply(&mut other);
+
+        for i in 0..100 {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            assert_eq!(biquad.process(x), other.process(x));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn postcard_round_trip() {
+        let params = AdsrParams {
+            attack: 0.01,
+            decay: 0.1,
+            sustain: 0.5,
+            release: 0.2,
+            slope: 1.0,
+        };
+
+        let mut buffer = [0_u8; 32];
+        let bytes = postcard::to_slice(&params, &mut buffer).unwrap();
+
+        assert_eq!(bytes.len(), 20);
+        assert_eq!(postcard::from_bytes::<AdsrParams>(bytes).unwrap(), params);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::block::dot;
+use crate::memory::{memory_slice::MemorySlice, Mutable};
+use PitchDetectorError::*;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Dips of the normalized difference below this count as periods
+const DEFAULT_THRESHOLD: f32 = 0.15;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+pub enum PitchDetectorError {
+    /// Frequencies have to be positive and `max_freq` above `min_freq`
+    InvalidRange,
+    /// The window needs to hold at least two periods of `min_freq`
+    WindowTooSmall,
+}
+
+/// Result of a `PitchDetector`
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Pitch {
+    /// In Hz, 0 if there was no periodicity at all
+    pub freq: f32,
+    /// Between 0 (noise, silence) and 1 (perfectly periodic)
+    pub confidence: f32,
+}
+
+/**
+Monophonic pitch detector for tuners and pitch following effects, based on the
+normalized difference function of the YIN algorithm.
+
+Collects a window of samples in a caller-provided buffer, which has to hold at least two
+periods of the lowest frequency, and analyzes it once it is full. The correlation runs on
+the `block` kernels.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice_mut, PitchDetector};
+
+// Guitar range at 48 kHz
+let mut window = [0.0; 2048];
+let mut detector = PitchDetector::new(from_slice_mut(&mut window), 48_000.0, 60.0, 1000.0).unwrap();
+
+// during audio callback
+for i in 0..2048 {
+    let sample = (core::f32::consts::TAU * 110.0 * i as f32 / 48_000.0).sin();
+
+    if let Some(pitch) = detector.tick(sample) {
+        assert!((pitch.freq - 110.0).abs() < 0.1);
+        assert!(pitch.confidence > 0.9);
+    }
+}
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct PitchDetector {
+    window: MemorySlice<Mutable>,
+    pos: usize,
+    sr: f32,
+    min_lag: usize,
+    max_lag: usize,
+    threshold: f32,
+}
+
+impl PitchDetector {
+    pub fn new(
+        window: MemorySlice<Mutable>,
+        sr: f32,
+        min_freq: f32,
+        max_freq: f32,
+    ) -> Result<PitchDetector, PitchDetectorError> {
+        if !(min_freq > 0.0 && max_freq > min_freq) {
+            return Err(InvalidRange);
+        }
+
+        // One more lag than needed, so the lowest frequency still has a right neighbour
+        let max_lag = (sr / min_freq).ceil() as usize + 1;
+        let min_lag = ((sr / max_freq) as usize).max(2);
+
+        if window.len() < 2 * max_lag {
+            return Err(WindowTooSmall);
+        }
+
+        Ok(PitchDetector {
+            window,
+            pos: 0,
+            sr,
+            min_lag,
+            max_lag,
+            threshold: DEFAULT_THRESHOLD,
+        })
+    }
+
+    /// Lower values reject more octave errors but need cleaner signals, clamps between
+    /// 0 and 1 (0.15 by default)
+    #[inline(always)]
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    /// Restarts collecting the window
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.pos = 0;
+    }
+
+    /// Returns the pitch of the window once it is full and starts collecting the next one
+    pub fn tick(&mut self, sample: f32) -> Option<Pitch> {
+        unsafe { self.window.assign_unchecked(self.pos, sample) };
+        self.pos += 1;
+
+        if self.pos < self.window.len() {
+            return None;
+        }
+
+        self.pos = 0;
+        Some(self.detect())
+    }
+
+    /// Analyzes the whole window as it is
+    pub fn detect(&self) -> Pitch {
+        let x = unsafe { &*self.window.as_slice() };
+        let len = x.len() - self.max_lag;
+
+        let head = &x[..len];
+        let energy = dot(head, head);
+
+        // Energy of the window shifted by the lag, starting at a lag of 0
+        let mut shifted = energy;
+        let mut sum = 0.0;
+
+        // Normalized difference at the lags before the current one
+        let mut prev = [1.0; 2];
+        let mut best: Option<(usize, [f32; 3])> = None;
+
+        for lag in 1..=self.max_lag {
+            shifted += x[len + lag - 1] * x[len + lag - 1] - x[lag - 1] * x[lag - 1];
+
+            let diff = (energy + shifted - 2.0 * dot(head, &x[lag..lag + len])).max(0.0);
+            sum += diff;
+
+            let normalized = if sum > 0.0 {
+                diff * lag as f32 / sum
+            } else {
+                1.0
+            };
+
+            // Decide on the lag before, now that both of its neighbours are known
+            let candidate = lag - 1;
+            let values = [prev[0], prev[1], normalized];
+
+            if candidate >= self.min_lag {
+                let is_dip = values[1] < self.threshold && values[1] <= values[2];
+
+                if is_dip {
+                    return self.pitch(candidate, values);
+                }
+
+                if best.is_none_or(|(_, best)| values[1] < best[1]) {
+                    best = Some((candidate, values));
+                }
+            }
+
+            prev = [prev[1], normalized];
+        }
+
+        match best {
+            Some((lag, values)) if values[1] < 1.0 => self.pitch(lag, values),
+            _ => Pitch {
+                freq: 0.0,
+                confidence: 0.0,
+            },
+        }
+    }
+
+    /// Refines `lag` with a parabola through the normalized differences around it
+    #[inline(always)]
+    fn pitch(&self, lag: usize, [left, center, right]: [f32; 3]) -> Pitch {
+        let curvature = left - 2.0 * center + right;
+
+        let offset = if curvature > 0.0 {
+            (0.5 * (left - right) / curvature).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+
+        Pitch {
+            freq: self.sr / (lag as f32 + offset),
+            confidence: (1.0 - center).clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::memory_slice::from_slice_mut;
+    use crate::oscillator::noise::WhiteNoise;
+    use core::f32::consts::TAU;
+
+    const SR: f32 = 48_000.0;
+    const LEN: usize = 2048;
+
+    fn detect(signal: impl Fn(usize) -> f32) -> Pitch {
+        let mut window = [0.0; LEN];
+        let mut detector =
+            PitchDetector::new(from_slice_mut(&mut window), SR, 60.0, 1500.0).unwrap();
+
+        (0..LEN).find_map(|i| detector.tick(signal(i))).unwrap()
+    }
+
+    #[test]
+    fn sines() {
+        for freq in [82.41, 110.0, 329.63, 440.0, 1318.5] {
+            let pitch = detect(|i| (TAU * freq * i as f32 / SR).sin());
+
+            assert!(
+                (pitch.freq - freq).abs() < freq * 0.001,
+                "{}: {:?}",
+                freq,
+                pitch
+            );
+            assert!(pitch.confidence > 0.95);
+        }
+    }
+
+    #[test]
+    fn harmonic_rich() {
+        // The harmonics of a sawtooth must not pull the result up an octave
+        let pitch = detect(|i| {
+            let phase = (110.0 * i as f32 / SR).fract();
+            2.0 * phase - 1.0
+        });
+
+        assert!((pitch.freq - 110.0).abs() < 0.5, "{:?}", pitch);
+    }
+
+    #[test]
+    fn silence_and_noise() {
+        assert_eq!(detect(|_| 0.0).confidence, 0.0);
+
+        let mut noise = WhiteNoise::default();
+        let mut window = [0.0; LEN];
+        let mut detector =
+            PitchDetector::new(from_slice_mut(&mut window), SR, 60.0, 1500.0).unwrap();
+
+        let pitch = (0..LEN)
+            .find_map(|_| detector.tick(noise.next_u32() as f32 / u32::MAX as f32 - 0.5))
+            .unwrap();
+        assert!(pitch.confidence < 0.5, "{:?}", pitch);
+    }
+
+    #[test]
+    fn invalid_setup() {
+        let mut window = [0.0; 1024];
+
+        assert_eq!(
+            PitchDetector::new(from_slice_mut(&mut window), SR, 60.0, 1500.0).unwrap_err(),
+            WindowTooSmall
+        );
+        assert_eq!(
+            PitchDetector::new(from_slice_mut(&mut window), SR, 500.0, 100.0).unwrap_err(),
+            InvalidRange
+        );
+        assert_eq!(
+            PitchDetector::new(from_slice_mut(&mut window), SR, 0.0, 100.0).unwrap_err(),
+            InvalidRange
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use crate::all_pass::AllPass;
+use crate::biquad::{smoothed::SmoothedBiquad, Biquad};
+use crate::block::{BiquadCascade, Fir};
+use crate::comb::Comb;
+use crate::metering::AWeighting;
+use crate::vca::Vca;
+use crate::waveshaper::{
+    AsymmetricSaturator, Bitcrusher, ChebyshevShaper, Exciter, Saturator, TableShaper,
+};
+
+/**
+Common interface of mono sample processors, so generic effect chains and test harnesses
+can hold different processors, either statically or as `&mut dyn Process`.
+
+Any `FnMut(f32) -> f32` is a processor as well, which covers the `clipping` functions
+and closures.
+
+### Example
+
+```rust
+use embedded_audio_tools::{clipping::hard_clip, filter::*, Process};
+
+let mut biquad: Biquad<Butterworth> = Biquad::new(BiquadCoeffs::new());
+biquad.coeffs.lowpass(1000.0, 1.0, 48_000.0);
+
+let mut clipper = hard_clip;
+let mut chain: [&mut dyn Process; 2] = [&mut biquad, &mut clipper];
+
+// during audio callback
+let mut block = [0.5; 32];
+for processor in chain.iter_mut() {
+    processor.process_block(&mut block);
+}
+# assert!(block.iter().all(|x| x.abs() <= 1.0));
+```
+*/
+pub trait Process {
+    fn process(&mut self, sample: f32) -> f32;
+
+    /// Processes `block` in place
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        for sample in block.iter_mut() {
+            *sample = self.process(*sample);
+        }
+    }
+}
+
+/**
+Runs `first` and feeds its output into `second`.
+
+### Example
+
+```rust
+use embedded_audio_tools::{clipping::hard_clip, Bypass, Chain, Parallel, Process};
+
+let gain = |x: f32| x * 4.0;
+let mut chain = Chain::new(gain, Parallel::new(hard_clip, |x: f32| x * 0.25, 0.5));
+
+assert_eq!(chain.process(0.5), 0.75);
+
+let mut bypass = Bypass::new(chain);
+bypass.set_bypassed(true);
+
+assert_eq!(bypass.process(0.5), 0.5);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A: Process, B: Process> Chain<A, B> {
+    pub const fn new(first: A, second: B) -> Chain<A, B> {
+        Chain { first, second }
+    }
+}
+
+impl<A: Process, B: Process> Process for Chain<A, B> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        self.second.process(self.first.process(sample))
+    }
+
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        self.first.process_block(block);
+        self.second.process_block(block);
+    }
+}
+
+/// Runs `first` and `second` on the same input and mixes their outputs, from only
+/// `first` at a mix of 0 to only `second` at 1
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Parallel<A, B> {
+    pub first: A,
+    pub second: B,
+    mix: f32,
+}
+
+impl<A: Process, B: Process> Parallel<A, B> {
+    /// `mix` gets clamped between 0 and 1
+    pub fn new(first: A, second: B, mix: f32) -> Parallel<A, B> {
+        Parallel {
+            first,
+            second,
+            mix: mix.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Clamps between 0 and 1
+    #[inline(always)]
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+}
+
+impl<A: Process, B: Process> Process for Parallel<A, B> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        let a = self.first.process(sample);
+        let b = self.second.process(sample);
+
+        a + (b - a) * self.mix
+    }
+}
+
+/// Passes the input through untouched while bypassed, the processor doesn't run then
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Bypass<P> {
+    pub processor: P,
+    bypassed: bool,
+}
+
+impl<P: Process> Bypass<P> {
+    /// Starts active
+    pub const fn new(processor: P) -> Bypass<P> {
+        Bypass {
+            processor,
+            bypassed: false,
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_bypassed(&mut self, bypassed: bool) {
+        self.bypassed = bypassed;
+    }
+
+    #[inline(always)]
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed
+    }
+}
+
+impl<P: Process> Process for Bypass<P> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        if self.bypassed {
+            sample
+        } else {
+            self.processor.process(sample)
+        }
+    }
+
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        if !self.bypassed {
+            self.processor.process_block(block);
+        }
+    }
+}
+
+impl<F: FnMut(f32) -> f32> Process for F {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        self(sample)
+    }
+}
+
+impl<T> Process for Biquad<T> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Biquad::process(self, sample)
+    }
+}
+
+impl Process for SmoothedBiquad {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        SmoothedBiquad::process(self, sample)
+    }
+
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        SmoothedBiquad::process_block(self, block);
+    }
+}
+
+impl<const N: usize> Process for BiquadCascade<N> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        BiquadCascade::process(self, sample)
+    }
+
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        BiquadCascade::process_block(self, block)
+    }
+}
+
+impl<const N: usize> Process for Fir<N> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Fir::process(self, sample)
+    }
+}
+
+impl Process for Comb {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        self.tick(sample)
+    }
+}
+
+impl Process for AllPass {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        self.tick(sample)
+    }
+}
+
+impl Process for Vca {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Vca::process(self, sample)
+    }
+
+    #[inline(always)]
+    fn process_block(&mut self, block: &mut [f32]) {
+        Vca::process_block(self, block)
+    }
+}
+
+impl Process for AWeighting {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        AWeighting::process(self, sample)
+    }
+}
+
+impl Process for AsymmetricSaturator {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        AsymmetricSaturator::process(self, sample)
+    }
+}
+
+impl Process for Bitcrusher {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Bitcrusher::process(self, sample)
+    }
+}
+
+impl<const N: usize> Process for ChebyshevShaper<N> {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        ChebyshevShaper::process(self, sample)
+    }
+}
+
+impl Process for Exciter {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Exciter::process(self, sample)
+    }
+}
+
+impl Process for Saturator {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        Saturator::process(self, sample)
+    }
+}
+
+impl Process for TableShaper {
+    #[inline(always)]
+    fn process(&mut self, sample: f32) -> f32 {
+        TableShaper::process(self, sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipping::{cubic_clip, hard_clip};
+    use crate::memory::memory_slice::from_slice_mut;
+    use crate::waveshaper::SaturationCurve;
+
+    #[test]
+    fn matches_inherent_methods() {
+        let mut buffer = [0.0_f32; 2];
+        let mut comb = Comb::new(from_slice_mut(&mut buffer[..]));
+        let mut block = [1.0, 0.0, 0.0, 0.0, 0.0];
+        comb.process_block(&mut block);
+
+        assert_eq!(block, [0.0, 0.0, 1.0, 0.0, 0.25]);
+
+        let mut saturator = Saturator::new(SaturationCurve::Cubic);
+        assert_eq!(Process::process(&mut saturator, 0.5), cubic_clip(0.5));
+    }
+
+    #[test]
+    fn combinators() {
+        let double = |x: f32| x * 2.0;
+        let offset = |x: f32| x + 1.0;
+
+        let mut chain = Chain::new(double, offset);
+        assert_eq!(chain.process(1.0), 3.0);
+
+        let mut block = [0.0, 1.0];
+        chain.process_block(&mut block);
+        assert_eq!(block, [1.0, 3.0]);
+
+        let mut parallel = Parallel::new(double, offset, 0.25);
+        assert_eq!(parallel.process(3.0), 5.5);
+
+        parallel.set_mix(5.0);
+        assert_eq!(parallel.process(3.0), 4.0);
+
+        let mut bypass = Bypass::new(Chain::new(parallel, double));
+        assert_eq!(bypass.process(3.0), 8.0);
+
+        bypass.set_bypassed(true);
+        assert!(bypass.is_bypassed());
+
+        let mut block = [1.0, 2.0];
+        bypass.process_block(&mut block);
+        assert_eq!(block, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn heterogeneous_processors() {
+        let mut gain = |x: f32| x * 4.0;
+        let mut clipper = hard_clip;
+        let mut shaper = ChebyshevShaper::new([0.5]);
+
+        let mut chain: [&mut dyn Process; 3] = [&mut gain, &mut clipper, &mut shaper];
+
+        let mut block = [-1.0, 0.1, 0.5];
+        for processor in chain.iter_mut() {
+            processor.process_block(&mut block);
+        }
+
+        assert_eq!(block, [-0.5, 0.2, 0.5]);
+    }
+}
@@ -0,0 +1,354 @@
+use core::f64::consts::PI;
+
+use crate::block::dot_ring;
+use crate::memory::{memory_slice::MemorySlice, Mutable};
+use crate::oscillator::lookup_tables::{const_cos, const_sin};
+use ResamplerError::*;
+
+/// Passband edge relative to the lower of both Nyquist frequencies
+const ROLLOFF: f64 = 0.9;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+pub enum ResamplerError {
+    ZeroRate,
+    /// The table needs room for at least two phases of `TAPS` coefficients
+    TableTooSmall,
+}
+
+/**
+Sample rate converter for any ratio of two integer rates, e.g. 44.1 kHz ↔ 48 kHz between
+a codec and USB audio, or for playing back assets recorded at a different rate.
+
+Interpolates with a Blackman windowed sinc of `TAPS` coefficients, an odd number fails to
+compile. Its polyphase table lives in a caller-provided buffer: more phases lower the
+interpolation error between them, 64 to 256 phases are a good start. The ratio is
+tracked exactly, so there is no drift over time, and the latency is `TAPS / 2` input
+samples.
+
+Equal rates pass the signal through untouched, apart from the latency.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice_mut, Resampler};
+
+// 128 phases of 16 taps
+let mut table = [0.0; 129 * 16];
+let mut resampler: Resampler<16> =
+    Resampler::new(from_slice_mut(&mut table), 44_100, 48_000).unwrap();
+
+// during audio callback
+let input = [0.0; 147];
+let mut output = [0.0; 160];
+
+let (consumed, produced) = resampler.process(&input, &mut output);
+assert_eq!((consumed, produced), (147, 160));
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Resampler<const TAPS: usize> {
+    /// `phases + 1` rows of `TAPS` coefficients for the oldest to the newest sample
+    table: MemorySlice<Mutable>,
+    phases: usize,
+
+    history: [f32; TAPS],
+    pos: usize,
+
+    /// Position of the next output after the `TAPS / 2`-th newest sample, in 1/`den`.
+    /// Reaching `den` means the next input sample is needed.
+    frac: u64,
+    step: u64,
+    den: u64,
+}
+
+impl<const TAPS: usize> Resampler<TAPS> {
+    /// Evaluated at compile time for every `TAPS` that gets constructed
+    const EVEN_TAPS: () = assert!(
+        TAPS >= 2 && TAPS.is_multiple_of(2),
+        "The number of taps has to be even"
+    );
+
+    pub fn new(
+        table: MemorySlice<Mutable>,
+        in_rate: u32,
+        out_rate: u32,
+    ) -> Result<Resampler<TAPS>, ResamplerError> {
+        let () = Self::EVEN_TAPS;
+
+        let mut resampler = Resampler {
+            table,
+            phases: 0,
+            history: [0.0; TAPS],
+            pos: 0,
+            frac: 0,
+            step: 1,
+            den: 1,
+        };
+
+        resampler.set_rates(in_rate, out_rate)?;
+        Ok(resampler)
+    }
+
+    /// Recalculates the table, which is too expensive for the audio callback
+    pub fn set_rates(&mut self, in_rate: u32, out_rate: u32) -> Result<(), ResamplerError> {
+        if in_rate == 0 || out_rate == 0 {
+            return Err(ZeroRate);
+        }
+
+        if self.table.len() / TAPS < 2 {
+            return Err(TableTooSmall);
+        }
+
+        let divisor = gcd(in_rate, out_rate);
+        self.step = (in_rate / divisor) as u64;
+        self.den = (out_rate / divisor) as u64;
+        self.frac = self.den;
+        self.phases = self.table.len() / TAPS - 1;
+
+        self.fill_table();
+        Ok(())
+    }
+
+    /// Clears the history, e.g. when playback jumps
+    #[inline]
+    pub fn reset(&mut self) {
+        self.history = [0.0; TAPS];
+        self.frac = self.den;
+    }
+
+    /// Converts as much of `input` into `output` as possible and returns the number of
+    /// consumed input and produced output samples. Input that wasn't consumed has to be
+    /// handed in again on the next call.
+    pub fn process(&mut self, input: &[f32], output: &mut [f32]) -> (usize, usize) {
+        let mut consumed = 0;
+        let mut produced = 0;
+
+        loop {
+            while self.frac < self.den {
+                let Some(out) = output.get_mut(produced) else {
+                    return (consumed, produced);
+                };
+
+                *out = self.interpolate();
+                produced += 1;
+                self.frac += self.step;
+            }
+
+            let Some(sample) = input.get(consumed) else {
+                return (consumed, produced);
+            };
+
+            self.push(*sample);
+            consumed += 1;
+            self.frac -= self.den;
+        }
+    }
+
+    #[inline(always)]
+    fn push(&mut self, sample: f32) {
+        self.pos = if self.pos + 1 == TAPS {
+            0
+        } else {
+            self.pos + 1
+        };
+        self.history[self.pos] = sample;
+    }
+
+    fn interpolate(&self) -> f32 {
+        let scaled = self.frac * self.phases as u64;
+        let row = (scaled / self.den) as usize;
+        let amount = (scaled % self.den) as f32 / self.den as f32;
+
+        let a = self.convolve(row);
+
+        if amount == 0.0 {
+            a
+        } else {
+            a + (self.convolve(row + 1) - a) * amount
+        }
+    }
+
+    #[inline(always)]
+    fn convolve(&self, row: usize) -> f32 {
+        let coeffs = unsafe { &(&*self.table.as_slice())[row * TAPS..(row + 1) * TAPS] };
+
+        // The oldest sample sits right after the newest one
+        let oldest = if self.pos + 1 == TAPS {
+            0
+        } else {
+            self.pos + 1
+        };
+
+        dot_ring(coeffs, &self.history, oldest)
+    }
+
+    fn fill_table(&mut self) {
+        let half = (TAPS / 2) as f64;
+
+        // Lowpass at the lower Nyquist frequency, relative to the input rate
+        let cutoff = if self.step == self.den {
+            1.0
+        } else if self.den < self.step {
+            ROLLOFF * self.den as f64 / self.step as f64
+        } else {
+            ROLLOFF
+        };
+
+        let table = unsafe { &mut *self.table.as_slice_mut() };
+
+        for (phase, row) in table
+            .chunks_exact_mut(TAPS)
+            .take(self.phases + 1)
+            .enumerate()
+        {
+            let offset = phase as f64 / self.phases as f64;
+            let mut sum = 0.0;
+
+            for (j, coeff) in row.iter_mut().enumerate() {
+                // Distance of the output to the j-th oldest sample
+                let x = half - 1.0 - j as f64 + offset;
+
+                let value = sinc(cutoff * x) * blackman(x, half);
+                *coeff = value as f32;
+                sum += value;
+            }
+
+            // Unity gain at DC for every phase
+            for coeff in row.iter_mut() {
+                *coeff = (*coeff as f64 / sum) as f32;
+            }
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        const_sin(PI * x) / (PI * x)
+    }
+}
+
+fn blackman(x: f64, half: f64) -> f64 {
+    if x.abs() >= half {
+        return 0.0;
+    }
+
+    let t = PI * x / half;
+    0.42 + 0.5 * const_cos(t) + 0.08 * const_cos(2.0 * t)
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_access::from_slice_mut;
+    use core::f64::consts::TAU;
+
+    const LEN: usize = 4800;
+
+    /// Returns the number of produced samples
+    fn convert(in_rate: u32, out_rate: u32, input: &[f32], output: &mut [f32]) -> usize {
+        let mut table = [0.0; 129 * 16];
+        let mut resampler: Resampler<16> =
+            Resampler::new(from_slice_mut(&mut table), in_rate, out_rate).unwrap();
+
+        let mut produced = 0;
+
+        // Odd chunk sizes on purpose
+        for chunk in input.chunks(37) {
+            let (consumed, n) = resampler.process(chunk, &mut output[produced..]);
+            assert_eq!(consumed, chunk.len());
+            produced += n;
+        }
+
+        produced
+    }
+
+    fn sine(freq: f64, sr: u32) -> [f32; LEN] {
+        core::array::from_fn(|i| (TAU * freq * i as f64 / sr as f64).sin() as f32)
+    }
+
+    #[test]
+    fn invalid_setup() {
+        let mut table = [0.0; 31];
+
+        assert_eq!(
+            Resampler::<16>::new(from_slice_mut(&mut table), 44_100, 48_000).unwrap_err(),
+            TableTooSmall
+        );
+        assert_eq!(
+            Resampler::<8>::new(from_slice_mut(&mut table), 0, 48_000).unwrap_err(),
+            ZeroRate
+        );
+    }
+
+    #[test]
+    fn equal_rates_pass_through() {
+        let input = sine(1000.0, 48_000);
+        let mut output = [0.0; LEN];
+
+        assert_eq!(convert(48_000, 48_000, &input, &mut output), LEN);
+
+        // Latency of TAPS / 2 samples
+        for (out, sample) in output[8..].iter().zip(&input) {
+            assert!((out - sample).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn exact_ratio() {
+        let input = [0.0; LEN];
+        let mut output = [0.0; 2 * LEN];
+
+        for (in_rate, out_rate) in [(44_100, 48_000), (48_000, 44_100), (48_000, 16_000)] {
+            let len = in_rate as usize / 10;
+            let produced = convert(in_rate, out_rate, &input[..len], &mut output);
+
+            assert_eq!(produced, out_rate as usize / 10);
+        }
+    }
+
+    #[test]
+    fn sine_keeps_frequency_and_level() {
+        let mut output = [0.0; 2 * LEN];
+
+        for (in_rate, out_rate) in [(44_100, 48_000), (48_000, 44_100), (44_100, 47_999)] {
+            let input = sine(1000.0, in_rate);
+            let produced = convert(in_rate, out_rate, &input, &mut output);
+
+            // Compare against an ideal sine, delayed by the latency
+            let latency = 8.0 / in_rate as f64;
+
+            for (i, out) in output[..produced].iter().enumerate().skip(32) {
+                let t = i as f64 / out_rate as f64 - latency;
+                let expected = (TAU * 1000.0 * t).sin() as f32;
+
+                assert!((out - expected).abs() < 0.01, "{} Hz at {}", out_rate, i);
+            }
+        }
+    }
+
+    #[test]
+    fn downsampling_removes_aliases() {
+        // 20 kHz would alias to 4 kHz at 16 kHz
+        let input = sine(20_000.0, 48_000);
+        let mut output = [0.0; LEN];
+
+        let produced = convert(48_000, 16_000, &input, &mut output);
+        let peak = output[32..produced]
+            .iter()
+            .fold(0.0_f32, |peak, x| peak.max(x.abs()));
+
+        assert!(peak < 0.01, "{}", peak);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::float::hermite_unchecked;
+use crate::memory::{memory_slice::MemorySlice, NonMutable};
+
+/// What a `SamplePlayer` does with its gate
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PlayMode {
+    /// Plays from start to end once, ignoring `trigger_off`, e.g. for drums
+    OneShot,
+    /// Plays from start to end once, `trigger_off` stops right away
+    Gated,
+    /// Repeats the loop while the gate is on, then plays on to the end, like a sustain loop
+    Loop,
+}
+
+/**
+Sample playback at any rate with Hermite interpolation, start, end and loop points, and a
+crossfade across the loop seam, the core of a rompler or drum machine voice.
+
+`trigger_on` and `trigger_off` take the same gate as `AudioRateADSR`, so player and
+envelope run in lockstep.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice, tuning, PlayMode, SamplePlayer};
+
+static SAMPLE: [f32; 8] = [0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5];
+
+let mut player = SamplePlayer::new(from_slice(&SAMPLE));
+player.set_mode(PlayMode::Loop);
+
+// A fifth up
+player.set_rate(tuning::semitones_to_ratio(7.0));
+
+// on note on
+player.trigger_on();
+
+// during audio callback
+let sample = player.tick();
+# assert_eq!(sample, 0.0);
+# assert!(player.is_playing());
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct SamplePlayer {
+    sample: MemorySlice<NonMutable>,
+    index: usize,
+    frac: f32,
+    rate: f32,
+
+    start: usize,
+    end: usize,
+    loop_start: usize,
+    loop_end: usize,
+    crossfade: usize,
+
+    mode: PlayMode,
+    playing: bool,
+    gate: bool,
+}
+
+impl SamplePlayer {
+    /// Plays the whole sample once at its original rate, the loop covers the whole sample
+    pub fn new(sample: MemorySlice<NonMutable>) -> SamplePlayer {
+        let len = sample.len();
+
+        SamplePlayer {
+            sample,
+            index: 0,
+            frac: 0.0,
+            rate: 1.0,
+            start: 0,
+            end: len,
+            loop_start: 0,
+            loop_end: len,
+            crossfade: 0,
+            mode: PlayMode::OneShot,
+            playing: false,
+            gate: false,
+        }
+    }
+
+    /// Playback speed, 2.0 is an octave up. Negative rates are treated as 0.
+    /// A sample recorded at another rate plays at its pitch with `sample_sr / sr`.
+    #[inline(always)]
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Clamps to the sample, the loop and crossfade get clamped to the new range. An empty
+    /// range stops playback.
+    pub fn set_range(&mut self, start: usize, end: usize) {
+        self.end = end.min(self.sample.len());
+        self.start = start.min(self.end);
+        self.set_loop(self.loop_start, self.loop_end);
+
+        if self.start == self.end {
+            self.playing = false;
+        }
+    }
+
+    /// Clamps to the range set by `set_range`, the crossfade gets clamped to the new loop
+    pub fn set_loop(&mut self, loop_start: usize, loop_end: usize) {
+        self.loop_end = loop_end.clamp(self.start, self.end);
+        self.loop_start = loop_start.clamp(self.start, self.loop_end);
+        self.set_crossfade(self.crossfade);
+    }
+
+    /// Length of the fade from the end of the loop into the audio before its start, in
+    /// samples. Clamps to the loop length and the audio available before the loop.
+    #[inline(always)]
+    pub fn set_crossfade(&mut self, samples: usize) {
+        self.crossfade = samples
+            .min(self.loop_start - self.start)
+            .min(self.loop_end - self.loop_start);
+    }
+
+    #[inline(always)]
+    pub fn set_mode(&mut self, mode: PlayMode) {
+        self.mode = mode;
+    }
+
+    /// Swaps the sample, the range and loop get reset to the whole sample
+    pub fn change_sample(&mut self, sample: MemorySlice<NonMutable>) {
+        self.sample = sample;
+        self.playing = false;
+        self.start = 0;
+        self.end = self.sample.len();
+        self.set_loop(0, self.end);
+    }
+
+    /// Restarts playback from the start point
+    #[inline(always)]
+    pub fn trigger_on(&mut self) {
+        self.index = self.start;
+        self.frac = 0.0;
+        self.playing = self.start < self.end;
+        self.gate = true;
+    }
+
+    #[inline(always)]
+    pub fn trigger_off(&mut self) {
+        self.gate = false;
+
+        if self.mode == PlayMode::Gated {
+            self.playing = false;
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Playback position in samples
+    #[inline(always)]
+    pub fn position(&self) -> f32 {
+        self.index as f32 + self.frac
+    }
+
+    /// Returns 0 when not playing
+    pub fn tick(&mut self) -> f32 {
+        // The range can shrink below the playback position in between
+        if self.playing && self.index >= self.end {
+            self.playing = false;
+        }
+
+        if !self.playing {
+            return 0.0;
+        }
+
+        let looping = self.mode == PlayMode::Loop && self.gate && self.loop_start < self.loop_end;
+        let fade_start = self.loop_end - self.crossfade;
+
+        let out = if looping && self.index >= fade_start && self.index < self.loop_end {
+            // Fade over into the audio leading into the loop start
+            let amount = (self.position() - fade_start as f32) / self.crossfade as f32;
+            let a = self.read(self.index);
+            let b = self.read(self.index - (self.loop_end - self.loop_start));
+
+            a + (b - a) * amount
+        } else {
+            self.read(self.index)
+        };
+
+        self.advance(looping);
+        out
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, looping: bool) {
+        self.frac += self.rate;
+
+        let whole = self.frac as usize;
+        self.index += whole;
+        self.frac -= whole as f32;
+
+        if looping && self.index >= self.loop_end {
+            self.index = self.loop_start
+                + (self.index - self.loop_start) % (self.loop_end - self.loop_start);
+        } else if self.index >= self.end {
+            self.playing = false;
+        }
+    }
+
+    /// Interpolated sample at `index` plus the current fraction, the neighbours are
+    /// clamped to the range
+    #[inline(always)]
+    fn read(&self, index: usize) -> f32 {
+        let last = self.end - 1;
+        let at = |i: usize| unsafe { self.sample.get_unchecked(i.clamp(self.start, last)) };
+
+        hermite_unchecked(
+            at(index.saturating_sub(1)),
+            at(index),
+            at(index + 1),
+            at(index + 2),
+            self.frac,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::memory_slice::from_slice;
+
+    const RAMP: [f32; 16] = [
+        0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
+    ];
+
+    #[test]
+    fn one_shot() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        assert_eq!(player.tick(), 0.0);
+
+        player.trigger_on();
+        player.trigger_off();
+
+        for expected in RAMP {
+            assert!(player.is_playing());
+            assert_eq!(player.tick(), expected);
+        }
+
+        assert!(!player.is_playing());
+        assert_eq!(player.tick(), 0.0);
+    }
+
+    #[test]
+    fn rates() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        player.set_range(2, 10);
+
+        // Hermite reproduces a ramp exactly, apart from the clamped neighbour at the start
+        player.set_rate(0.5);
+        player.trigger_on();
+        let half: [f32; 5] = core::array::from_fn(|_| player.tick());
+        assert_eq!(half[0], 2.0);
+        assert_eq!(&half[2..], &[3.0, 3.5, 4.0]);
+
+        player.set_rate(3.0);
+        player.trigger_on();
+        let triple: [f32; 4] = core::array::from_fn(|_| player.tick());
+        assert_eq!(triple, [2.0, 5.0, 8.0, 0.0]);
+    }
+
+    #[test]
+    fn sustain_loop() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        player.set_mode(PlayMode::Loop);
+        player.set_loop(4, 8);
+        player.trigger_on();
+
+        let out: [f32; 12] = core::array::from_fn(|_| player.tick());
+        assert_eq!(
+            out,
+            [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 4.0, 5.0, 6.0, 7.0]
+        );
+
+        // Plays out to the end after the release
+        player.trigger_off();
+        let out: [f32; 9] = core::array::from_fn(|_| player.tick());
+        assert_eq!(out, [4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0]);
+    }
+
+    #[test]
+    fn loop_crossfade() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        player.set_mode(PlayMode::Loop);
+        player.set_loop(8, 12);
+        player.set_crossfade(4);
+        player.trigger_on();
+
+        // The loop fades into the ramp leading up to its start, which meets the start
+        // value right at the seam
+        let out: [f32; 16] = core::array::from_fn(|_| player.tick());
+        assert_eq!(&out[..8], &RAMP[..8]);
+        assert!(out[8..].iter().all(|x| *x == 8.0));
+    }
+
+    #[test]
+    fn gated_and_clamping() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        player.set_mode(PlayMode::Gated);
+        player.set_range(4, 100);
+        player.set_loop(0, 100);
+        player.set_crossfade(8);
+
+        assert_eq!((player.start, player.end), (4, 16));
+        assert_eq!((player.loop_start, player.loop_end), (4, 16));
+        assert_eq!(player.crossfade, 0);
+
+        player.trigger_on();
+        assert_eq!(player.tick(), 4.0);
+
+        player.trigger_off();
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn range_change_while_playing() {
+        let mut player = SamplePlayer::new(from_slice(&RAMP));
+        player.trigger_on();
+
+        for expected in [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            assert_eq!(player.tick(), expected);
+        }
+
+        // Ends right away once the position lies behind the new end
+        player.set_range(2, 6);
+        assert!(player.is_playing());
+        assert_eq!(player.tick(), 0.0);
+        assert!(!player.is_playing());
+
+        for (start, end) in [(5, 5), (0, 0)] {
+            player.set_range(0, 16);
+            player.trigger_on();
+            player.tick();
+
+            player.set_range(start, end);
+            assert!(!player.is_playing());
+            assert_eq!(player.tick(), 0.0);
+
+            player.trigger_on();
+            assert!(!player.is_playing());
+            assert_eq!(player.tick(), 0.0);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// Ramp shape of a `SmoothedParam`
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Smoothing {
+    /// Exponential approach, `time_ms` is the time constant (63% of the way)
+    OnePole,
+    /// Constant slope, reaches the target after exactly `time_ms`
+    Linear,
+}
+
+/// Values closer than this to the target snap onto it, so the one pole ramp ends.
+/// Ramps which stall further away because of the float resolution snap as well.
+const SNAP_DISTANCE: f32 = 1e-6;
+
+/**
+Parameter which glides towards its target instead of jumping, to avoid zipper noise
+when values change from a UI thread or a pot.
+
+### Example
+
+```rust
+use embedded_audio_tools::{SmoothedParam, Smoothing};
+
+let mut cutoff = SmoothedParam::new(1000.0, 10.0, 48_000.0, Smoothing::Linear);
+cutoff.set_target(2000.0);
+
+// during audio callback
+for _ in 0..480 {
+    let value = cutoff.next();
+    # assert!(value > 1000.0 && value <= 2000.0);
+}
+
+assert_eq!(cutoff.value(), 2000.0);
+assert!(!cutoff.is_smoothing());
+```
+*/
+#[cfg_attr(feature = "ffi", repr(C))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct SmoothedParam {
+    current: f32,
+    target: f32,
+    mode: Smoothing,
+    time_ms: f32,
+    sr: f32,
+    coeff: f32,
+    step: f32,
+    remaining: u32,
+}
+
+impl SmoothedParam {
+    pub fn new(value: f32, time_ms: f32, sr: f32, mode: Smoothing) -> SmoothedParam {
+        let mut param = SmoothedParam {
+            current: value,
+            target: value,
+            mode,
+            time_ms: 0.0,
+            sr,
+            coeff: 0.0,
+            step: 0.0,
+            remaining: 0,
+        };
+
+        param.set_time(time_ms);
+        param
+    }
+
+    /// Starts a new ramp from the current value towards `target`
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+
+        if self.mode == Smoothing::Linear {
+            self.remaining = ((self.time_ms * 0.001 * self.sr).round() as u32).max(1);
+            self.step = (target - self.current) / self.remaining as f32;
+        }
+    }
+
+    /// Jumps to `value` without smoothing, e.g. when a voice starts
+    #[inline(always)]
+    pub fn set_immediate(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    /// Negative times are treated as 0, which disables smoothing.
+    /// Takes effect with the next `set_target`
+    pub fn set_time(&mut self, time_ms: f32) {
+        self.time_ms = time_ms.max(0.0);
+        self.update_coeff();
+    }
+
+    pub fn set_mode(&mut self, mode: Smoothing) {
+        self.mode = mode;
+        self.set_target(self.target);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_coeff();
+    }
+
+    /// Advances the ramp by one sample and returns the new value
+    #[inline(always)]
+    pub fn next(&mut self) -> f32 {
+        match self.mode {
+            Smoothing::OnePole => {
+                let next = self.target + (self.current - self.target) * self.coeff;
+
+                // Close to large targets the update can round back onto the old value
+                let stalled = next == self.current;
+
+                self.current = if stalled || (next - self.target).abs() < SNAP_DISTANCE {
+                    self.target
+                } else {
+                    next
+                };
+            }
+            Smoothing::Linear => {
+                if self.remaining > 1 {
+                    self.current += self.step;
+                    self.remaining -= 1;
+                } else {
+                    self.current = self.target;
+                    self.remaining = 0;
+                }
+            }
+        }
+
+        self.current
+    }
+
+    /// Advances the ramp by `samples`, e.g. when the value is only needed once per block
+    pub fn skip(&mut self, samples: usize) -> f32 {
+        for _ in 0..samples {
+            if !self.is_smoothing() {
+                break;
+            }
+
+            self.next();
+        }
+
+        self.current
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> f32 {
+        self.current
+    }
+
+    #[inline(always)]
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    #[inline(always)]
+    pub fn is_smoothing(&self) -> bool {
+        self.current != self.target
+    }
+
+    #[inline(always)]
+    fn update_coeff(&mut self) {
+        let samples = self.time_ms * 0.001 * self.sr;
+
+        self.coeff = if samples > 0.0 {
+            (-1.0 / samples).exp()
+        } else {
+            0.0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 1000.0;
+
+    #[test]
+    fn linear_ramp() {
+        let mut param = SmoothedParam::new(0.0, 4.0, SR, Smoothing::Linear);
+        param.set_target(1.0);
+
+        assert_eq!(param.next(), 0.25);
+        assert_eq!(param.next(), 0.5);
+        assert_eq!(param.next(), 0.75);
+        assert_eq!(param.next(), 1.0);
+        assert_eq!(param.next(), 1.0);
+        assert!(!param.is_smoothing());
+    }
+
+    #[test]
+    fn one_pole_time_constant() {
+        let mut param = SmoothedParam::new(0.0, 10.0, SR, Smoothing::OnePole);
+        param.set_target(1.0);
+
+        let value = param.skip(10);
+        assert!((value - 0.632).abs() < 0.01);
+
+        param.skip(1000);
+        assert_eq!(param.value(), 1.0);
+        assert!(!param.is_smoothing());
+    }
+
+    #[test]
+    fn one_pole_settles_at_audio_rate() {
+        for (start, target) in [(0.0, 1.0), (0.0, 100.0), (1000.0, 2000.0), (2000.0, -3.0)] {
+            let mut param = SmoothedParam::new(start, 10.0, 48_000.0, Smoothing::OnePole);
+            param.set_target(target);
+
+            param.skip(48_000);
+            assert_eq!(param.value(), target);
+            assert!(!param.is_smoothing());
+        }
+    }
+
+    #[test]
+    fn zero_time_jumps() {
+        let mut param = SmoothedParam::new(0.0, 0.0, SR, Smoothing::OnePole);
+        param.set_target(1.0);
+        assert_eq!(param.next(), 1.0);
+
+        param.set_mode(Smoothing::Linear);
+        param.set_target(-1.0);
+        assert_eq!(param.next(), -1.0);
+    }
+
+    #[test]
+    fn immediate() {
+        let mut param = SmoothedParam::new(0.0, 10.0, SR, Smoothing::Linear);
+        param.set_target(1.0);
+        param.set_immediate(0.5);
+
+        assert_eq!(param.next(), 0.5);
+        assert_eq!(param.target(), 0.5);
+    }
+}
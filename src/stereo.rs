@@ -1,8 +1,11 @@
+use crate::float::{crossfade_gains, AdditionalF32Ext, CrossfadeCurve};
+use core::f32::consts::FRAC_PI_4;
 use PanningError::*;
 
 #[allow(unused_imports)]
-use micromath::F32Ext;
+use crate::float::F32Ext;
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 pub enum PanningError {
     TooLeft,
@@ -34,6 +37,104 @@ pub fn mono_pan(amount: f32, sample: f32) -> Result<(f32, f32), PanningError> {
     Ok(mono_pan_unchecked(check_pan_error(amount)?, sample))
 }
 
+/// Pans `sample` onto a ring of `N` evenly spaced speakers, e.g. quad outputs
+///
+/// `azimuth` is a normalized angle wrapping at 1, where speaker `k` sits at `k / N`.
+/// In between two neighbouring speakers a constant power law is applied.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::stereo::pan_n;
+///
+/// // front left, front right, rear right, rear left
+/// assert_eq!(pan_n::<4>(0.25, 1.0), [0.0, 1.0, 0.0, 0.0]);
+///
+/// let gains = pan_n::<4>(0.875, 1.0);
+/// assert!((gains[3] - gains[0]).abs() < 1e-4 && gains[1] == 0.0);
+/// ```
+pub fn pan_n<const N: usize>(azimuth: f32, sample: f32) -> [f32; N] {
+    let mut out = [0.0; N];
+    match N {
+        0 => return out,
+        1 => {
+            out[0] = sample;
+            return out;
+        }
+        _ => {}
+    }
+
+    let position = (azimuth - azimuth.floor()) * N as f32;
+    let index = (position as usize).min(N - 1);
+    let gains = crossfade_gains(position - index as f32, CrossfadeCurve::EqualPower);
+
+    out[index] += sample * gains.0;
+    out[(index + 1) % N] += sample * gains.1;
+    out
+}
+
+/// Attenuates one channel of a stereo signal, `amount = -1` mutes the right and
+/// `amount = 1` the left channel
+///
+/// Unlike `stereo_pan`, the center stays at unity gain, which is what stereo stems need.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::stereo::balance;
+///
+/// assert_eq!(balance(0.0, (0.5, 0.25)), Ok((0.5, 0.25)));
+/// assert_eq!(balance(0.5, (0.5, 0.25)), Ok((0.25, 0.25)));
+/// assert!(balance(2.0, (0.5, 0.25)).is_err());
+/// ```
+pub fn balance(amount: f32, samples: (f32, f32)) -> Result<(f32, f32), PanningError> {
+    Ok(balance_unchecked(check_pan_error(amount)?, samples))
+}
+
+/// Rotates an already stereo signal, `amount = -1` moves the center fully left and
+/// `amount = 1` fully right
+///
+/// Unlike panning, the rotation keeps the total energy of both channels.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::stereo::rotate;
+///
+/// let (left, right) = rotate(1.0, (1.0, 1.0)).unwrap();
+/// assert!(left.abs() < 1e-4 && (right - 2.0_f32.sqrt()).abs() < 1e-4);
+/// assert!(rotate(-2.0, (1.0, 1.0)).is_err());
+/// ```
+pub fn rotate(amount: f32, samples: (f32, f32)) -> Result<(f32, f32), PanningError> {
+    Ok(rotate_unchecked(check_pan_error(amount)?, samples))
+}
+
+/// Fades between `a` at `amount = -1` and `b` at `amount = 1`
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::float::CrossfadeCurve;
+/// use embedded_audio_tools::stereo::crossfade;
+///
+/// assert_eq!(crossfade(0.0, CrossfadeCurve::Linear, 1.0, 0.0), Ok(0.5));
+/// assert!(crossfade(2.0, CrossfadeCurve::Linear, 1.0, 0.0).is_err());
+/// ```
+pub fn crossfade(amount: f32, curve: CrossfadeCurve, a: f32, b: f32) -> Result<f32, PanningError> {
+    Ok(crossfade_unchecked(check_pan_error(amount)?, curve, a, b))
+}
+
+/// Fades between the stereo signals `a` at `amount = -1` and `b` at `amount = 1`
+pub fn crossfade_stereo(
+    amount: f32,
+    curve: CrossfadeCurve,
+    a: (f32, f32),
+    b: (f32, f32),
+) -> Result<(f32, f32), PanningError> {
+    Ok(crossfade_stereo_unchecked(
+        check_pan_error(amount)?,
+        curve,
+        a,
+        b,
+    ))
+}
+
 // =========
 // UNCHECKED
 // =========
@@ -50,12 +151,50 @@ pub fn mono_pan_unchecked(amount: f32, sample: f32) -> (f32, f32) {
     (sample * pan.0, sample * pan.1)
 }
 
+#[inline(always)]
+pub fn balance_unchecked(amount: f32, samples: (f32, f32)) -> (f32, f32) {
+    if amount > 0.0 {
+        (samples.0 * (1.0 - amount), samples.1)
+    } else {
+        (samples.0, samples.1 * (1.0 + amount))
+    }
+}
+
+#[inline(always)]
+pub fn rotate_unchecked(amount: f32, samples: (f32, f32)) -> (f32, f32) {
+    let (sin, cos) = (amount * FRAC_PI_4).sincos();
+
+    (
+        samples.0 * cos - samples.1 * sin,
+        samples.0 * sin + samples.1 * cos,
+    )
+}
+
 #[inline(always)]
 pub fn crossfade_correlated_unchecked(amount: f32, sample: (f32, f32)) -> f32 {
     let pan = equal_amplitude_pan_unchecked(amount);
     sample.0 * pan.0 + sample.1 * pan.1
 }
 
+/// Fades between `a` at `amount = -1` and `b` at `amount = 1`
+#[inline(always)]
+pub fn crossfade_unchecked(amount: f32, curve: CrossfadeCurve, a: f32, b: f32) -> f32 {
+    let gains = crossfade_gains((amount + 1.0) * 0.5, curve);
+    a * gains.0 + b * gains.1
+}
+
+/// Fades between the stereo signals `a` at `amount = -1` and `b` at `amount = 1`
+#[inline(always)]
+pub fn crossfade_stereo_unchecked(
+    amount: f32,
+    curve: CrossfadeCurve,
+    a: (f32, f32),
+    b: (f32, f32),
+) -> (f32, f32) {
+    let gains = crossfade_gains((amount + 1.0) * 0.5, curve);
+    (a.0 * gains.0 + b.0 * gains.1, a.1 * gains.0 + b.1 * gains.1)
+}
+
 #[inline(always)]
 fn equal_amplitude_pan_unchecked(amount: f32) -> (f32, f32) {
     ((1.0 - amount) * 0.5, (1.0 + amount) * 0.5)
@@ -85,6 +224,119 @@ mod tests {
         assert_eq!(equal_power_pan_unchecked(1.0), (0.0, 1.0));
     }
 
+    #[test]
+    fn stereo_crossfade() {
+        let a = (1.0, -1.0);
+        let b = (0.5, 0.25);
+
+        assert_eq!(
+            crossfade_stereo_unchecked(-1.0, CrossfadeCurve::Linear, a, b),
+            a
+        );
+        assert_eq!(
+            crossfade_stereo_unchecked(1.0, CrossfadeCurve::Linear, a, b),
+            b
+        );
+        assert_eq!(
+            crossfade_stereo_unchecked(0.0, CrossfadeCurve::Linear, a, b),
+            (0.75, -0.375)
+        );
+    }
+
+    #[test]
+    fn multichannel_panning() {
+        assert_eq!(pan_n::<0>(0.3, 1.0), [0.0_f32; 0]);
+        assert_eq!(pan_n::<1>(0.3, 0.5), [0.5]);
+
+        assert_eq!(pan_n::<4>(0.0, 1.0), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(pan_n::<4>(1.5, 0.5), [0.0, 0.0, 0.5, 0.0]);
+        assert_eq!(pan_n::<4>(-0.25, 1.0), [0.0, 0.0, 0.0, 1.0]);
+
+        for i in 0..100 {
+            let gains = pan_n::<6>(i as f32 / 100.0, 1.0);
+            let power: f32 = gains.iter().map(|g| g * g).sum();
+            assert!((power - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn balancing() {
+        let samples = (0.8, -0.4);
+
+        assert_eq!(balance_unchecked(0.0, samples), samples);
+        assert_eq!(balance_unchecked(-1.0, samples), (0.8, 0.0));
+        assert_eq!(balance_unchecked(1.0, samples), (0.0, -0.4));
+        assert_eq!(balance_unchecked(-0.5, samples), (0.8, -0.2));
+
+        assert_eq!(balance(-5.0, samples), Err(TooLeft));
+        assert_eq!(balance(5.0, samples), Err(TooRight));
+    }
+
+    #[test]
+    fn rotation() {
+        let samples = (0.8, -0.3);
+        let energy = samples.0 * samples.0 + samples.1 * samples.1;
+
+        for i in -10..=10 {
+            let (left, right) = rotate_unchecked(i as f32 / 10.0, samples);
+            assert!((left * left + right * right - energy).abs() < 1e-3);
+        }
+
+        let (left, right) = rotate_unchecked(0.0, samples);
+        assert!((left - samples.0).abs() < 1e-4 && (right - samples.1).abs() < 1e-4);
+
+        let (left, right) = rotate_unchecked(-1.0, (1.0, 1.0));
+        assert!((left - 2.0_f32.sqrt()).abs() < 1e-4 && right.abs() < 1e-4);
+
+        assert_eq!(rotate(-5.0, samples), Err(TooLeft));
+        assert_eq!(rotate(5.0, samples), Err(TooRight));
+    }
+
+    #[test]
+    fn mono_crossfade() {
+        assert_eq!(
+            crossfade_unchecked(-1.0, CrossfadeCurve::Linear, 1.0, 0.5),
+            1.0
+        );
+        assert_eq!(
+            crossfade_unchecked(1.0, CrossfadeCurve::Linear, 1.0, 0.5),
+            0.5
+        );
+        assert_eq!(
+            crossfade_unchecked(0.0, CrossfadeCurve::Linear, 1.0, 0.5),
+            0.75
+        );
+
+        // constant power for uncorrelated signals in the middle
+        let gain = crossfade_unchecked(0.0, CrossfadeCurve::EqualPower, 1.0, 0.0);
+        assert!((gain * gain - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn crossfade_error() {
+        let a = (1.0, 1.0);
+        let b = (0.0, 0.0);
+
+        assert_eq!(
+            crossfade(-5.0, CrossfadeCurve::Linear, 1.0, 0.0),
+            Err(TooLeft)
+        );
+        assert_eq!(
+            crossfade(5.0, CrossfadeCurve::Linear, 1.0, 0.0),
+            Err(TooRight)
+        );
+
+        assert_eq!(
+            crossfade_stereo(-5.0, CrossfadeCurve::Linear, a, b),
+            Err(TooLeft)
+        );
+        assert_eq!(
+            crossfade_stereo(5.0, CrossfadeCurve::Linear, a, b),
+            Err(TooRight)
+        );
+        assert_eq!(crossfade_stereo(1.0, CrossfadeCurve::Linear, a, b), Ok(b));
+    }
+
     #[test]
     fn pan_error() {
         assert_eq!(mono_pan(-5.0, 1.0), Err(TooLeft));
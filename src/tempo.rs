@@ -0,0 +1,303 @@
+/// Tempos below this are treated as this
+pub const MIN_BPM: f32 = 1.0;
+
+/// Converts a tempo to the length of a beat (quarter note) in samples
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tempo::bpm_to_samples_per_beat;
+///
+/// assert_eq!(bpm_to_samples_per_beat(120.0, 48_000.0), 24_000.0);
+/// ```
+#[inline(always)]
+pub fn bpm_to_samples_per_beat(bpm: f32, sr: f32) -> f32 {
+    60.0 * sr / bpm.max(MIN_BPM)
+}
+
+/// Converts the length of a beat (quarter note) in samples to a tempo, e.g. for tap tempo
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tempo::samples_per_beat_to_bpm;
+///
+/// assert_eq!(samples_per_beat_to_bpm(24_000.0, 48_000.0), 120.0);
+/// ```
+#[inline(always)]
+pub fn samples_per_beat_to_bpm(samples: f32, sr: f32) -> f32 {
+    60.0 * sr / samples
+}
+
+/// Delay of a swung step in samples. Every second step of a pair is pushed back, `swing`
+/// is the share of the pair the first step takes: 0.5 plays straight, 2/3 is a triplet
+/// shuffle. Clamps between 0.5 and 0.75.
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tempo::swing_offset;
+///
+/// // 16ths at 120 BPM and 48 kHz are 6000 samples long
+/// assert_eq!(swing_offset(0, 6000.0, 0.6), 0.0);
+/// assert_eq!(swing_offset(1, 6000.0, 0.75), 3000.0);
+/// ```
+#[inline(always)]
+pub fn swing_offset(step: u32, step_samples: f32, swing: f32) -> f32 {
+    if step.is_multiple_of(2) {
+        0.0
+    } else {
+        (swing.clamp(0.5, 0.75) - 0.5) * 2.0 * step_samples
+    }
+}
+
+/// Musical note lengths, relative to a beat (quarter note)
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NoteValue {
+    Whole,
+    Half,
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+}
+
+/// Variation of a `NoteValue`
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Feel {
+    Straight,
+    /// 3/2 of the length
+    Dotted,
+    /// 2/3 of the length
+    Triplet,
+}
+
+impl NoteValue {
+    /// Length in beats
+    pub const fn beats(self, feel: Feel) -> f32 {
+        let beats = match self {
+            NoteValue::Whole => 4.0,
+            NoteValue::Half => 2.0,
+            NoteValue::Quarter => 1.0,
+            NoteValue::Eighth => 0.5,
+            NoteValue::Sixteenth => 0.25,
+            NoteValue::ThirtySecond => 0.125,
+        };
+
+        match feel {
+            Feel::Straight => beats,
+            Feel::Dotted => beats * 1.5,
+            Feel::Triplet => beats * (2.0 / 3.0),
+        }
+    }
+}
+
+/**
+Converts note values to the units of the processors at the current tempo: delay times
+in ms, LFO frequencies in Hz and envelope times in seconds.
+
+### Example
+
+```rust
+use embedded_audio_tools::tempo::{Feel, NoteValue, TempoSync};
+
+let sync = TempoSync::new(120.0, 48_000.0);
+
+// dotted eighth delay
+assert_eq!(sync.ms(NoteValue::Eighth, Feel::Dotted), 375.0);
+
+// LFO cycling once per bar
+assert_eq!(sync.hz(NoteValue::Whole, Feel::Straight), 0.5);
+
+assert_eq!(sync.samples(NoteValue::Sixteenth, Feel::Straight), 6000.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct TempoSync {
+    bpm: f32,
+    sr: f32,
+    samples_per_beat: f32,
+}
+
+impl TempoSync {
+    /// Tempos below `MIN_BPM` are treated as `MIN_BPM`
+    pub fn new(bpm: f32, sr: f32) -> TempoSync {
+        let mut sync = TempoSync {
+            bpm: MIN_BPM,
+            sr,
+            samples_per_beat: 0.0,
+        };
+
+        sync.set_bpm(bpm);
+        sync
+    }
+
+    /// Tempos below `MIN_BPM` are treated as `MIN_BPM`
+    #[inline(always)]
+    pub fn set_bpm(&mut self, bpm: f32) {
+        self.bpm = bpm.max(MIN_BPM);
+        self.samples_per_beat = bpm_to_samples_per_beat(self.bpm, self.sr);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.samples_per_beat = bpm_to_samples_per_beat(self.bpm, sr);
+    }
+
+    #[inline(always)]
+    pub fn bpm(&self) -> f32 {
+        self.bpm
+    }
+
+    #[inline(always)]
+    pub fn samples_per_beat(&self) -> f32 {
+        self.samples_per_beat
+    }
+
+    #[inline(always)]
+    pub fn samples(&self, note: NoteValue, feel: Feel) -> f32 {
+        note.beats(feel) * self.samples_per_beat
+    }
+
+    #[inline(always)]
+    pub fn seconds(&self, note: NoteValue, feel: Feel) -> f32 {
+        note.beats(feel) * 60.0 / self.bpm
+    }
+
+    #[inline(always)]
+    pub fn ms(&self, note: NoteValue, feel: Feel) -> f32 {
+        self.seconds(note, feel) * 1000.0
+    }
+
+    /// Frequency of one cycle per `note`
+    #[inline(always)]
+    pub fn hz(&self, note: NoteValue, feel: Feel) -> f32 {
+        self.bpm / (60.0 * note.beats(feel))
+    }
+
+    /// Delay of a swung step of length `note`, see `swing_offset`
+    #[inline(always)]
+    pub fn swing_offset(&self, step: u32, note: NoteValue, swing: f32) -> f32 {
+        swing_offset(step, self.samples(note, Feel::Straight), swing)
+    }
+}
+
+/**
+Divides an incoming clock, e.g. MIDI clock at 24 pulses per quarter note or a gate
+input, by an integer factor.
+
+### Example
+
+```rust
+use embedded_audio_tools::tempo::ClockDivider;
+
+// 16ths from MIDI clock
+let mut divider = ClockDivider::new(6);
+
+let pulses = (0..24).filter(|_| divider.tick()).count();
+assert_eq!(pulses, 4);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct ClockDivider {
+    division: u32,
+    count: u32,
+}
+
+impl ClockDivider {
+    /// A `division` of 0 is treated as 1
+    pub const fn new(division: u32) -> ClockDivider {
+        ClockDivider {
+            division: if division == 0 { 1 } else { division },
+            count: 0,
+        }
+    }
+
+    /// A `division` of 0 is treated as 1. Keeps the position within the current cycle
+    /// where possible.
+    #[inline(always)]
+    pub fn set_division(&mut self, division: u32) {
+        self.division = division.max(1);
+        self.count %= self.division;
+    }
+
+    #[inline(always)]
+    pub fn division(&self) -> u32 {
+        self.division
+    }
+
+    /// The next incoming pulse fires
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    /// Call on every incoming pulse, returns true on every `division`-th one,
+    /// starting with the first
+    #[inline(always)]
+    pub fn tick(&mut self) -> bool {
+        let fire = self.count == 0;
+
+        self.count += 1;
+        if self.count == self.division {
+            self.count = 0;
+        }
+
+        fire
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bpm_round_trip() {
+        for bpm in [60.0, 97.5, 120.0, 174.0] {
+            let samples = bpm_to_samples_per_beat(bpm, 44_100.0);
+            assert!((samples_per_beat_to_bpm(samples, 44_100.0) - bpm).abs() < 1e-3);
+        }
+
+        assert_eq!(bpm_to_samples_per_beat(0.0, 48_000.0), 48_000.0 * 60.0);
+    }
+
+    #[test]
+    fn note_values() {
+        let sync = TempoSync::new(120.0, 48_000.0);
+
+        assert_eq!(sync.samples(NoteValue::Quarter, Feel::Straight), 24_000.0);
+        assert_eq!(sync.samples(NoteValue::Quarter, Feel::Dotted), 36_000.0);
+        assert_eq!(sync.samples(NoteValue::Quarter, Feel::Triplet), 16_000.0);
+        assert_eq!(sync.seconds(NoteValue::Whole, Feel::Straight), 2.0);
+        assert_eq!(sync.hz(NoteValue::Eighth, Feel::Straight), 4.0);
+    }
+
+    #[test]
+    fn swing() {
+        let sync = TempoSync::new(120.0, 48_000.0);
+
+        // Triplet shuffle puts the second 16th onto the last eighth triplet
+        let offset = sync.swing_offset(1, NoteValue::Sixteenth, 2.0 / 3.0);
+        assert!((offset - 2000.0).abs() < 0.01);
+
+        assert_eq!(sync.swing_offset(3, NoteValue::Sixteenth, 0.5), 0.0);
+        assert_eq!(sync.swing_offset(3, NoteValue::Sixteenth, 1.0), 3000.0);
+    }
+
+    #[test]
+    fn divider() {
+        let mut divider = ClockDivider::new(3);
+        let pulses: [bool; 7] = core::array::from_fn(|_| divider.tick());
+        assert_eq!(pulses, [true, false, false, true, false, false, true]);
+
+        divider.set_division(0);
+        assert!(divider.tick() && divider.tick());
+
+        divider.set_division(4);
+        divider.tick();
+        divider.reset();
+        assert!(divider.tick());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::float::AdditionalF32Ext;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/// MIDI note number of A4
+pub const A4_NOTE: u8 = 69;
+
+/// Standard concert pitch of A4 in Hz
+pub const A4_FREQ: f32 = 440.0;
+
+/// Converts a (fractional) MIDI note number to a frequency in Hz with `a4` as reference
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tuning::{note_to_freq, A4_FREQ};
+///
+/// assert_eq!(note_to_freq(69.0, A4_FREQ), 440.0);
+/// assert_eq!(note_to_freq(81.0, A4_FREQ), 880.0);
+/// assert!((note_to_freq(60.0, A4_FREQ) - 261.6256).abs() < 1e-2);
+/// ```
+#[inline(always)]
+pub fn note_to_freq(note: f32, a4: f32) -> f32 {
+    a4 * ((note - A4_NOTE as f32) * (1.0 / 12.0)).fast_exp2()
+}
+
+/// Converts a frequency in Hz to a (fractional) MIDI note number with `a4` as reference
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tuning::{freq_to_note, A4_FREQ};
+///
+/// assert_eq!(freq_to_note(440.0, A4_FREQ), 69.0);
+/// assert!((freq_to_note(261.6256, A4_FREQ) - 60.0).abs() < 1e-2);
+/// ```
+#[inline(always)]
+pub fn freq_to_note(freq: f32, a4: f32) -> f32 {
+    A4_NOTE as f32 + 12.0 * (freq / a4).fast_log2()
+}
+
+/// Converts an integer MIDI note number to a frequency in Hz with `a4` as reference
+#[inline(always)]
+pub fn midi_note_to_freq(note: u8, a4: f32) -> f32 {
+    note_to_freq(note as f32, a4)
+}
+
+/// Converts a frequency in Hz to the closest integer MIDI note number (0 to 127)
+#[inline(always)]
+pub fn freq_to_midi_note(freq: f32, a4: f32) -> u8 {
+    freq_to_note(freq, a4).round().clamp(0.0, 127.0) as u8
+}
+
+/// Converts a transposition in semitones to a frequency ratio
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tuning::semitones_to_ratio;
+///
+/// assert_eq!(semitones_to_ratio(12.0), 2.0);
+/// assert_eq!(semitones_to_ratio(-12.0), 0.5);
+/// assert!((semitones_to_ratio(7.0) - 1.4983).abs() < 1e-4);
+/// ```
+#[inline(always)]
+pub fn semitones_to_ratio(semitones: f32) -> f32 {
+    (semitones * (1.0 / 12.0)).fast_exp2()
+}
+
+/// Converts a frequency ratio to a transposition in semitones
+#[inline(always)]
+pub fn ratio_to_semitones(ratio: f32) -> f32 {
+    12.0 * ratio.fast_log2()
+}
+
+/// Converts a detune in cents to a frequency ratio
+///
+/// ## Example
+/// ```rust
+/// use embedded_audio_tools::tuning::cents_to_ratio;
+///
+/// assert_eq!(cents_to_ratio(0.0), 1.0);
+/// assert_eq!(cents_to_ratio(1200.0), 2.0);
+/// ```
+#[inline(always)]
+pub fn cents_to_ratio(cents: f32) -> f32 {
+    (cents * (1.0 / 1200.0)).fast_exp2()
+}
+
+/// Converts a frequency ratio to a detune in cents
+#[inline(always)]
+pub fn ratio_to_cents(ratio: f32) -> f32 {
+    1200.0 * ratio.fast_log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for note in 0..=127 {
+            let freq = midi_note_to_freq(note, A4_FREQ);
+            assert_eq!(freq_to_midi_note(freq, A4_FREQ), note);
+        }
+    }
+
+    #[test]
+    fn reference_pitch() {
+        assert_eq!(midi_note_to_freq(69, 432.0), 432.0);
+        assert_eq!(midi_note_to_freq(57, 432.0), 216.0);
+        assert_eq!(freq_to_midi_note(0.0, A4_FREQ), 0);
+        assert_eq!(freq_to_midi_note(1e6, A4_FREQ), 127);
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(ratio_to_semitones(2.0), 12.0);
+        assert_eq!(ratio_to_cents(0.5), -1200.0);
+
+        for i in -480..480 {
+            let semitones = i as f32 * 0.1;
+            let ratio = semitones_to_ratio(semitones);
+            assert!((ratio_to_semitones(ratio) - semitones).abs() < 2e-3);
+            assert!(
+                (ratio_to_cents(cents_to_ratio(semitones * 100.0)) - semitones * 100.0).abs() < 0.2
+            );
+        }
+    }
+
+    #[test]
+    fn accuracy() {
+        for note in 0..=127 {
+            let exact = 440.0 * ((note as f64 - 69.0) / 12.0).exp2();
+            let error = ((midi_note_to_freq(note, A4_FREQ) as f64 - exact) / exact).abs();
+            assert!(error < 1e-5, "note: {}, error: {}", note, error);
+        }
+    }
+}
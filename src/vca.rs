@@ -0,0 +1,218 @@
+use crate::block;
+use crate::decibels::Decibels;
+use crate::float::{crossfade_gains, CrossfadeCurve};
+use crate::smoothed_param::{SmoothedParam, Smoothing};
+
+/**
+Gain stage with built-in smoothing, so gain changes from a UI thread don't zipper.
+
+### Example
+
+```rust
+use embedded_audio_tools::{Smoothing, Vca};
+
+let mut vca = Vca::new(5.0, 48_000.0, Smoothing::OnePole);
+vca.set_gain_db(-6.0);
+
+// during audio callback
+let sample = vca.process(1.0);
+# assert!(sample < 1.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Vca {
+    gain: SmoothedParam,
+}
+
+impl Vca {
+    /// Starts at unity gain
+    pub fn new(time_ms: f32, sr: f32, mode: Smoothing) -> Vca {
+        Vca {
+            gain: SmoothedParam::new(1.0, time_ms, sr, mode),
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain.set_target(gain);
+    }
+
+    #[inline(always)]
+    pub fn set_gain_db(&mut self, db: f32) {
+        self.gain.set_target(db.to_volt_ratio());
+    }
+
+    #[inline(always)]
+    pub fn set_time(&mut self, time_ms: f32) {
+        self.gain.set_time(time_ms);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.gain.set_sr_unchecked(sr);
+    }
+
+    /// Current, possibly still ramping gain
+    #[inline(always)]
+    pub fn gain(&self) -> f32 {
+        self.gain.value()
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, sample: f32) -> f32 {
+        sample * self.gain.next()
+    }
+
+    #[inline(always)]
+    pub fn process_stereo(&mut self, samples: (f32, f32)) -> (f32, f32) {
+        let gain = self.gain.next();
+        (samples.0 * gain, samples.1 * gain)
+    }
+
+    /// Processes `block` in place, with a 4-wide kernel once the gain has settled
+    pub fn process_block(&mut self, block: &mut [f32]) {
+        if self.gain.is_smoothing() {
+            for sample in block {
+                *sample *= self.gain.next();
+            }
+        } else {
+            block::scale(block, self.gain.value());
+        }
+    }
+}
+
+/**
+Crossfader with built-in smoothing of its position, from `a` at `-1` to `b` at `1`.
+
+### Example
+
+```rust
+use embedded_audio_tools::{float::CrossfadeCurve, Crossfader, Smoothing};
+
+let mut fader = Crossfader::new(CrossfadeCurve::EqualPower, 5.0, 48_000.0, Smoothing::Linear);
+fader.set_position(1.0);
+
+// during audio callback
+let sample = fader.process(0.5, -0.5);
+# assert!(sample.abs() <= 0.5);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Crossfader {
+    position: SmoothedParam,
+    curve: CrossfadeCurve,
+}
+
+impl Crossfader {
+    /// Starts fully at `a`
+    pub fn new(curve: CrossfadeCurve, time_ms: f32, sr: f32, mode: Smoothing) -> Crossfader {
+        Crossfader {
+            position: SmoothedParam::new(-1.0, time_ms, sr, mode),
+            curve,
+        }
+    }
+
+    /// Clamps between -1 and 1
+    #[inline(always)]
+    pub fn set_position(&mut self, position: f32) {
+        self.position.set_target(position.clamp(-1.0, 1.0));
+    }
+
+    #[inline(always)]
+    pub fn set_curve(&mut self, curve: CrossfadeCurve) {
+        self.curve = curve;
+    }
+
+    #[inline(always)]
+    pub fn set_time(&mut self, time_ms: f32) {
+        self.position.set_time(time_ms);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.position.set_sr_unchecked(sr);
+    }
+
+    /// Current, possibly still ramping position
+    #[inline(always)]
+    pub fn position(&self) -> f32 {
+        self.position.value()
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, a: f32, b: f32) -> f32 {
+        let gains = self.next_gains();
+        a * gains.0 + b * gains.1
+    }
+
+    #[inline(always)]
+    pub fn process_stereo(&mut self, a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+        let gains = self.next_gains();
+        (a.0 * gains.0 + b.0 * gains.1, a.1 * gains.0 + b.1 * gains.1)
+    }
+
+    #[inline(always)]
+    fn next_gains(&mut self) -> (f32, f32) {
+        crossfade_gains((self.position.next() + 1.0) * 0.5, self.curve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 1000.0;
+
+    #[test]
+    fn vca_ramps() {
+        let mut vca = Vca::new(4.0, SR, Smoothing::Linear);
+        vca.set_gain(0.0);
+
+        assert_eq!(vca.process(1.0), 0.75);
+        assert_eq!(vca.process_stereo((1.0, -1.0)), (0.5, -0.5));
+        assert_eq!(vca.process(1.0), 0.25);
+        assert_eq!(vca.process(1.0), 0.0);
+    }
+
+    #[test]
+    fn vca_decibels() {
+        let mut vca = Vca::new(0.0, SR, Smoothing::OnePole);
+        vca.set_gain_db(-20.0);
+
+        assert!((vca.process(1.0) - 0.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn vca_block() {
+        let mut vca = Vca::new(4.0, SR, Smoothing::Linear);
+        vca.set_gain(0.0);
+
+        let mut block = [1.0; 6];
+        vca.process_block(&mut block);
+        assert_eq!(block, [0.75, 0.5, 0.25, 0.0, 0.0, 0.0]);
+
+        vca.set_gain(0.5);
+        vca.process_block(&mut block);
+        vca.process_block(&mut block);
+        assert_eq!(vca.gain(), 0.5);
+
+        let mut block = [1.0, -2.0, 4.0, 0.5, 1.0];
+        vca.process_block(&mut block);
+        assert_eq!(block, [0.5, -1.0, 2.0, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn crossfader_ramps() {
+        let mut fader = Crossfader::new(CrossfadeCurve::Linear, 4.0, SR, Smoothing::Linear);
+        assert_eq!(fader.process(1.0, 0.0), 1.0);
+
+        fader.set_position(3.0);
+        assert_eq!(fader.process(1.0, 0.0), 0.75);
+        assert_eq!(fader.process_stereo((1.0, 1.0), (0.0, 0.0)), (0.5, 0.5));
+        assert_eq!(fader.process(1.0, 0.0), 0.25);
+        assert_eq!(fader.process(1.0, 0.0), 0.0);
+        assert_eq!(fader.position(), 1.0);
+    }
+}
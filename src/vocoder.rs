@@ -0,0 +1,227 @@
+use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+use crate::dynamics::EnvelopeFollower;
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+/**
+Channel vocoder with `BANDS` bandpass filters, spaced logarithmically between a low and a
+high frequency.
+
+Each band of the modulator (e.g. a voice) drives an envelope follower, which sets the
+level of the same band of the carrier (e.g. a saw or noise). The Q of the bands follows
+from their spacing, so neighbours meet at about -3 dB.
+
+### Example
+
+```rust
+use embedded_audio_tools::Vocoder;
+
+let mut vocoder: Vocoder<16> = Vocoder::new(100.0, 8000.0, 48_000.0);
+vocoder.set_attack(2.0);
+vocoder.set_release(30.0);
+
+// during audio callback
+let (voice, saw) = (0.3, -0.8);
+let out = vocoder.process(voice, saw);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Vocoder<const BANDS: usize> {
+    analysis: [Biquad<Butterworth>; BANDS],
+    synthesis: [Biquad<Butterworth>; BANDS],
+    followers: [EnvelopeFollower; BANDS],
+
+    low: f32,
+    high: f32,
+    sr: f32,
+}
+
+impl<const BANDS: usize> Vocoder<BANDS> {
+    /// Starts with an attack of 5 ms and a release of 50 ms
+    pub fn new(low: f32, high: f32, sr: f32) -> Vocoder<BANDS> {
+        let mut vocoder = Vocoder {
+            analysis: core::array::from_fn(|_| Biquad::new(BiquadCoeffs::new())),
+            synthesis: core::array::from_fn(|_| Biquad::new(BiquadCoeffs::new())),
+            followers: [EnvelopeFollower::new(5.0, 50.0, sr); BANDS],
+            low,
+            high,
+            sr,
+        };
+
+        vocoder.update_bands();
+        vocoder
+    }
+
+    /// Center frequencies of the lowest and highest band, which swap if `low` is higher
+    pub fn set_range(&mut self, low: f32, high: f32) {
+        self.low = low.min(high);
+        self.high = low.max(high);
+        self.update_bands();
+    }
+
+    /// How fast the bands open, in ms
+    pub fn set_attack(&mut self, attack_ms: f32) {
+        for follower in self.followers.iter_mut() {
+            follower.set_attack(attack_ms);
+        }
+    }
+
+    /// How fast the bands close, in ms
+    pub fn set_release(&mut self, release_ms: f32) {
+        for follower in self.followers.iter_mut() {
+            follower.set_release(release_ms);
+        }
+    }
+
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+
+        for follower in self.followers.iter_mut() {
+            follower.set_sr_unchecked(sr);
+        }
+
+        self.update_bands();
+    }
+
+    /// Center frequency of `band`, counting from the lowest
+    #[inline(always)]
+    pub fn band_freq(&self, band: usize) -> f32 {
+        self.low * self.spacing().powf(band as f32)
+    }
+
+    /// Current level of each band of the modulator
+    #[inline(always)]
+    pub fn levels(&self) -> [f32; BANDS] {
+        core::array::from_fn(|band| self.followers[band].value())
+    }
+
+    pub fn reset(&mut self) {
+        for follower in self.followers.iter_mut() {
+            follower.reset();
+        }
+
+        for filter in self.analysis.iter_mut().chain(self.synthesis.iter_mut()) {
+            filter.reset();
+        }
+    }
+
+    /// Imposes the spectral envelope of `modulator` onto `carrier`
+    pub fn process(&mut self, modulator: f32, carrier: f32) -> f32 {
+        let mut out = 0.0;
+
+        for band in 0..BANDS {
+            let level = self.followers[band].tick(self.analysis[band].process(modulator));
+            out += self.synthesis[band].process(carrier) * level;
+        }
+
+        out
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    pub fn flush_denormals(&mut self) {
+        for filter in self.analysis.iter_mut().chain(self.synthesis.iter_mut()) {
+            filter.flush_denormals();
+        }
+    }
+
+    /// Ratio between the center frequencies of neighbouring bands
+    #[inline(always)]
+    fn spacing(&self) -> f32 {
+        if BANDS > 1 {
+            (self.high / self.low).powf(1.0 / (BANDS - 1) as f32)
+        } else {
+            1.0
+        }
+    }
+
+    fn update_bands(&mut self) {
+        let spacing = self.spacing();
+
+        // Band edges halfway (in octaves) to the neighbours, a single band spans an octave
+        let q = if spacing > 1.0 {
+            spacing.sqrt() / (spacing - 1.0)
+        } else {
+            core::f32::consts::SQRT_2
+        };
+
+        for band in 0..BANDS {
+            let fc = self.band_freq(band).min(0.49 * self.sr);
+
+            self.analysis[band].coeffs.bandpass(fc, q, self.sr);
+            self.synthesis[band].coeffs.bandpass(fc, q, self.sr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::TAU;
+
+    const SR: f32 = 48_000.0;
+
+    fn sine(freq: f32, i: usize) -> f32 {
+        (TAU * freq * i as f32 / SR).sin()
+    }
+
+    #[test]
+    fn band_layout() {
+        let vocoder: Vocoder<5> = Vocoder::new(100.0, 1600.0, SR);
+
+        for (band, freq) in [100.0, 200.0, 400.0, 800.0, 1600.0].iter().enumerate() {
+            assert!((vocoder.band_freq(band) - freq).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn silent_modulator() {
+        let mut vocoder: Vocoder<8> = Vocoder::new(100.0, 8000.0, SR);
+
+        for i in 0..4800 {
+            assert_eq!(vocoder.process(0.0, sine(440.0, i)), 0.0);
+        }
+    }
+
+    #[test]
+    fn follows_modulator_bands() {
+        let mut vocoder: Vocoder<5> = Vocoder::new(100.0, 1600.0, SR);
+
+        // Modulator right on the 400 Hz band
+        for i in 0..9600 {
+            vocoder.process(sine(400.0, i), 0.0);
+        }
+
+        // Bands an octave apart overlap, but fall off towards the sides
+        let levels = vocoder.levels();
+        assert!(levels[2] > 0.8, "{:?}", levels);
+        assert!(levels[1] < 0.5 && levels[3] < 0.5, "{:?}", levels);
+        assert!(levels[0] < 0.2 && levels[4] < 0.2, "{:?}", levels);
+
+        // A carrier in the open band passes, one in a closed band much less
+        let peak = |vocoder: &mut Vocoder<5>, freq: f32| {
+            (0..9600)
+                .map(|i| vocoder.process(sine(400.0, i), sine(freq, i)).abs())
+                .skip(4800)
+                .fold(0.0, f32::max)
+        };
+
+        let open = peak(&mut vocoder, 400.0);
+        let closed = peak(&mut vocoder, 1600.0);
+        assert!(open > 0.8 && closed < 0.5 * open, "{} {}", open, closed);
+    }
+
+    #[test]
+    fn reset() {
+        let mut vocoder: Vocoder<4> = Vocoder::new(200.0, 4000.0, SR);
+
+        for i in 0..480 {
+            vocoder.process(sine(1000.0, i), sine(1000.0, i));
+        }
+
+        vocoder.reset();
+        assert_eq!(vocoder.levels(), [0.0; 4]);
+        assert_eq!(vocoder.process(0.0, 0.0), 0.0);
+    }
+}
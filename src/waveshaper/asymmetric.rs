@@ -0,0 +1,210 @@
+use core::f32::consts::TAU;
+
+use crate::float::{flush_denormal, AdditionalF32Ext};
+
+#[allow(unused_imports)]
+use crate::float::F32Ext;
+
+const DC_BLOCKER_FREQ: f32 = 10.0;
+
+/**
+Saturator with a bias and an asymmetry control for even harmonic content, distinct from
+the symmetric clippers which only generate odd harmonics.
+
+* `bias` shifts the operating point along the curve before saturation
+* `asymmetry` makes the negative (`> 0`) or positive (`< 0`) half clip earlier,
+  down to a quarter of the other half at ±1
+
+The static offset at silence is subtracted right away, the signal dependent offset is
+removed by a 10 Hz DC blocker, so the output stays centered.
+
+### Example
+
+```rust
+use embedded_audio_tools::waveshaper::AsymmetricSaturator;
+
+let mut saturator = AsymmetricSaturator::new(48_000.0);
+saturator.set_drive(2.0);
+saturator.set_bias(0.2);
+saturator.set_asymmetry(0.5);
+
+// during audio callback
+let sample = saturator.process(0.0);
+# assert_eq!(sample, 0.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct AsymmetricSaturator {
+    drive: f32,
+    bias: f32,
+    asymmetry: f32,
+    offset: f32,
+    dc_coeff: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl AsymmetricSaturator {
+    /// Starts with unity drive, no bias and no asymmetry
+    pub fn new(sr: f32) -> AsymmetricSaturator {
+        let mut saturator = AsymmetricSaturator {
+            drive: 1.0,
+            bias: 0.0,
+            asymmetry: 0.0,
+            offset: 0.0,
+            dc_coeff: 0.0,
+            x1: 0.0,
+            y1: 0.0,
+        };
+
+        saturator.set_sr_unchecked(sr);
+        saturator
+    }
+
+    /// Input gain before the bias, clamps to positive values
+    #[inline(always)]
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive.max(0.0);
+    }
+
+    /// Clamps between -1 and 1
+    #[inline(always)]
+    pub fn set_bias(&mut self, bias: f32) {
+        self.bias = bias.clamp(-1.0, 1.0);
+        self.update_offset();
+    }
+
+    /// Clamps between -1 and 1
+    #[inline(always)]
+    pub fn set_asymmetry(&mut self, asymmetry: f32) {
+        self.asymmetry = asymmetry.clamp(-1.0, 1.0);
+        self.update_offset();
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.dc_coeff = 1.0 - TAU * DC_BLOCKER_FREQ / sr;
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let shaped = self.shape(sample * self.drive + self.bias) - self.offset;
+
+        // one pole DC blocker
+        let out = shaped - self.x1 + self.dc_coeff * self.y1;
+        self.x1 = shaped;
+        self.y1 = out;
+
+        out
+    }
+
+    /// Flushes the subnormal DC blocker state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.y1 = flush_denormal(self.y1);
+    }
+
+    /// tanh(g * x) / g, with the gain g > 1 on the harder clipping half
+    #[inline(always)]
+    fn shape(&self, x: f32) -> f32 {
+        let hardness = if x < 0.0 {
+            1.0 + 3.0 * self.asymmetry.max(0.0)
+        } else {
+            1.0 - 3.0 * self.asymmetry.min(0.0)
+        };
+
+        (hardness * x).fast_tanh() / hardness
+    }
+
+    #[inline(always)]
+    fn update_offset(&mut self) {
+        self.offset = self.shape(self.bias);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn sine(i: usize) -> f32 {
+        (core::f64::consts::TAU * 1000.0 * i as f64 / SR as f64).sin() as f32
+    }
+
+    /// Magnitude of the `harmonic`-th harmonic of 1 kHz over `samples`
+    fn harmonic(samples: &[f32], harmonic: usize) -> f64 {
+        let (mut re, mut im) = (0.0, 0.0);
+
+        for (i, x) in samples.iter().enumerate() {
+            let phase = core::f64::consts::TAU * (1000 * harmonic) as f64 * i as f64 / SR as f64;
+            re += *x as f64 * phase.cos();
+            im += *x as f64 * phase.sin();
+        }
+
+        (re * re + im * im).sqrt() * 2.0 / samples.len() as f64
+    }
+
+    #[test]
+    fn silence_stays_silent() {
+        let mut saturator = AsymmetricSaturator::new(SR);
+        saturator.set_bias(0.7);
+        saturator.set_asymmetry(-0.8);
+
+        for _ in 0..100 {
+            assert_eq!(saturator.process(0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn symmetric_without_bias() {
+        let saturator = AsymmetricSaturator::new(SR);
+
+        for i in 0..=100 {
+            let x = i as f32 * 0.05;
+            assert_eq!(saturator.shape(-x), -saturator.shape(x));
+        }
+    }
+
+    #[test]
+    fn asymmetry_adds_even_harmonics() {
+        let render = |asymmetry: f32| {
+            let mut saturator = AsymmetricSaturator::new(SR);
+            saturator.set_drive(2.0);
+            saturator.set_asymmetry(asymmetry);
+
+            let mut out = [0.0; 4800];
+            for (i, y) in out.iter_mut().enumerate() {
+                *y = saturator.process(sine(i));
+            }
+            out
+        };
+
+        let symmetric = render(0.0);
+        let asymmetric = render(0.6);
+
+        assert!(harmonic(&symmetric, 2) < 1e-3);
+        assert!(harmonic(&asymmetric, 2) > 0.05);
+        assert!(harmonic(&asymmetric, 3) > 0.01);
+    }
+
+    #[test]
+    fn removes_dc() {
+        let mut saturator = AsymmetricSaturator::new(SR);
+        saturator.set_drive(4.0);
+        saturator.set_bias(0.3);
+        saturator.set_asymmetry(1.0);
+
+        // let the DC blocker settle for half a second
+        for i in 0..24_000 {
+            saturator.process(sine(i));
+        }
+
+        let mut sum = 0.0;
+        for i in 24_000..28_800 {
+            sum += saturator.process(sine(i));
+        }
+
+        assert!((sum / 4800.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+use crate::float::bit_reduce_unchecked;
+
+const ANTI_ALIAS_Q: f32 = core::f32::consts::FRAC_1_SQRT_2;
+/// Cutoff of the anti alias filter relative to the reduced rate
+const ANTI_ALIAS_RATIO: f32 = 0.45;
+
+/**
+Lo-fi effect combining bit depth reduction with a sample and hold rate reducer.
+
+The reduced rate can be any fraction of the sample rate. The held samples are picked
+by a phase accumulator, so fractional rates alternate between hold lengths instead of
+being rounded. An optional lowpass in front of the rate reducer tames the aliasing
+for a cleaner, more vintage sampler like sound.
+
+### Example
+
+```rust
+use embedded_audio_tools::waveshaper::Bitcrusher;
+
+let mut crusher = Bitcrusher::new(48_000.0);
+crusher.set_bits(8);
+crusher.set_rate(11_025.0);
+crusher.set_mix(0.5);
+
+// during audio callback
+let sample = crusher.process(0.25);
+# assert!(sample.abs() <= 0.25);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Bitcrusher {
+    anti_alias: Biquad<Butterworth>,
+    anti_alias_enabled: bool,
+    shift: u8,
+    rate: f32,
+    sr: f32,
+    increment: f32,
+    phase: f32,
+    held: f32,
+    mix: f32,
+}
+
+impl Bitcrusher {
+    /// Starts with full resolution, no rate reduction and a fully wet mix
+    pub fn new(sr: f32) -> Bitcrusher {
+        let mut crusher = Bitcrusher {
+            anti_alias: Biquad::new(BiquadCoeffs::new()),
+            anti_alias_enabled: false,
+            shift: 0,
+            rate: sr,
+            sr,
+            increment: 1.0,
+            phase: 1.0,
+            held: 0.0,
+            mix: 1.0,
+        };
+
+        crusher.set_sr_unchecked(sr);
+        crusher
+    }
+
+    /// Resulting resolution including the sign, clamps between 2 and 32 bits.
+    /// 32 bits leaves the signal untouched.
+    #[inline(always)]
+    pub fn set_bits(&mut self, bits: u8) {
+        self.shift = 32 - bits.clamp(2, 32);
+    }
+
+    /// Reduced sample rate in Hz, clamps between 1 Hz and the sample rate
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate;
+        self.update_rate();
+    }
+
+    #[inline(always)]
+    pub fn set_anti_alias(&mut self, enabled: bool) {
+        self.anti_alias_enabled = enabled;
+    }
+
+    /// Dry/wet mix, clamps between 0 and 1
+    #[inline(always)]
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_rate();
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let filtered = if self.anti_alias_enabled {
+            self.anti_alias.process(sample)
+        } else {
+            sample
+        };
+
+        self.phase += self.increment;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+            self.held = bit_reduce_unchecked(filtered.clamp(-1.0, 1.0), self.shift);
+        }
+
+        sample + (self.held - sample) * self.mix
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.anti_alias.flush_denormals();
+    }
+
+    fn update_rate(&mut self) {
+        let rate = self.rate.clamp(1.0, self.sr);
+
+        self.increment = rate / self.sr;
+        self.anti_alias
+            .coeffs
+            .lowpass(rate * ANTI_ALIAS_RATIO, ANTI_ALIAS_Q, self.sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn transparent_by_default() {
+        let mut crusher = Bitcrusher::new(SR);
+
+        for i in -100..100 {
+            let x = i as f32 / 100.0;
+            assert_eq!(crusher.process(x), x);
+        }
+    }
+
+    #[test]
+    fn bit_reduction() {
+        let mut crusher = Bitcrusher::new(SR);
+        crusher.set_bits(2);
+
+        // steps of 0.5 towards zero
+        assert_eq!(crusher.process(0.7), 0.5);
+        assert_eq!(crusher.process(0.2), 0.0);
+        assert_eq!(crusher.process(-0.9), -0.5);
+    }
+
+    #[test]
+    fn fractional_rate() {
+        let mut crusher = Bitcrusher::new(SR);
+        crusher.set_rate(SR / 2.5);
+
+        let out: [f32; 10] = core::array::from_fn(|i| crusher.process(i as f32 * 0.01));
+
+        // new samples are taken every 2 or 3 samples, 4 times in 10 samples. The first one
+        // is taken right at the start, so the output only changes 3 times.
+        let mut changes = 0;
+        for i in 1..out.len() {
+            if out[i] != out[i - 1] {
+                changes += 1;
+                assert_eq!(out[i], i as f32 * 0.01);
+            }
+        }
+
+        assert_eq!(changes, 3);
+        assert_eq!(out[0], 0.0);
+    }
+
+    #[test]
+    fn mix() {
+        let mut crusher = Bitcrusher::new(SR);
+        crusher.set_bits(2);
+        crusher.set_mix(0.5);
+
+        assert_eq!(crusher.process(0.7), 0.6);
+    }
+
+    #[test]
+    fn anti_alias_smooths() {
+        let mut crusher = Bitcrusher::new(SR);
+        crusher.set_rate(4_000.0);
+        crusher.set_anti_alias(true);
+
+        // nyquist tone is removed before sampling
+        let mut peak: f32 = 0.0;
+        for i in 0..4800 {
+            let x = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let y = crusher.process(x);
+
+            if i > 480 {
+                peak = peak.max(y.abs());
+            }
+        }
+
+        assert!(peak < 0.01);
+    }
+}
@@ -0,0 +1,123 @@
+/**
+Waveshaper summing weighted Chebyshev polynomials T1 to TN.
+
+Driven by a full scale sine, Tn produces exactly the n-th harmonic, so the weights are
+a harmonic recipe: `[1.0, 0.0, 0.1]` keeps the fundamental and adds 10% of the 3rd
+harmonic. Lower input levels result in a softer, level dependent mix, much like a
+tube stage. The input gets clamped to ±1, where the polynomials are defined.
+
+### Example
+
+```rust
+use embedded_audio_tools::waveshaper::ChebyshevShaper;
+
+// fundamental plus a bit of 2nd and 3rd harmonic
+let mut shaper = ChebyshevShaper::new([1.0, 0.2, 0.1]);
+shaper.set_weight(3, 0.05);
+
+// during audio callback
+let sample = shaper.process(0.5);
+# assert!(sample.abs() <= 1.25);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct ChebyshevShaper<const N: usize> {
+    weights: [f32; N],
+}
+
+impl<const N: usize> ChebyshevShaper<N> {
+    /// `weights[0]` belongs to T1 (the fundamental), `weights[N - 1]` to TN
+    pub const fn new(weights: [f32; N]) -> ChebyshevShaper<N> {
+        ChebyshevShaper { weights }
+    }
+
+    /// Weight of the `harmonic`-th polynomial, ignored outside of 1 to N
+    #[inline(always)]
+    pub fn set_weight(&mut self, harmonic: usize, weight: f32) {
+        if let Some(w) = harmonic
+            .checked_sub(1)
+            .and_then(|i| self.weights.get_mut(i))
+        {
+            *w = weight;
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_weights(&mut self, weights: [f32; N]) {
+        self.weights = weights;
+    }
+
+    #[inline(always)]
+    pub fn weights(&self) -> &[f32; N] {
+        &self.weights
+    }
+
+    pub fn process(&self, sample: f32) -> f32 {
+        let x = sample.clamp(-1.0, 1.0);
+
+        // T(n + 1) = 2x * T(n) - T(n - 1)
+        let mut previous = 1.0;
+        let mut current = x;
+        let mut out = 0.0;
+
+        for weight in self.weights {
+            out += weight * current;
+
+            let next = 2.0 * x * current - previous;
+            previous = current;
+            current = next;
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polynomials() {
+        let t = |n: usize, x: f32| {
+            let mut weights = [0.0; 5];
+            weights[n - 1] = 1.0;
+            ChebyshevShaper::new(weights).process(x)
+        };
+
+        for i in -100..=100 {
+            let x = i as f32 / 100.0;
+
+            assert!((t(1, x) - x).abs() < 1e-6);
+            assert!((t(2, x) - (2.0 * x * x - 1.0)).abs() < 1e-6);
+            assert!((t(3, x) - (4.0 * x * x * x - 3.0 * x)).abs() < 1e-6);
+            assert!((t(5, x) - (16.0 * x.powi(5) - 20.0 * x.powi(3) + 5.0 * x)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn harmonic_recipe() {
+        let shaper = ChebyshevShaper::new([1.0, 0.0, 0.5, 0.0, 0.25]);
+
+        for i in 0..64 {
+            let phase = core::f64::consts::TAU * i as f64 / 64.0;
+            let expected = phase.cos() + 0.5 * (3.0 * phase).cos() + 0.25 * (5.0 * phase).cos();
+
+            let y = shaper.process(phase.cos() as f32);
+            assert!((y as f64 - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn set_weight_bounds() {
+        let mut shaper = ChebyshevShaper::new([1.0, 0.0]);
+
+        shaper.set_weight(0, 5.0);
+        shaper.set_weight(3, 5.0);
+        assert_eq!(shaper.weights(), &[1.0, 0.0]);
+
+        shaper.set_weight(2, 0.5);
+        assert_eq!(shaper.weights(), &[1.0, 0.5]);
+        assert_eq!(shaper.process(2.0), 1.5);
+    }
+}
@@ -0,0 +1,168 @@
+use core::f32::consts::FRAC_1_SQRT_2;
+
+use super::saturator::{SaturationCurve, Saturator};
+use crate::biquad::{butterworth::Butterworth, Biquad, BiquadCoeffs};
+
+/// Drive into the curve until `set_drive_db`
+const DEFAULT_DRIVE_DB: f32 = 12.0;
+
+/**
+Harmonic exciter, adds brightness and presence by generating new harmonics from the top
+end of the signal, e.g. for mastering or small speakers.
+
+The input is highpassed at `freq` and driven into a `Saturator` with auto gain. A second
+highpass at the same frequency removes the low intermodulation products, and `amount` of
+this wet signal gets added to the dry path.
+
+### Example
+
+```rust
+use embedded_audio_tools::waveshaper::{Exciter, SaturationCurve};
+
+let mut exciter = Exciter::new(3000.0, 48_000.0);
+exciter.set_amount(0.3);
+exciter.set_curve(SaturationCurve::Poly(3));
+
+// during audio callback
+let sample = exciter.process(0.5);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct Exciter {
+    pre: Biquad<Butterworth>,
+    post: Biquad<Butterworth>,
+    saturator: Saturator,
+    freq: f32,
+    amount: f32,
+    sr: f32,
+}
+
+impl Exciter {
+    /// Starts with a sigmoid curve at 12 dB drive and an `amount` of 0, so the output
+    /// equals the input until `set_amount`
+    pub fn new(freq: f32, sr: f32) -> Exciter {
+        let mut saturator = Saturator::new(SaturationCurve::Sigmoid(2.0));
+        saturator.set_drive_db(DEFAULT_DRIVE_DB);
+        saturator.set_auto_gain(true);
+
+        let mut exciter = Exciter {
+            pre: Biquad::new(BiquadCoeffs::new()),
+            post: Biquad::new(BiquadCoeffs::new()),
+            saturator,
+            freq,
+            amount: 0.0,
+            sr,
+        };
+
+        exciter.update_coeffs();
+        exciter
+    }
+
+    /// Lowest frequency which gets excited
+    #[inline(always)]
+    pub fn set_freq(&mut self, freq: f32) {
+        self.freq = freq;
+        self.update_coeffs();
+    }
+
+    /// Level of the generated harmonics added to the dry signal, clamps between 0 and 1
+    #[inline(always)]
+    pub fn set_amount(&mut self, amount: f32) {
+        self.amount = amount.clamp(0.0, 1.0);
+    }
+
+    #[inline(always)]
+    pub fn set_curve(&mut self, curve: SaturationCurve) {
+        self.saturator.set_curve(curve);
+    }
+
+    /// More drive generates more and higher harmonics, the auto gain keeps their level
+    #[inline(always)]
+    pub fn set_drive_db(&mut self, db: f32) {
+        self.saturator.set_drive_db(db);
+    }
+
+    #[inline(always)]
+    pub fn set_sr_unchecked(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_coeffs();
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.pre.reset();
+        self.post.reset();
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let shaped = self.saturator.process(self.pre.process(sample));
+        sample + self.post.process(shaped) * self.amount
+    }
+
+    /// Flushes subnormal filter state to zero, once per block is enough
+    #[inline(always)]
+    pub fn flush_denormals(&mut self) {
+        self.pre.flush_denormals();
+        self.post.flush_denormals();
+    }
+
+    #[inline(always)]
+    fn update_coeffs(&mut self) {
+        self.pre.coeffs.highpass(self.freq, FRAC_1_SQRT_2, self.sr);
+        self.post.coeffs.highpass(self.freq, FRAC_1_SQRT_2, self.sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Goertzel;
+    use core::f32::consts::TAU;
+
+    const SR: f32 = 48_000.0;
+
+    fn sine(freq: f32, i: usize) -> f32 {
+        0.5 * (TAU * freq * i as f32 / SR).sin()
+    }
+
+    #[test]
+    fn no_amount_is_dry() {
+        let mut exciter = Exciter::new(2000.0, SR);
+
+        for i in 0..480 {
+            assert_eq!(exciter.process(sine(5000.0, i)), sine(5000.0, i));
+        }
+    }
+
+    #[test]
+    fn adds_harmonics() {
+        let mut exciter = Exciter::new(2000.0, SR);
+        exciter.set_amount(1.0);
+
+        // 3rd harmonic of 3 kHz, measured over whole periods after the filters settled
+        let mut detector = Goertzel::new(9000.0, 480, SR);
+
+        let harmonic = (0..960)
+            .filter_map(|i| {
+                let out = exciter.process(sine(3000.0, i));
+                (i >= 480).then(|| detector.tick(out)).flatten()
+            })
+            .next()
+            .unwrap();
+
+        assert!(harmonic > 0.05, "{}", harmonic);
+    }
+
+    #[test]
+    fn lows_pass_untouched() {
+        let mut exciter = Exciter::new(3000.0, SR);
+        exciter.set_amount(1.0);
+
+        let max_diff = (0..4800)
+            .map(|i| (exciter.process(sine(100.0, i)) - sine(100.0, i)).abs())
+            .fold(0.0, f32::max);
+
+        assert!(max_diff < 0.01, "{}", max_diff);
+    }
+}
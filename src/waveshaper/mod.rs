@@ -0,0 +1,13 @@
+pub mod asymmetric;
+pub mod bitcrusher;
+pub mod chebyshev;
+pub mod exciter;
+pub mod saturator;
+pub mod table;
+
+pub use asymmetric::AsymmetricSaturator;
+pub use bitcrusher::Bitcrusher;
+pub use chebyshev::ChebyshevShaper;
+pub use exciter::Exciter;
+pub use saturator::{SaturationCurve, Saturator};
+pub use table::TableShaper;
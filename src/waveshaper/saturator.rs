@@ -0,0 +1,169 @@
+use crate::clipping::{cubic_clip, hard_clip, poly_clip, sigmoid_clip};
+use crate::decibels::Decibels;
+
+/// Level the auto gain keeps constant, a peak at -12 dBFS
+const AUTO_GAIN_REFERENCE: f32 = 0.25;
+
+/// Shaping functions of a `Saturator`, see `clipping`
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SaturationCurve {
+    Hard,
+    Cubic,
+    /// Odd polynomial with the given hardness
+    Poly(u8),
+    /// Normalized tanh with the given steepness
+    Sigmoid(f32),
+}
+
+impl SaturationCurve {
+    #[inline(always)]
+    fn apply(&self, sample: f32) -> f32 {
+        match *self {
+            SaturationCurve::Hard => hard_clip(sample),
+            SaturationCurve::Cubic => cubic_clip(sample),
+            SaturationCurve::Poly(hardness) => poly_clip(sample, hardness),
+            SaturationCurve::Sigmoid(steepness) => sigmoid_clip(sample, steepness),
+        }
+    }
+}
+
+/**
+Gain staging around a shaping function: input drive, the curve itself, output trim and
+an optional auto gain.
+
+The auto gain keeps the level of a -12 dBFS peak constant while the drive changes, so
+turning up the drive adds saturation instead of mostly adding loudness.
+
+### Example
+
+```rust
+use embedded_audio_tools::waveshaper::{SaturationCurve, Saturator};
+
+let mut saturator = Saturator::new(SaturationCurve::Sigmoid(2.0));
+saturator.set_drive_db(12.0);
+saturator.set_trim_db(-1.0);
+saturator.set_auto_gain(true);
+
+// during audio callback
+let sample = saturator.process(0.5);
+# assert!(sample.abs() < 1.0);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct Saturator {
+    curve: SaturationCurve,
+    drive: f32,
+    trim: f32,
+    auto_gain: bool,
+    compensation: f32,
+}
+
+impl Saturator {
+    /// Starts at 0 dB drive and trim with auto gain disabled
+    pub fn new(curve: SaturationCurve) -> Saturator {
+        let mut saturator = Saturator {
+            curve,
+            drive: 1.0,
+            trim: 1.0,
+            auto_gain: false,
+            compensation: 1.0,
+        };
+
+        saturator.update_compensation();
+        saturator
+    }
+
+    #[inline(always)]
+    pub fn set_curve(&mut self, curve: SaturationCurve) {
+        self.curve = curve;
+        self.update_compensation();
+    }
+
+    #[inline(always)]
+    pub fn set_drive_db(&mut self, db: f32) {
+        self.drive = db.to_volt_ratio();
+        self.update_compensation();
+    }
+
+    #[inline(always)]
+    pub fn set_trim_db(&mut self, db: f32) {
+        self.trim = db.to_volt_ratio();
+    }
+
+    #[inline(always)]
+    pub fn set_auto_gain(&mut self, enabled: bool) {
+        self.auto_gain = enabled;
+    }
+
+    #[inline(always)]
+    pub fn process(&self, sample: f32) -> f32 {
+        let gain = if self.auto_gain {
+            self.trim * self.compensation
+        } else {
+            self.trim
+        };
+
+        self.curve.apply(sample * self.drive) * gain
+    }
+
+    #[inline(always)]
+    fn update_compensation(&mut self) {
+        let reference = self.curve.apply(AUTO_GAIN_REFERENCE * self.drive);
+
+        self.compensation = if reference > 0.0 {
+            AUTO_GAIN_REFERENCE / reference
+        } else {
+            1.0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_staging() {
+        let mut saturator = Saturator::new(SaturationCurve::Hard);
+        assert_eq!(saturator.process(0.5), 0.5);
+
+        saturator.set_drive_db(20.0);
+        assert!((saturator.process(0.05) - 0.5).abs() < 1e-3);
+        assert_eq!(saturator.process(0.5), 1.0);
+
+        saturator.set_trim_db(-20.0);
+        assert!((saturator.process(0.5) - 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn auto_gain_keeps_reference_level() {
+        let curves = [
+            SaturationCurve::Hard,
+            SaturationCurve::Cubic,
+            SaturationCurve::Poly(9),
+            SaturationCurve::Sigmoid(3.0),
+        ];
+
+        for curve in curves {
+            let mut saturator = Saturator::new(curve);
+            saturator.set_auto_gain(true);
+
+            for db in [-6.0, 0.0, 6.0, 12.0] {
+                saturator.set_drive_db(db);
+                let level = saturator.process(AUTO_GAIN_REFERENCE);
+                assert!((level - AUTO_GAIN_REFERENCE).abs() < 1e-5, "{:?}", curve);
+            }
+        }
+    }
+
+    #[test]
+    fn silent_drive() {
+        let mut saturator = Saturator::new(SaturationCurve::Cubic);
+        saturator.set_auto_gain(true);
+        saturator.set_drive_db(-200.0);
+
+        assert!(saturator.process(0.5).is_finite());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::float::{lerp_unchecked, EdgeMode};
+use crate::memory::{memory_slice::MemorySlice, NonMutable};
+
+/**
+Waveshaper mapping the input range -1 to 1 through a transfer curve stored in a table,
+e.g. a measured or designed curve placed in flash.
+
+The first element of the table is the output at -1, the last one at 1, with the points
+spread evenly in between. Inputs beyond ±1 are clamped.
+
+### Example
+
+```rust
+use embedded_audio_tools::{memory_access::from_slice, waveshaper::TableShaper};
+
+// soft knee around zero, hard limit at ±0.8
+let curve = [-0.8, -0.7, 0.0, 0.7, 0.8];
+let shaper = TableShaper::new(from_slice(&curve[..]));
+
+// during audio callback
+assert_eq!(shaper.process(0.25), 0.35);
+assert_eq!(shaper.process(-4.0), -0.8);
+```
+*/
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy)]
+pub struct TableShaper {
+    table: MemorySlice<NonMutable>,
+}
+
+impl TableShaper {
+    pub fn new(table: MemorySlice<NonMutable>) -> TableShaper {
+        TableShaper { table }
+    }
+
+    #[inline(always)]
+    pub fn change_table(&mut self, table: MemorySlice<NonMutable>) {
+        self.table = table;
+    }
+
+    /// Linearly interpolated transfer curve, an empty table results in silence
+    pub fn process(&self, sample: f32) -> f32 {
+        let len = self.table.len();
+        if len < 2 {
+            return self.table.get(0).unwrap_or(0.0);
+        }
+
+        let index = self.index(sample);
+        let int_index = (index as usize).min(len - 2);
+
+        unsafe {
+            let a = self.table.get_unchecked(int_index);
+            let b = self.table.get_unchecked(int_index + 1);
+
+            lerp_unchecked(a, b, index - int_index as f32)
+        }
+    }
+
+    /// Catmull-Rom interpolated transfer curve, smoother for coarse tables
+    pub fn process_cubic(&self, sample: f32) -> f32 {
+        self.table.catmull_rom(self.index(sample), EdgeMode::Clamp)
+    }
+
+    #[inline(always)]
+    fn index(&self, sample: f32) -> f32 {
+        let max_index = self.table.len().saturating_sub(1) as f32;
+        (sample.clamp(-1.0, 1.0) + 1.0) * 0.5 * max_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::memory_slice::from_slice;
+
+    #[test]
+    fn identity_curve() {
+        let curve = [-1.0, -0.5, 0.0, 0.5, 1.0];
+        let shaper = TableShaper::new(from_slice(&curve[..]));
+
+        for i in -200..=200 {
+            let x = i as f32 / 100.0;
+
+            assert!((shaper.process(x) - x.clamp(-1.0, 1.0)).abs() < 1e-6);
+        }
+
+        // the clamped edges bend the outer segments of the spline
+        for i in -50..=50 {
+            let x = i as f32 / 100.0;
+            assert!((shaper.process_cubic(x) - x).abs() < 1e-6);
+        }
+
+        assert_eq!(shaper.process_cubic(-2.0), -1.0);
+        assert_eq!(shaper.process_cubic(2.0), 1.0);
+    }
+
+    #[test]
+    fn endpoints() {
+        let curve = [0.1, 0.4, 0.9];
+        let shaper = TableShaper::new(from_slice(&curve[..]));
+
+        assert_eq!(shaper.process(-1.0), 0.1);
+        assert_eq!(shaper.process(0.0), 0.4);
+        assert_eq!(shaper.process(1.0), 0.9);
+        assert_eq!(shaper.process(0.5), 0.65);
+    }
+
+    #[test]
+    fn short_tables() {
+        let single = [0.3];
+        let shaper = TableShaper::new(from_slice(&single[..]));
+        assert_eq!(shaper.process(0.7), 0.3);
+
+        let empty: [f32; 0] = [];
+        let shaper = TableShaper::new(from_slice(&empty[..]));
+        assert_eq!(shaper.process(0.7), 0.0);
+        assert_eq!(shaper.process_cubic(0.7), 0.0);
+    }
+}